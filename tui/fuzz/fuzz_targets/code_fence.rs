@@ -0,0 +1,12 @@
+//! Split arbitrary post content into text and fenced code blocks
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use whoami_tui::components::render_post_lines;
+use whoami_tui::widgets::CodeBlock;
+
+fuzz_target!(|input: (u8, &str, &str)| {
+    let (width, language, content) = input;
+    render_post_lines(content, width as usize);
+    CodeBlock::new(content).language(language).width(width as u16).to_lines();
+});
@@ -0,0 +1,22 @@
+//! Wrap and render arbitrary Markdown at an arbitrary width
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use whoami_tui::widgets::{render_markdown_line, render_markdown_wrapped, wrap_text, Markdown};
+
+fuzz_target!(|input: (u8, &str)| {
+    let (width, text) = input;
+    let width = width as usize;
+    for line in wrap_text(text, width) {
+        render_markdown_line(&line);
+    }
+    for line in text.lines() {
+        for (row, _) in render_markdown_wrapped(line, width) {
+            // Narrower than a list marker and a wide character nothing fits
+            assert!(width < 5 || row.width() <= width, "{row} is wider than {width}");
+        }
+    }
+    let markdown = Markdown::wrapped(text, width);
+    markdown.render_all();
+    markdown.headings();
+});
@@ -1,18 +1,28 @@
 use std::collections::HashMap;
 use std::env;
+use std::fs::File;
+use std::io;
 use std::net::{IpAddr, SocketAddr};
+use std::os::fd::{AsRawFd, FromRawFd};
 use std::process::Stdio;
-use std::sync::Arc;
+use std::ptr;
+use std::sync::{Arc, Mutex as SyncMutex};
 use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
 use rand::rngs::OsRng;
 use russh::keys::{Algorithm, PrivateKey};
-use russh::server::{Auth, Msg, Session};
-use russh::{Channel, ChannelId, CryptoVec};
+use russh::server::{Auth, Msg, Server as _, Session};
+use russh::{Channel, ChannelId, CryptoVec, Pty};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
-use tokio::process::{ChildStdin, Command};
+use tokio::net::TcpListener;
+use tokio::process::Command;
 use tokio::sync::{Mutex, OwnedSemaphorePermit, Semaphore};
+use whoami_tui::export::{self, export_command};
+use whoami_tui::recording::{Recorder, RecordingConfig};
+use whoami_tui::render::render_command;
+use whoami_tui::route::Route;
+use whoami_tui::sftp::SftpSession;
 
 const DEFAULT_PORT: u16 = 2222;
 const DEFAULT_MAX_SESSIONS: usize = 40;
@@ -20,6 +30,17 @@ const DEFAULT_RATE_LIMIT_PER_MIN: usize = 30;
 const DEFAULT_IDLE_TIMEOUT_SECS: u64 = 180;
 const DEFAULT_MAX_SESSION_SECS: u64 = 900;
 
+/// Client environment variables accepted for the child session; they only
+/// describe the visitor's terminal
+const TERM_ENV: &[&str] = &[
+    "COLORTERM",
+    "TERM_PROGRAM",
+    "LC_TERMINAL",
+    "VTE_VERSION",
+    "WT_SESSION",
+    "FORCE_HYPERLINK",
+];
+
 #[derive(Clone)]
 struct ServerState {
     max_sessions: Arc<Semaphore>,
@@ -27,10 +48,12 @@ struct ServerState {
     per_ip_rate_limit_per_min: usize,
     idle_timeout: Duration,
     max_session_duration: Duration,
+    /// Where shell sessions are recorded, if anywhere (`RECORD_DIR`)
+    recording: Option<Arc<RecordingConfig>>,
 }
 
 impl ServerState {
-    fn allow_connection(&self, peer: Option<SocketAddr>) -> bool {
+    async fn allow_connection(&self, peer: Option<SocketAddr>) -> bool {
         let Some(ip) = peer.map(|p| p.ip()) else {
             return true;
         };
@@ -38,7 +61,7 @@ impl ServerState {
         let now = Instant::now();
         let cutoff = now - Duration::from_secs(60);
 
-        let mut guard = self.per_ip_window.blocking_lock();
+        let mut guard = self.per_ip_window.lock().await;
         let bucket = guard.entry(ip).or_default();
         bucket.retain(|t| *t >= cutoff);
 
@@ -63,10 +86,16 @@ impl russh::server::Server for SshServer {
             state: self.state.clone(),
             peer_addr,
             session_permit: None,
+            route: None,
+            pending_channel: None,
+            pty_size: (80, 24),
+            term_env: Vec::new(),
+            pty_master: None,
             child_stdin: None,
             started_at: Instant::now(),
             last_activity: Arc::new(Mutex::new(Instant::now())),
             active_channel: None,
+            recorder: None,
         }
     }
 }
@@ -75,33 +104,84 @@ struct ClientHandler {
     state: ServerState,
     peer_addr: Option<SocketAddr>,
     session_permit: Option<OwnedSemaphorePermit>,
-    child_stdin: Option<ChildStdin>,
+    /// Deep link named by the SSH username, e.g. `blog-my-post`
+    route: Option<Route>,
+    /// Session channel waiting for a shell or exec request
+    pending_channel: Option<Channel<Msg>>,
+    /// Terminal size requested by the client, as (columns, rows)
+    pty_size: (u16, u16),
+    /// Client variables describing its terminal, passed to the child so it
+    /// can tell which features (e.g. hyperlinks) the terminal supports
+    term_env: Vec<(String, String)>,
+    /// Master side of the child's pseudo-terminal, kept for resizes
+    pty_master: Option<File>,
+    child_stdin: Option<tokio::fs::File>,
     started_at: Instant,
     last_activity: Arc<Mutex<Instant>>,
     active_channel: Option<ChannelId>,
+    /// Recording of the shell session, shared with the task copying output
+    recorder: Option<Arc<SyncMutex<Recorder>>>,
 }
 
 impl ClientHandler {
     async fn spawn_tui_process(
         &mut self,
         channel: Channel<Msg>,
+        route: Option<Route>,
         session: &mut Session,
     ) -> Result<(), anyhow::Error> {
         if self.child_stdin.is_some() {
             return Ok(());
         }
 
+        // The TUI needs a real terminal for raw mode and size queries
+        let (cols, rows) = self.pty_size;
+        let (master, slave) = open_pty(cols, rows).context("allocate child pty")?;
+
         let exe = std::env::current_exe().context("resolve current executable path")?;
-        let mut child = Command::new(exe)
+        let mut command = Command::new(exe);
+        command
             .arg("--child-session")
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::null())
-            .spawn()
-            .context("spawn child TUI session process")?;
+            .envs(self.term_env.iter().cloned())
+            // Limits for the countdown in the status bar
+            .env("SSH_IDLE_TIMEOUT_SECS", self.state.idle_timeout.as_secs().to_string())
+            .env(
+                "SSH_MAX_SESSION_SECS",
+                self.state.max_session_duration.as_secs().to_string(),
+            );
+        if let Some(route) = route {
+            command.arg("--open").arg(route.to_string());
+        }
+        // The session is recorded here, not by the child
+        for name in RecordingConfig::ENV {
+            command.env_remove(name);
+        }
+        command
+            .stdin(Stdio::from(slave.try_clone()?))
+            .stdout(Stdio::from(slave.try_clone()?))
+            .stderr(Stdio::from(slave));
+        // SAFETY: only async-signal-safe calls between fork and exec
+        unsafe {
+            command.pre_exec(|| {
+                // New session with the pty as its controlling terminal
+                if libc::setsid() == -1 || libc::ioctl(0, libc::TIOCSCTTY, 0) == -1 {
+                    return Err(io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+        command.spawn().context("spawn child TUI session process")?;
 
-        let stdin = child.stdin.take().context("capture child stdin")?;
-        let mut stdout = child.stdout.take().context("capture child stdout")?;
+        if let Some(config) = &self.state.recording {
+            match Recorder::create(config, cols, rows) {
+                Ok(recorder) => self.recorder = Some(Arc::new(SyncMutex::new(recorder))),
+                Err(err) => eprintln!("Not recording session: {err}"),
+            }
+        }
+        let recorder = self.recorder.clone();
+
+        let stdin = tokio::fs::File::from_std(master.try_clone()?);
+        let mut stdout = tokio::fs::File::from_std(master.try_clone()?);
 
         let handle = session.handle();
         let channel_id = channel.id();
@@ -113,22 +193,32 @@ impl ClientHandler {
         tokio::spawn(async move {
             let mut buf = vec![0_u8; 8192];
             loop {
-                {
+                let deadline = {
                     let last = *last_activity.lock().await;
-                    if last.elapsed() > idle_timeout || started_at.elapsed() > max_session_duration {
-                        let _ = handle.eof(channel_id).await;
-                        let _ = handle.close(channel_id).await;
-                        break;
-                    }
+                    (last + idle_timeout).min(started_at + max_session_duration)
+                };
+                if Instant::now() >= deadline {
+                    let _ = handle.eof(channel_id).await;
+                    let _ = handle.close(channel_id).await;
+                    break;
                 }
 
-                match stdout.read(&mut buf).await {
+                // Wake at the deadline even if the child draws nothing
+                let read = tokio::time::timeout_at(deadline.into(), stdout.read(&mut buf));
+                let Ok(read) = read.await else {
+                    // Input since may have pushed the idle deadline back
+                    continue;
+                };
+                match read {
                     Ok(0) => {
                         let _ = handle.eof(channel_id).await;
                         let _ = handle.close(channel_id).await;
                         break;
                     }
                     Ok(read) => {
+                        if let Some(recorder) = &recorder {
+                            lock(recorder).output(&buf[..read]);
+                        }
                         if handle
                             .data(channel_id, CryptoVec::from_slice(&buf[..read]))
                             .await
@@ -147,37 +237,216 @@ impl ClientHandler {
         });
 
         self.active_channel = Some(channel_id);
+        self.pty_master = Some(master);
         self.child_stdin = Some(stdin);
         Ok(())
     }
+
+    /// Send `bytes`, e.g. the resume in some format, as the output of an
+    /// exec command
+    fn send_export(
+        &mut self,
+        channel: ChannelId,
+        bytes: Vec<u8>,
+        session: &mut Session,
+    ) -> Result<()> {
+        if self.take_pending(channel).is_none() {
+            session.channel_failure(channel)?;
+            return Ok(());
+        }
+        session.channel_success(channel)?;
+
+        // The handle waits for the client's window, unlike the session
+        let handle = session.handle();
+        tokio::spawn(async move {
+            if handle.data(channel, CryptoVec::from(bytes)).await.is_ok() {
+                let _ = handle.exit_status_request(channel, 0).await;
+            }
+            let _ = handle.eof(channel).await;
+            let _ = handle.close(channel).await;
+        });
+        Ok(())
+    }
+
+    /// Claim the session channel opened earlier, if `channel` is that one
+    fn take_pending(&mut self, channel: ChannelId) -> Option<Channel<Msg>> {
+        match &self.pending_channel {
+            Some(pending) if pending.id() == channel => self.pending_channel.take(),
+            _ => None,
+        }
+    }
 }
 
 impl russh::server::Handler for ClientHandler {
     type Error = anyhow::Error;
 
-    async fn auth_none(&mut self, _user: &str) -> Result<Auth, Self::Error> {
-        if !self.state.allow_connection(self.peer_addr) {
-            return Ok(Auth::Reject);
+    /// Visitors are told before logging in when sessions are recorded
+    async fn authentication_banner(&mut self) -> Result<Option<String>, Self::Error> {
+        Ok(self.state.recording.as_ref().map(|config| config.banner()))
+    }
+
+    async fn auth_none(&mut self, user: &str) -> Result<Auth, Self::Error> {
+        if !self.state.allow_connection(self.peer_addr).await {
+            return Ok(Auth::reject());
         }
 
+        self.route = Route::from_ssh_user(user);
+
         match Arc::clone(&self.state.max_sessions).try_acquire_owned() {
             Ok(permit) => {
                 self.session_permit = Some(permit);
                 Ok(Auth::Accept)
             }
-            Err(_) => Ok(Auth::Reject),
+            Err(_) => Ok(Auth::reject()),
         }
     }
 
     async fn channel_open_session(
         &mut self,
         channel: Channel<Msg>,
-        session: &mut Session,
+        _session: &mut Session,
     ) -> Result<bool, Self::Error> {
-        self.spawn_tui_process(channel, session).await?;
+        // The TUI starts once the client asks for a shell or a command
+        self.pending_channel = Some(channel);
         Ok(true)
     }
 
+    #[allow(clippy::too_many_arguments)]
+    async fn pty_request(
+        &mut self,
+        channel: ChannelId,
+        term: &str,
+        col_width: u32,
+        row_height: u32,
+        _pix_width: u32,
+        _pix_height: u32,
+        _modes: &[(Pty, u32)],
+        session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        self.pty_size = clamp_size(col_width, row_height);
+        if !term.is_empty() {
+            self.term_env.push(("TERM".to_string(), term.to_string()));
+        }
+        session.channel_success(channel)?;
+        Ok(())
+    }
+
+    async fn env_request(
+        &mut self,
+        channel: ChannelId,
+        variable_name: &str,
+        variable_value: &str,
+        session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        if TERM_ENV.contains(&variable_name) {
+            self.term_env
+                .push((variable_name.to_string(), variable_value.to_string()));
+            session.channel_success(channel)?;
+        } else {
+            session.channel_failure(channel)?;
+        }
+        Ok(())
+    }
+
+    async fn window_change_request(
+        &mut self,
+        _channel: ChannelId,
+        col_width: u32,
+        row_height: u32,
+        _pix_width: u32,
+        _pix_height: u32,
+        _session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        self.pty_size = clamp_size(col_width, row_height);
+        if let Some(master) = &self.pty_master {
+            let (cols, rows) = self.pty_size;
+            resize_pty(master, cols, rows)?;
+            if let Some(recorder) = &self.recorder {
+                lock(recorder).resize(cols, rows);
+            }
+        }
+        Ok(())
+    }
+
+    async fn shell_request(
+        &mut self,
+        channel: ChannelId,
+        session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        match self.take_pending(channel) {
+            Some(pending) => {
+                let route = self.route.clone();
+                self.spawn_tui_process(pending, route, session).await?;
+                session.channel_success(channel)?;
+            }
+            None => session.channel_failure(channel)?,
+        }
+        Ok(())
+    }
+
+    /// `ssh -t host /blog/my-post` opens the route given as the command;
+    /// `ssh host resume --format pdf` writes out the resume instead, and
+    /// `ssh host render --view /blog/my-post` a screenshot of the route
+    async fn exec_request(
+        &mut self,
+        channel: ChannelId,
+        data: &[u8],
+        session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        let command = String::from_utf8_lossy(data);
+        if command.trim() == "contact.vcf" {
+            return self.send_export(channel, export::vcard().into_bytes(), session);
+        }
+        let parsed = match export_command(&command) {
+            Some(Ok(format)) => {
+                return self.send_export(channel, export::resume(format).to_vec(), session);
+            }
+            Some(Err(err)) => Err(err),
+            None => match render_command(&command).map(|request| request?.render()) {
+                Some(Ok(screen)) => return self.send_export(channel, screen.into_bytes(), session),
+                Some(Err(err)) => Err(err),
+                None => command.parse::<Route>(),
+            },
+        };
+        let route = match parsed {
+            Ok(route) => route,
+            Err(err) => {
+                let message = format!("{err}\r\n");
+                session.extended_data(channel, 1, CryptoVec::from(message))?;
+                session.channel_failure(channel)?;
+                session.close(channel)?;
+                return Ok(());
+            }
+        };
+
+        match self.take_pending(channel) {
+            Some(pending) => {
+                self.spawn_tui_process(pending, Some(route), session).await?;
+                session.channel_success(channel)?;
+            }
+            None => session.channel_failure(channel)?,
+        }
+        Ok(())
+    }
+
+    /// `sftp` and `scp` browse the resume downloads, read-only
+    async fn subsystem_request(
+        &mut self,
+        channel: ChannelId,
+        name: &str,
+        session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        match self.take_pending(channel) {
+            Some(pending) if name == "sftp" => {
+                session.channel_success(channel)?;
+                let deadline = self.started_at + self.state.max_session_duration;
+                tokio::spawn(serve_sftp(pending, deadline));
+            }
+            _ => session.channel_failure(channel)?,
+        }
+        Ok(())
+    }
+
     async fn data(
         &mut self,
         channel: ChannelId,
@@ -189,13 +458,54 @@ impl russh::server::Handler for ClientHandler {
         }
 
         if let Some(stdin) = &mut self.child_stdin {
+            if let Some(recorder) = &self.recorder {
+                lock(recorder).input(data);
+            }
             stdin.write_all(data).await?;
+            stdin.flush().await?;
             *self.last_activity.lock().await = Instant::now();
         }
         Ok(())
     }
 }
 
+/// Run the SFTP subsystem on `channel` until the client stops sending or
+/// the session reaches `deadline`
+///
+/// The server talks to one end of an in-memory pipe while this copies the
+/// other end to and from the channel, so the exit status can still be sent
+/// once the client is done.
+async fn serve_sftp(mut channel: Channel<Msg>, deadline: Instant) {
+    let (pipe, server_end) = tokio::io::duplex(64 * 1024);
+    russh_sftp::server::run(server_end, SftpSession::new()).await;
+
+    let (mut from_server, mut to_server) = tokio::io::split(pipe);
+    let mut writer = channel.make_writer();
+    let mut reader = channel.make_reader();
+    let incoming = async {
+        let _ = tokio::io::copy(&mut reader, &mut to_server).await;
+        let _ = to_server.shutdown().await;
+    };
+    let outgoing = tokio::io::copy(&mut from_server, &mut writer);
+    let finished = tokio::time::timeout_at(deadline.into(), async {
+        tokio::join!(incoming, outgoing)
+    })
+    .await
+    .is_ok();
+    drop(reader);
+
+    if finished {
+        let _ = channel.exit_status(0).await;
+    }
+    let _ = channel.eof().await;
+    let _ = channel.close().await;
+}
+
+/// The session's recorder, even if a panic poisoned its lock
+fn lock(recorder: &SyncMutex<Recorder>) -> std::sync::MutexGuard<'_, Recorder> {
+    recorder.lock().unwrap_or_else(|e| e.into_inner())
+}
+
 fn parse_env_u16(key: &str, default: u16) -> u16 {
     env::var(key)
         .ok()
@@ -217,6 +527,64 @@ fn parse_env_u64(key: &str, default: u64) -> u64 {
         .unwrap_or(default)
 }
 
+/// Terminal size from an SSH request, with zero meaning "unknown"
+fn clamp_size(cols: u32, rows: u32) -> (u16, u16) {
+    let clamp = |v: u32, default: u16| match v {
+        0 => default,
+        v => v.min(u16::MAX as u32) as u16,
+    };
+    (clamp(cols, 80), clamp(rows, 24))
+}
+
+fn winsize(cols: u16, rows: u16) -> libc::winsize {
+    libc::winsize {
+        ws_row: rows,
+        ws_col: cols,
+        ws_xpixel: 0,
+        ws_ypixel: 0,
+    }
+}
+
+/// Allocate a pseudo-terminal, returning its (master, slave) ends
+fn open_pty(cols: u16, rows: u16) -> io::Result<(File, File)> {
+    let mut master = 0;
+    let mut slave = 0;
+    let size = winsize(cols, rows);
+    // SAFETY: openpty writes two new descriptors into the provided ints
+    let rc = unsafe { libc::openpty(&mut master, &mut slave, ptr::null_mut(), ptr::null(), &size) };
+    if rc != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: both descriptors were just opened and are owned by nobody else
+    let (master, slave) = unsafe { (File::from_raw_fd(master), File::from_raw_fd(slave)) };
+
+    // Children must not inherit the master: the terminal only hangs up,
+    // ending the child, once every copy of it is closed
+    for fd in [master.as_raw_fd(), slave.as_raw_fd()] {
+        // SAFETY: F_SETFD only changes the flags of a descriptor we own
+        if unsafe { libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) } == -1 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok((master, slave))
+}
+
+/// Propagate a client window resize to the child's terminal
+fn resize_pty(master: &File, cols: u16, rows: u16) -> io::Result<()> {
+    let size = winsize(cols, rows);
+    // SAFETY: TIOCSWINSZ reads a winsize from the pointer
+    if unsafe { libc::ioctl(master.as_raw_fd(), libc::TIOCSWINSZ, &size) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Route passed to a child session with `--open <path>`
+fn open_route() -> Result<Option<Route>> {
+    let mut args = env::args().skip_while(|arg| arg != "--open").skip(1);
+    args.next().map(|path| path.parse()).transpose()
+}
+
 fn host_key() -> Result<PrivateKey> {
     let key = PrivateKey::random(&mut OsRng, Algorithm::Ed25519)
         .context("generate ephemeral ed25519 host key")?;
@@ -226,7 +594,8 @@ fn host_key() -> Result<PrivateKey> {
 #[tokio::main]
 async fn main() -> Result<()> {
     if env::args().any(|arg| arg == "--child-session") {
-        return whoami_tui::runtime::run_stdio_tui().map_err(Into::into);
+        whoami_tui::links::set_remote(true);
+        return whoami_tui::runtime::run_stdio_tui(open_route()?).map_err(Into::into);
     }
 
     let port = parse_env_u16("PORT", DEFAULT_PORT);
@@ -247,7 +616,11 @@ async fn main() -> Result<()> {
         per_ip_rate_limit_per_min: per_ip_rate,
         idle_timeout,
         max_session_duration,
+        recording: RecordingConfig::from_env().map(Arc::new),
     };
+    if let Some(config) = &state.recording {
+        eprintln!("Recording shell sessions to {}", config.dir.display());
+    }
 
     let mut config = russh::server::Config {
         auth_rejection_time: Duration::from_millis(200),
@@ -257,9 +630,15 @@ async fn main() -> Result<()> {
     };
     config.keys.push(host_key()?);
 
-    let addr = format!("0.0.0.0:{port}");
-    let server = SshServer { state };
-    russh::server::run(Arc::new(config), addr, server)
+    // Port 0 picks a free port, which is logged for whoever started us
+    let listener = TcpListener::bind(("0.0.0.0", port))
+        .await
+        .with_context(|| format!("bind port {port}"))?;
+    println!("Listening on {}", listener.local_addr()?);
+
+    let mut server = SshServer { state };
+    server
+        .run_on_socket(Arc::new(config), &listener)
         .await
         .context("run ssh server")?;
     Ok(())
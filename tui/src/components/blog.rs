@@ -1,18 +1,27 @@
 //! Blog view component with list and detail modes
 
+use std::collections::HashMap;
+
 use ratatui::layout::Rect;
 use ratatui::style::Modifier;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::Paragraph;
-use tuirealm::command::{Cmd, CmdResult, Direction};
-use tuirealm::event::{Key, KeyEvent, KeyModifiers};
-use tuirealm::props::{AttrValue, Attribute, Props};
+use tuirealm::command::{Cmd, CmdResult, Direction, Position};
+use tuirealm::props::{AttrValue, Attribute, PropPayload, PropValue, Props};
 use tuirealm::{Component, Event, Frame, MockComponent, NoUserEvent, State, StateValue};
 
 use crate::content::{Post, POSTS};
+use crate::keymap::{keymap, route, InputMode, Keymap};
+use crate::links::{self, hyperlinks_enabled, SpanLinks};
 use crate::msg::{Msg, ViewId};
+use crate::route::Route;
+use crate::search::{Find, FIND_ATTR};
 use crate::styles;
-use crate::widgets::{render_markdown_line, wrap_text, CodeBlock, PageLayout, SelectableItem, TagList};
+use crate::widgets::{
+    markdown_heading, markdown_headings, markdown_links, markdown_text, render_markdown_wrapped,
+    render_preview, stacked, Breakpoint, CodeBlock, FindState, Heading, HitMap, PageLayout,
+    Passage, ScrollList, SelectableItem, TagList, Toc,
+};
 
 /// Blog display mode
 #[derive(Clone, Copy, PartialEq)]
@@ -21,20 +30,73 @@ pub enum BlogMode {
     Detail,
 }
 
+/// Order of the post list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PostSort {
+    Newest,
+    Oldest,
+    Title,
+}
+
+impl PostSort {
+    const ALL: [PostSort; 3] = [PostSort::Newest, PostSort::Oldest, PostSort::Title];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PostSort::Newest => "newest",
+            PostSort::Oldest => "oldest",
+            PostSort::Title => "title",
+        }
+    }
+
+    fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|s| *s == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    fn from_label(label: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.label() == label)
+    }
+}
+
 /// Represents a processed content line - either regular text or part of a code block
 enum ContentLine {
-    Text(String),
-    CodeBlockLine(Line<'static>),
+    /// A line of text and the URLs of its links
+    Text(Line<'static>, SpanLinks),
+    /// A line of the code block with this index in the post
+    CodeBlockLine(Line<'static>, usize),
 }
 
 /// Blog MockComponent - handles the visual representation
 pub struct BlogMock {
     props: Props,
     mode: BlogMode,
+    /// Position in `visible`
     cursor: usize,
     selected_idx: Option<usize>,
+    sort: PostSort,
+    /// Tags a post must all have to be listed
+    filter: Vec<&'static str>,
+    /// Indices into `POSTS`, filtered and sorted
+    visible: Vec<usize>,
+    /// Whether the tag bar (list) or the post's tags (detail) have focus
+    tag_focus: bool,
+    /// Focused tag, indexing `all_tags()` in the list or the post's tags
+    tag_cursor: usize,
+    scroll: ScrollList,
+    /// List rows of each post on screen at the last render, by position in
+    /// `visible`
+    hits: HitMap<usize>,
     viewport: usize,
     max_scroll: usize,
+    /// Lines of the open post on screen at the last render
+    page_height: usize,
+    /// Wrap width of the open post, to re-index matches on resize
+    wrap_width: usize,
+    /// Search phrase highlighted in the open post
+    find: Option<FindState>,
+    /// Table of contents of the open post
+    toc: Toc,
 }
 
 impl Default for BlogMock {
@@ -50,8 +112,19 @@ impl BlogMock {
             mode: BlogMode::List,
             cursor: 0,
             selected_idx: None,
+            sort: PostSort::Newest,
+            filter: Vec::new(),
+            visible: sorted_posts(PostSort::Newest, &[]),
+            tag_focus: false,
+            tag_cursor: 0,
+            scroll: ScrollList::new(),
+            hits: HitMap::new(),
             viewport: 0,
             max_scroll: 0,
+            page_height: 0,
+            wrap_width: 0,
+            find: None,
+            toc: Toc::new(),
         }
     }
 
@@ -73,7 +146,7 @@ impl BlogMock {
     fn cursor_down(&mut self) {
         match self.mode {
             BlogMode::List => {
-                if self.cursor < POSTS.len().saturating_sub(1) {
+                if self.cursor < self.visible.len().saturating_sub(1) {
                     self.cursor += 1;
                 }
             }
@@ -85,51 +158,393 @@ impl BlogMock {
         }
     }
 
+    fn page_up(&mut self) {
+        match self.mode {
+            BlogMode::List => self.cursor = self.cursor.saturating_sub(self.scroll.page()),
+            BlogMode::Detail => self.viewport = self.viewport.saturating_sub(10),
+        }
+    }
+
+    fn page_down(&mut self) {
+        match self.mode {
+            BlogMode::List => {
+                self.cursor =
+                    (self.cursor + self.scroll.page()).min(self.visible.len().saturating_sub(1));
+            }
+            BlogMode::Detail => self.viewport = (self.viewport + 10).min(self.max_scroll),
+        }
+    }
+
+    fn first(&mut self) {
+        match self.mode {
+            BlogMode::List => self.cursor = 0,
+            BlogMode::Detail => self.viewport = 0,
+        }
+    }
+
+    fn last(&mut self) {
+        match self.mode {
+            BlogMode::List => self.cursor = self.visible.len().saturating_sub(1),
+            BlogMode::Detail => self.viewport = self.max_scroll,
+        }
+    }
+
     fn select(&mut self) {
-        if self.mode == BlogMode::List && !POSTS.is_empty() {
-            self.selected_idx = Some(self.cursor);
-            self.mode = BlogMode::Detail;
-            self.viewport = 0;
-            self.max_scroll = 0;
+        match (self.mode, self.tag_focus) {
+            (BlogMode::List, true) => self.toggle_tag(),
+            (BlogMode::Detail, true) => self.browse_tag(),
+            (BlogMode::List, false) => {
+                if let Some(&idx) = self.visible.get(self.cursor) {
+                    self.open(idx);
+                }
+            }
+            (BlogMode::Detail, false) => {}
         }
     }
 
-    /// Returns true if we went back within the component (detail -> list)
-    fn back(&mut self) -> bool {
+    /// Select the clicked post in the list, or open it if already selected;
+    /// in a post, jump to the clicked entry of the contents
+    fn click(&mut self, column: u16, row: u16) {
         if self.mode == BlogMode::Detail {
+            if let Some(line) = self.toc.click(column, row) {
+                self.viewport = line;
+            }
+            return;
+        }
+        let Some(pos) = self.hits.at(column, row) else {
+            return;
+        };
+        if pos == self.cursor && !self.tag_focus {
+            self.select();
+        } else {
+            self.cursor = pos;
+            self.tag_focus = false;
+        }
+    }
+
+    /// Open the post at `idx` in `POSTS`
+    fn open(&mut self, idx: usize) {
+        self.selected_idx = Some(idx);
+        self.mode = BlogMode::Detail;
+        self.viewport = 0;
+        self.max_scroll = 0;
+        self.tag_focus = false;
+        self.find = None;
+        // Unwrapped until the first render, which knows the width
+        self.toc.set_headings(markdown_headings(POSTS[idx].content.lines()));
+        self.toc.close();
+    }
+
+    fn next_section(&mut self) {
+        let line = self.toc.next_line(self.viewport);
+        match line {
+            Some(line) if self.mode == BlogMode::Detail => {
+                self.viewport = line.min(self.max_scroll);
+            }
+            _ => {}
+        }
+    }
+
+    fn prev_section(&mut self) {
+        let line = self.toc.prev_line(self.viewport);
+        match line {
+            Some(line) if self.mode == BlogMode::Detail => self.viewport = line,
+            _ => {}
+        }
+    }
+
+    fn toggle_toc(&mut self) {
+        if self.mode == BlogMode::Detail {
+            self.toc.toggle(self.viewport);
+        }
+    }
+
+    /// Post under the list cursor, as an index into `POSTS`
+    fn cursor_post(&self) -> Option<usize> {
+        self.visible.get(self.cursor).copied()
+    }
+
+    /// Re-filter and re-sort the list, keeping the cursor on the same post
+    fn refresh_list(&mut self) {
+        let current = self.cursor_post();
+        self.visible = sorted_posts(self.sort, &self.filter);
+        self.cursor = current
+            .and_then(|idx| self.visible.iter().position(|&i| i == idx))
+            .unwrap_or(0);
+    }
+
+    fn cycle_sort(&mut self) {
+        self.sort = self.sort.next();
+        self.refresh_list();
+    }
+
+    /// Toggle focus between the list and the tags
+    fn focus_tags(&mut self) {
+        self.tag_focus = !self.tag_focus;
+        self.tag_cursor = 0;
+    }
+
+    /// Number of tags the tag cursor moves over in the current mode
+    fn tag_count(&self) -> usize {
+        match (self.mode, self.selected_idx) {
+            (BlogMode::Detail, Some(idx)) => POSTS[idx].tags.len(),
+            _ => all_tags().len(),
+        }
+    }
+
+    fn tag_next(&mut self) {
+        if self.tag_focus && self.tag_count() > 0 {
+            self.tag_cursor = (self.tag_cursor + 1) % self.tag_count();
+        }
+    }
+
+    fn tag_prev(&mut self) {
+        if self.tag_focus && self.tag_count() > 0 {
+            self.tag_cursor = (self.tag_cursor + self.tag_count() - 1) % self.tag_count();
+        }
+    }
+
+    /// Add or remove the focused tag from the list filter
+    fn toggle_tag(&mut self) {
+        let Some(&(tag, _)) = all_tags().get(self.tag_cursor) else {
+            return;
+        };
+        match self.filter.iter().position(|t| *t == tag) {
+            Some(pos) => {
+                self.filter.remove(pos);
+            }
+            None => self.filter.push(tag),
+        }
+        self.refresh_list();
+    }
+
+    /// Leave the open post for the list of posts sharing its focused tag
+    fn browse_tag(&mut self) {
+        let Some(tag) = self
+            .selected_idx
+            .and_then(|idx| POSTS[idx].tags.get(self.tag_cursor).copied())
+        else {
+            return;
+        };
+        self.tag_focus = false;
+        self.back();
+        self.filter = vec![tag];
+        self.refresh_list();
+        self.tag_focus = true;
+        self.tag_cursor = all_tags().iter().position(|(t, _)| *t == tag).unwrap_or(0);
+    }
+
+    /// Restore a history snapshot; the viewport is clamped on the next render
+    ///
+    /// `post` indexes `POSTS`, so deep links can build a snapshot without
+    /// knowing the list order.
+    fn restore(&mut self, post: usize, detail: bool, viewport: usize) {
+        self.refresh_list();
+        self.cursor = self.visible.iter().position(|&i| i == post).unwrap_or(0);
+        if detail && post < POSTS.len() {
+            self.open(post);
+            self.viewport = viewport;
+        }
+    }
+
+    /// Restore the list order and tag filter from a snapshot
+    fn restore_filter(&mut self, sort: &str, tags: &str) {
+        self.sort = PostSort::from_label(sort).unwrap_or(PostSort::Newest);
+        self.filter = all_tags()
+            .into_iter()
+            .map(|(tag, _)| tag)
+            .filter(|tag| tags.split(',').any(|t| t == *tag))
+            .collect();
+    }
+
+    /// Returns true if we went back within the component (tags -> list,
+    /// detail -> list)
+    fn back(&mut self) -> bool {
+        if self.tag_focus {
+            self.tag_focus = false;
+            true
+        } else if self.mode == BlogMode::Detail {
             self.mode = BlogMode::List;
             self.selected_idx = None;
             self.viewport = 0;
             self.max_scroll = 0;
+            self.find = None;
             true
         } else {
             false
         }
     }
 
-    fn render_list(&self, frame: &mut Frame, area: Rect) {
+    /// Web address of the open post, or of the one under the cursor
+    fn web_url(&self) -> Option<String> {
+        let post = POSTS.get(self.selected_idx.or(self.cursor_post())?)?;
+        Some(Route::BlogPost(post.slug.to_string()).web_url())
+    }
+
+    /// Source of the first code block on screen in the open post
+    fn visible_code(&self) -> Option<String> {
+        let post = POSTS.get(self.selected_idx?)?;
+        let (lines, ..) = render_post_content(post.content, self.wrap_width);
+        let end = (self.viewport + self.page_height).min(lines.len());
+        let block = lines.get(self.viewport..end)?.iter().find_map(|line| match line {
+            ContentLine::CodeBlockLine(_, block) => Some(*block),
+            ContentLine::Text(..) => None,
+        })?;
+        code_block(post, block)
+    }
+
+    /// URL of link `number` (1-based) in the open post
+    fn link_url(&self, number: usize) -> Option<String> {
+        let post = POSTS.get(self.selected_idx?)?;
+        let links = markdown_links(post.content);
+        links.get(number.checked_sub(1)?).map(|link| link.url.clone())
+    }
+
+    fn find_next(&mut self) {
+        if let Some(find) = &mut self.find {
+            find.next();
+        }
+    }
+
+    fn find_prev(&mut self) {
+        if let Some(find) = &mut self.find {
+            find.prev();
+        }
+    }
+
+    /// Footer while the tags have focus; the list footer otherwise
+    fn help(&self) -> String {
+        let keymap = keymap();
+        if !self.tag_focus {
+            return keymap.help(ViewId::Blog, InputMode::Normal);
+        }
+
+        let keys = |msg: &Msg| keymap.keys(ViewId::Blog, InputMode::Normal, msg);
+        let action = match self.mode {
+            BlogMode::List => "toggle",
+            BlogMode::Detail => "show posts",
+        };
+        [
+            keys(&Msg::FocusNext).map(|k| format!("{k} next tag")),
+            keys(&Msg::FocusPrev).map(|k| format!("{k} prev tag")),
+            keys(&Msg::Select).map(|k| format!("{k} {action}")),
+            tags_done_keys(&keymap).map(|k| format!("{k} done")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" • ")
+    }
+
+    /// Sort order, post count and one chip per tag, wrapped to `width`
+    fn tag_bar(&self, width: usize) -> Vec<Line<'static>> {
+        let mut lines = vec![Line::from(vec![
+            Span::styled(
+                format!("Sorted by {}", self.sort.label()),
+                styles::muted(),
+            ),
+            Span::styled(
+                format!("  ·  {} of {} posts", self.visible.len(), POSTS.len()),
+                styles::muted(),
+            ),
+        ])];
+
+        let mut row: Vec<Span> = Vec::new();
+        let mut row_width = 0;
+        for (i, (tag, _)) in all_tags().into_iter().enumerate() {
+            let selected = self.filter.contains(&tag);
+            let count = if selected {
+                self.visible.len()
+            } else {
+                let mut with_tag = self.filter.clone();
+                with_tag.push(tag);
+                sorted_posts(self.sort, &with_tag).len()
+            };
+            let style = if self.tag_focus && i == self.tag_cursor {
+                styles::tag_focused()
+            } else if selected {
+                styles::tag()
+            } else if count == 0 {
+                styles::border()
+            } else {
+                styles::text()
+            };
+
+            let chip = format!(" {tag} {count} ");
+            let chip_width = chip.chars().count() + 1;
+            if row_width + chip_width > width && !row.is_empty() {
+                lines.push(Line::from(std::mem::take(&mut row)));
+                row_width = 0;
+            }
+            row.push(Span::styled(chip, style));
+            row.push(Span::raw(" "));
+            row_width += chip_width;
+        }
+        if !row.is_empty() {
+            lines.push(Line::from(row));
+        }
+        lines
+    }
+
+    fn render_list(&mut self, frame: &mut Frame, area: Rect) {
+        let breakpoint = Breakpoint::of(area);
+        let content_area = PageLayout::content_area(area);
+        // Leave a column for the scrollbar so the bar wraps the same either way
+        let bar = self.tag_bar(content_area.width.saturating_sub(1) as usize);
+        let list_height = (content_area.height as usize).saturating_sub(bar.len() + 1);
+
+        // Excerpts are left out where space is short, or shown in the preview
+        let brief = breakpoint != Breakpoint::Normal;
+        let items: Vec<Vec<Line>> = self
+            .visible
+            .iter()
+            .enumerate()
+            .map(|(i, &idx)| post_item(&POSTS[idx], i == self.cursor && !self.tag_focus, brief))
+            .collect();
+        let heights: Vec<usize> = items.iter().map(Vec::len).collect();
+        let shown = self.scroll.follow(&heights, self.cursor, list_height);
+        let (position, total) = self.scroll.position(&heights);
+
+        let [list_area, preview_area] = breakpoint.panes(Rect {
+            y: content_area.y + bar.len() as u16 + 1,
+            height: list_height as u16,
+            ..content_area
+        });
+        let preview = match self.cursor_post() {
+            Some(idx) if !preview_area.is_empty() => {
+                post_preview(&POSTS[idx], preview_area.width.saturating_sub(3) as usize)
+            }
+            _ => Vec::new(),
+        };
+
+        self.hits.clear();
+        let rows = stacked(list_area, &heights[shown.clone()]);
+        for (rect, pos) in rows.into_iter().zip(shown.clone()) {
+            self.hits.push(rect, pos);
+        }
+        // Clicking the preview opens the post
+        self.hits.push(preview_area, self.cursor);
+
         PageLayout::new("📝 Blog")
-            .help("↑/k up • ↓/j down • enter read • esc back")
+            .breadcrumbs(["Home"])
+            .help(self.help())
+            .scroll_state(position, total, list_height)
             .render(frame, area, |f, content_area| {
-                let mut lines: Vec<Line> = Vec::new();
-
-                for (i, post) in POSTS.iter().enumerate() {
-                    // Title line with date suffix
-                    let item = SelectableItem::new(post.title)
-                        .selected(i == self.cursor)
-                        .suffix(Span::styled(post.published.to_string(), styles::muted()));
-                    lines.push(item.to_line());
-
-                    // Excerpt line
-                    lines.push(Line::from(vec![
-                        Span::raw("    "),
-                        Span::styled(post.excerpt.to_string(), styles::muted()),
-                    ]));
-                    lines.push(Line::from(""));
+                f.render_widget(Paragraph::new(bar), content_area);
+
+                let mut lines = Vec::new();
+                if self.visible.is_empty() {
+                    lines.push(Line::from(Span::styled(
+                        "  No posts have all of the selected tags",
+                        styles::muted(),
+                    )));
                 }
+                lines.extend(items[shown].iter().flatten().cloned());
+                f.render_widget(Paragraph::new(lines), list_area.intersection(content_area));
 
-                let content = Paragraph::new(lines);
-                f.render_widget(content, content_area);
+                if !preview_area.is_empty() {
+                    render_preview(f, preview_area.intersection(content_area), preview);
+                }
             });
     }
 
@@ -139,12 +554,22 @@ impl BlogMock {
             _ => return,
         };
 
+        self.hits.clear();
         let content_area = PageLayout::content_area(area);
+        let [sidebar, text_area] = self.toc.split(area, content_area);
         // Account for scrollbar (1 col) when calculating wrap width
-        let wrap_width = content_area.width.saturating_sub(2) as usize;
+        let wrap_width = text_area.width.saturating_sub(2) as usize;
 
         // Parse and wrap content with code blocks
-        let content_lines = render_post_content(post, wrap_width);
+        let (content_lines, headings, passages) = render_post_content(post.content, wrap_width);
+        let section = self.toc.current(self.viewport);
+        self.toc.set_headings(headings);
+        // Line offsets shift on rewrap, so go to the start of the section
+        let rewrap = wrap_width != self.wrap_width && self.wrap_width != 0;
+        match section.and_then(|idx| self.toc.line(idx)) {
+            Some(line) if rewrap => self.viewport = line,
+            _ => {}
+        }
 
         // Adjust for the extra header line (date + tags)
         let viewable_height = content_area.height.saturating_sub(1) as usize;
@@ -153,11 +578,30 @@ impl BlogMock {
         // Calculate and store scroll bounds
         self.max_scroll = content_length.saturating_sub(viewable_height);
 
+        // Index search matches on first render and after a resize
+        if let Some(find) = &mut self.find {
+            if find.needs_index() || wrap_width != self.wrap_width {
+                let lines: Vec<Line> = content_lines.iter().map(content_line_to_line).collect();
+                find.index(&lines, &passages);
+            }
+            if let Some(line) = find.take_jump() {
+                self.viewport = line.saturating_sub(2);
+            }
+        }
+        self.wrap_width = wrap_width;
+
         // Clamp viewport to valid range
         self.viewport = self.viewport.min(self.max_scroll);
+        self.page_height = viewable_height;
+
+        let mut help = self.help();
+        if let Some(find) = self.find.as_ref().filter(|_| !self.tag_focus) {
+            help = format!("{} • {help}", find.status());
+        }
 
         PageLayout::new(format!("📝 {}", post.title))
-            .help("↑/k up • ↓/j down • esc back to list")
+            .breadcrumbs(["Home", "Blog"])
+            .help(help)
             .scroll_state(self.viewport, content_length, viewable_height)
             .render(frame, area, |f, content_rect| {
                 // Date and tags on first line
@@ -168,7 +612,16 @@ impl BlogMock {
                     ),
                     Span::raw("  "),
                 ];
-                meta_spans.extend(TagList::from_vec(&post.tags).to_spans());
+                let focused = self.tag_focus.then_some(self.tag_cursor);
+                meta_spans.extend(TagList::from_vec(&post.tags).focused(focused).to_spans());
+
+                // Beside the contents, if shown
+                let indent = text_area.x - content_rect.x;
+                let content_rect = Rect {
+                    x: text_area.x,
+                    width: content_rect.width.saturating_sub(indent),
+                    ..content_rect
+                };
 
                 let meta = Paragraph::new(Line::from(meta_spans));
                 let meta_area = Rect::new(content_rect.x, content_rect.y, content_rect.width, 1);
@@ -183,36 +636,73 @@ impl BlogMock {
                 );
 
                 let end = (self.viewport + viewable_height).min(content_length);
-                let visible_lines: Vec<Line> = content_lines
-                    [self.viewport.min(content_length)..end]
-                    .iter()
-                    .map(content_line_to_line)
+                let visible = &content_lines[self.viewport.min(content_length)..end];
+                let visible_lines: Vec<Line> = visible.iter().map(content_line_to_line).collect();
+                links::place(
+                    text_area,
+                    visible_lines.iter().zip(visible.iter().map(content_line_links)),
+                );
+                let visible_lines: Vec<Line> = (self.viewport..)
+                    .zip(visible_lines)
+                    .map(|(row, line)| match &self.find {
+                        Some(find) => find.highlight(row, line),
+                        None => line,
+                    })
                     .collect();
 
                 let content = Paragraph::new(visible_lines);
                 f.render_widget(content, text_area);
+                self.toc.render_sidebar(f, sidebar, self.viewport);
             });
+        self.toc.render_popup(frame, area, self.viewport);
     }
 }
 
 /// Parse markdown content, wrap text, and render code blocks
-fn render_post_content(post: &Post, wrap_width: usize) -> Vec<ContentLine> {
+///
+/// Also returns the headings with the line each starts on, and the passages
+/// search matches in, as [`crate::search`] finds them.
+fn render_post_content(
+    content: &str,
+    wrap_width: usize,
+) -> (Vec<ContentLine>, Vec<Heading>, Vec<Passage>) {
     let mut result = Vec::new();
+    let mut headings = Vec::new();
+    let mut passages = Vec::new();
+    let mut push_text = |result: &mut Vec<ContentLine>, line: &str| {
+        if let Some((level, title)) = markdown_heading(line) {
+            headings.push(Heading {
+                level,
+                title: title.to_string(),
+                line: result.len(),
+            });
+        }
+        let wrapped = render_markdown_wrapped(line, wrap_width);
+        result.extend(wrapped.into_iter().map(|(line, links)| ContentLine::Text(line, links)));
+    };
+    let mut block_idx = 0;
     let mut in_code_block = false;
     let mut code_buffer = String::new();
     let mut code_lang = String::new();
 
-    for line in post.content.lines() {
+    for line in content.lines() {
         if line.starts_with("```") {
             if in_code_block {
                 // End of code block - render it using CodeBlock widget
                 let block = CodeBlock::new(&code_buffer)
                     .language(&code_lang)
-                    .width((wrap_width as u16).min(72));
+                    .width(wrap_width.min(72) as u16);
 
+                // The code is searched without the header and footer
+                let start = result.len() + 1;
                 for rendered_line in block.to_lines() {
-                    result.push(ContentLine::CodeBlockLine(rendered_line));
+                    result.push(ContentLine::CodeBlockLine(rendered_line, block_idx));
                 }
+                passages.push(Passage {
+                    text: code_buffer.clone(),
+                    rows: start..result.len() - 1,
+                });
+                block_idx += 1;
 
                 code_buffer.clear();
                 code_lang.clear();
@@ -231,23 +721,162 @@ fn render_post_content(post: &Post, wrap_width: usize) -> Vec<ContentLine> {
                 code_buffer.push('\n');
             }
             code_buffer.push_str(line);
-        } else {
-            // Regular markdown line - wrap it
-            let wrapped = wrap_text(line, wrap_width);
-            for wrapped_line in wrapped {
-                result.push(ContentLine::Text(wrapped_line));
+        } else if !line.is_empty() {
+            // Regular markdown line - render and wrap it; paragraphs are
+            // drawn without blank lines between them
+            let start = result.len();
+            push_text(&mut result, line);
+            passages.push(Passage {
+                text: markdown_text(line),
+                rows: start..result.len(),
+            });
+        }
+    }
+
+    // Footnotes for terminals that can't click links; the digit keys copy them
+    let links = markdown_links(content);
+    if !links.is_empty() && !hyperlinks_enabled() {
+        push_text(&mut result, "");
+        push_text(&mut result, "### Links");
+        for (i, link) in links.iter().enumerate() {
+            let note = if link.text == link.url {
+                format!("[{}] {}", i + 1, link.url)
+            } else {
+                format!("[{}] {} {}", i + 1, link.text, link.url)
+            };
+            push_text(&mut result, &note);
+        }
+    }
+
+    (result, headings, passages)
+}
+
+/// Source of the code block with index `idx` in a post
+fn code_block(post: &Post, idx: usize) -> Option<String> {
+    let mut blocks = Vec::new();
+    let mut code: Option<Vec<&str>> = None;
+    for line in post.content.lines() {
+        if line.starts_with("```") {
+            match code.take() {
+                Some(lines) => blocks.push(lines.join("\n")),
+                None => code = Some(Vec::new()),
             }
+        } else if let Some(lines) = &mut code {
+            lines.push(line);
+        }
+    }
+    blocks.into_iter().nth(idx)
+}
+
+/// List entry for a post; brief entries are a single line without the excerpt
+fn post_item(post: &Post, selected: bool, brief: bool) -> Vec<Line<'static>> {
+    let item = SelectableItem::new(post.title)
+        .selected(selected)
+        .suffix(Span::styled(post.published.to_string(), styles::muted()));
+    if brief {
+        return vec![item.to_line()];
+    }
+    vec![
+        item.to_line(),
+        Line::from(vec![
+            Span::raw("    "),
+            Span::styled(post.excerpt.to_string(), styles::muted()),
+        ]),
+        Line::from(""),
+    ]
+}
+
+/// Date, tags and the start of a post, beside the list on wide terminals
+fn post_preview(post: &Post, width: usize) -> Vec<Line<'static>> {
+    let mut meta = vec![
+        Span::styled(
+            format!("Published: {}", post.published),
+            styles::muted().add_modifier(Modifier::ITALIC),
+        ),
+        Span::raw("  "),
+    ];
+    meta.extend(TagList::from_vec(&post.tags).to_spans());
+
+    let mut lines = vec![Line::from(meta), Line::from("")];
+    lines.extend(render_post_lines(post.content, width));
+    lines
+}
+
+/// Every tag with its post count, most used first
+fn all_tags() -> Vec<(&'static str, usize)> {
+    let mut counts: Vec<(&'static str, usize)> = Vec::new();
+    for tag in POSTS.iter().flat_map(|post| post.tags.iter()) {
+        match counts.iter_mut().find(|(t, _)| t == tag) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((tag, 1)),
         }
     }
+    counts.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+    counts
+}
+
+/// Indices of the posts having every tag in `filter`, in `sort` order
+fn sorted_posts(sort: PostSort, filter: &[&str]) -> Vec<usize> {
+    let mut posts: Vec<usize> = (0..POSTS.len())
+        .filter(|&i| filter.iter().all(|tag| POSTS[i].tags.contains(tag)))
+        .collect();
+    match sort {
+        PostSort::Newest => posts.sort_by(|&a, &b| POSTS[b].published.cmp(POSTS[a].published)),
+        PostSort::Oldest => posts.sort_by_key(|&i| POSTS[i].published),
+        PostSort::Title => posts.sort_by_key(|&i| POSTS[i].title.to_lowercase()),
+    }
+    posts
+}
+
+/// Keys that leave tag focus: the tags toggle and back
+fn tags_done_keys(keymap: &Keymap) -> Option<String> {
+    let keys: Vec<String> = [Msg::FocusTags, Msg::GoBack]
+        .iter()
+        .filter_map(|msg| keymap.keys(ViewId::Blog, InputMode::Normal, msg))
+        .collect();
+    (!keys.is_empty()).then(|| keys.join("/"))
+}
+
+/// History snapshot with the post at `post` (an index into `POSTS`) open
+pub fn open_post_state(post: usize) -> AttrValue {
+    snapshot_value(post, true, 0, PostSort::Newest, &[])
+}
+
+fn snapshot_value(
+    post: usize,
+    detail: bool,
+    viewport: usize,
+    sort: PostSort,
+    filter: &[&str],
+) -> AttrValue {
+    AttrValue::Payload(PropPayload::Map(HashMap::from([
+        ("post".to_string(), PropValue::Usize(post)),
+        ("detail".to_string(), PropValue::Bool(detail)),
+        ("viewport".to_string(), PropValue::Usize(viewport)),
+        ("sort".to_string(), PropValue::Str(sort.label().to_string())),
+        ("tags".to_string(), PropValue::Str(filter.join(","))),
+    ])))
+}
 
-    result
+/// Lines of a post body wrapped to `width`, as the post view draws them
+pub fn render_post_lines(content: &str, width: usize) -> Vec<Line<'static>> {
+    let (lines, ..) = render_post_content(content, width);
+    lines.iter().map(content_line_to_line).collect()
 }
 
 /// Convert ContentLine to a renderable Line
 fn content_line_to_line(line: &ContentLine) -> Line<'static> {
     match line {
-        ContentLine::Text(text) => render_markdown_line(text),
-        ContentLine::CodeBlockLine(line) => line.clone(),
+        ContentLine::Text(line, _) => line.clone(),
+        ContentLine::CodeBlockLine(line, _) => line.clone(),
+    }
+}
+
+/// URLs of the links on a content line
+fn content_line_links(line: &ContentLine) -> &[(usize, String)] {
+    match line {
+        ContentLine::Text(_, links) => links,
+        ContentLine::CodeBlockLine(..) => &[],
     }
 }
 
@@ -260,11 +889,40 @@ impl MockComponent for BlogMock {
     }
 
     fn query(&self, attr: Attribute) -> Option<AttrValue> {
-        self.props.get(attr)
+        match attr {
+            // Snapshot saved in the navigation history: post under the cursor
+            // (or open), scroll, sort order and tag filter
+            Attribute::Value => Some(snapshot_value(
+                self.selected_idx.or(self.cursor_post()).unwrap_or(0),
+                self.mode == BlogMode::Detail,
+                self.viewport,
+                self.sort,
+                &self.filter,
+            )),
+            _ => self.props.get(attr),
+        }
     }
 
     fn attr(&mut self, attr: Attribute, value: AttrValue) {
-        self.props.set(attr, value);
+        match (attr, value) {
+            (Attribute::Value, AttrValue::Payload(PropPayload::Map(map))) => {
+                let usize_of = |key: &str| match map.get(key) {
+                    Some(PropValue::Usize(n)) => *n,
+                    _ => 0,
+                };
+                let str_of = |key: &str| match map.get(key) {
+                    Some(PropValue::Str(s)) => s.as_str(),
+                    _ => "",
+                };
+                let detail = matches!(map.get("detail"), Some(PropValue::Bool(true)));
+                self.restore_filter(str_of("sort"), str_of("tags"));
+                self.restore(usize_of("post"), detail, usize_of("viewport"));
+            }
+            (attr, value) if attr == FIND_ATTR && self.mode == BlogMode::Detail => {
+                self.find = Find::from_attr(value).map(FindState::new);
+            }
+            (attr, value) => self.props.set(attr, value),
+        }
     }
 
     fn state(&self) -> State {
@@ -273,18 +931,70 @@ impl MockComponent for BlogMock {
 
     fn perform(&mut self, cmd: Cmd) -> CmdResult {
         match cmd {
-            Cmd::Move(Direction::Up) | Cmd::Scroll(Direction::Up) => {
+            Cmd::Move(Direction::Up) => {
                 self.cursor_up();
                 CmdResult::Changed(self.state())
             }
-            Cmd::Move(Direction::Down) | Cmd::Scroll(Direction::Down) => {
+            Cmd::Move(Direction::Down) => {
                 self.cursor_down();
                 CmdResult::Changed(self.state())
             }
+            Cmd::Scroll(Direction::Up) => {
+                self.page_up();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Scroll(Direction::Down) => {
+                self.page_down();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::GoTo(Position::Begin) => {
+                self.first();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::GoTo(Position::End) => {
+                self.last();
+                CmdResult::Changed(self.state())
+            }
             Cmd::Submit => {
                 self.select();
                 CmdResult::Changed(self.state())
             }
+            Cmd::Move(Direction::Right) => {
+                self.tag_next();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Move(Direction::Left) => {
+                self.tag_prev();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Custom("focus_tags") => {
+                self.focus_tags();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Custom("cycle_sort") => {
+                self.cycle_sort();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Custom("next_section") => {
+                self.next_section();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Custom("prev_section") => {
+                self.prev_section();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Custom("toc") => {
+                self.toggle_toc();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Custom("find_next") => {
+                self.find_next();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Custom("find_prev") => {
+                self.find_prev();
+                CmdResult::Changed(self.state())
+            }
             _ => CmdResult::None,
         }
     }
@@ -311,63 +1021,134 @@ impl Blog {
 
 impl Component<Msg, NoUserEvent> for Blog {
     fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
-        match ev {
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('c'),
-                modifiers,
-            }) if modifiers.contains(KeyModifiers::CONTROL) => Some(Msg::Quit),
-            Event::Keyboard(KeyEvent { code: Key::Esc, .. })
-            | Event::Keyboard(KeyEvent {
-                code: Key::Char('q'),
-                ..
-            }) => {
+        let msg = route(ViewId::Blog, InputMode::Normal, &ev)?;
+
+        // The contents popup takes the movement keys while open
+        let toc = &mut self.component.toc;
+        if toc.is_open() {
+            return match msg {
+                Msg::CursorUp | Msg::ScrollUp => {
+                    toc.cursor_up();
+                    Some(Msg::None)
+                }
+                Msg::CursorDown | Msg::ScrollDown => {
+                    toc.cursor_down();
+                    Some(Msg::None)
+                }
+                Msg::Select => {
+                    if let Some(line) = toc.choose() {
+                        self.component.viewport = line;
+                    }
+                    Some(Msg::None)
+                }
+                Msg::Click(column, row) => {
+                    match toc.click(column, row) {
+                        Some(line) => self.component.viewport = line,
+                        None => toc.close(),
+                    }
+                    Some(Msg::None)
+                }
+                Msg::GoBack | Msg::ToggleToc => {
+                    toc.close();
+                    Some(Msg::None)
+                }
+                Msg::Tick => None,
+                msg => {
+                    toc.close();
+                    Some(msg)
+                }
+            };
+        }
+
+        match msg {
+            Msg::GoBack => {
                 if self.component.back() {
-                    // Went back within blog (detail -> list)
+                    // Went back within blog (tags -> list, detail -> list)
                     Some(Msg::None)
                 } else {
-                    // Go back to home
                     Some(Msg::GoBack)
                 }
             }
-            Event::Keyboard(KeyEvent {
-                code: Key::Up, ..
-            })
-            | Event::Keyboard(KeyEvent {
-                code: Key::Char('k'),
-                ..
-            }) => {
+            Msg::CursorUp | Msg::ScrollUp => {
                 self.component.perform(Cmd::Move(Direction::Up));
                 Some(Msg::None)
             }
-            Event::Keyboard(KeyEvent {
-                code: Key::Down, ..
-            })
-            | Event::Keyboard(KeyEvent {
-                code: Key::Char('j'),
-                ..
-            }) => {
+            Msg::CursorDown | Msg::ScrollDown => {
                 self.component.perform(Cmd::Move(Direction::Down));
                 Some(Msg::None)
             }
-            Event::Keyboard(KeyEvent {
-                code: Key::Enter, ..
-            }) => {
+            Msg::PageUp => {
+                self.component.perform(Cmd::Scroll(Direction::Up));
+                Some(Msg::None)
+            }
+            Msg::PageDown => {
+                self.component.perform(Cmd::Scroll(Direction::Down));
+                Some(Msg::None)
+            }
+            Msg::JumpTop => {
+                self.component.perform(Cmd::GoTo(Position::Begin));
+                Some(Msg::None)
+            }
+            Msg::JumpBottom => {
+                self.component.perform(Cmd::GoTo(Position::End));
+                Some(Msg::None)
+            }
+            Msg::Select => {
                 self.component.perform(Cmd::Submit);
                 Some(Msg::None)
             }
-            Event::Keyboard(KeyEvent {
-                code: Key::Char(c),
-                ..
-            }) => {
-                // Only allow navigation shortcuts in list mode
-                if self.component.mode == BlogMode::List {
-                    if let Some(view) = ViewId::from_shortcut(c) {
-                        return Some(Msg::NavigateTo(view));
-                    }
-                }
-                None
+            Msg::Click(column, row) => {
+                self.component.click(column, row);
+                Some(Msg::None)
+            }
+            Msg::FocusNext => {
+                self.component.perform(Cmd::Move(Direction::Right));
+                Some(Msg::None)
+            }
+            Msg::FocusPrev => {
+                self.component.perform(Cmd::Move(Direction::Left));
+                Some(Msg::None)
+            }
+            Msg::FocusTags => {
+                self.component.perform(Cmd::Custom("focus_tags"));
+                Some(Msg::None)
+            }
+            Msg::CycleSort => {
+                self.component.perform(Cmd::Custom("cycle_sort"));
+                Some(Msg::None)
+            }
+            Msg::Copy => Some(self.component.web_url().map_or(Msg::None, Msg::CopyText)),
+            Msg::CopyCode => Some(match self.component.visible_code() {
+                Some(code) => Msg::CopyText(code),
+                None => Msg::UpdateStatus("No code block on screen".to_string()),
+            }),
+            Msg::CopyLink(number) => Some(
+                self.component
+                    .link_url(number)
+                    .map_or(Msg::None, Msg::CopyText),
+            ),
+            Msg::NextItem => {
+                self.component.perform(Cmd::Custom("next_section"));
+                Some(Msg::None)
+            }
+            Msg::PrevItem => {
+                self.component.perform(Cmd::Custom("prev_section"));
+                Some(Msg::None)
+            }
+            Msg::ToggleToc => {
+                self.component.perform(Cmd::Custom("toc"));
+                Some(Msg::None)
+            }
+            Msg::FindNext => {
+                self.component.perform(Cmd::Custom("find_next"));
+                Some(Msg::None)
+            }
+            Msg::FindPrev => {
+                self.component.perform(Cmd::Custom("find_prev"));
+                Some(Msg::None)
             }
-            _ => None,
+            Msg::Tick => None,
+            msg => Some(msg),
         }
     }
 }
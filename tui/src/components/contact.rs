@@ -1,17 +1,36 @@
-//! Contact form component with input fields and submission
+//! Contact form component with input fields and submission, beside a
+//! contact card with a QR code
 
+use once_cell::sync::Lazy;
 use ratatui::layout::{Alignment, Constraint, Layout, Rect};
 use ratatui::style::Modifier;
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, BorderType, Borders, Paragraph};
-use tuirealm::command::{Cmd, CmdResult, Direction};
-use tuirealm::event::{Key, KeyEvent, KeyModifiers};
-use tuirealm::props::{AttrValue, Attribute, Props};
+use ratatui::widgets::{Block, BorderType, Borders, Padding, Paragraph};
+use tuirealm::command::{Cmd, CmdResult, Direction, Position};
+use tuirealm::props::{AttrValue, Attribute, PropPayload, PropValue, Props};
 use tuirealm::{Component, Event, Frame, MockComponent, NoUserEvent, State, StateValue};
 
-use crate::msg::Msg;
+use crate::content;
+use crate::export;
+use crate::keymap::{keymap, route, InputMode};
+use crate::links::{self, SpanLinks};
+use crate::msg::{Msg, ViewId};
 use crate::styles;
-use crate::widgets::{PageLayout, TextInput};
+use crate::widgets::{HitMap, PageLayout, QrCode, TextInput};
+
+/// Narrowest the form gets before the contact card is left out
+const FORM_MIN_WIDTH: u16 = 44;
+
+/// QR codes offered on the card, largest first, with their captions
+static CARD_CODES: Lazy<Vec<(QrCode, &'static str)>> = Lazy::new(|| {
+    [
+        (export::vcard(), "Scan to save the contact"),
+        (content::site_url().to_string(), "Scan to open the website"),
+    ]
+    .into_iter()
+    .filter_map(|(data, caption)| Some((QrCode::new(&data)?, caption)))
+    .collect()
+});
 
 /// Form field enum
 #[derive(Clone, Copy, PartialEq)]
@@ -22,6 +41,19 @@ pub enum ContactField {
     Submit,
 }
 
+impl ContactField {
+    const ALL: [ContactField; 4] = [
+        ContactField::Name,
+        ContactField::Email,
+        ContactField::Message,
+        ContactField::Submit,
+    ];
+
+    fn index(self) -> usize {
+        Self::ALL.iter().position(|f| *f == self).unwrap_or(0)
+    }
+}
+
 /// Contact MockComponent - handles the visual representation
 pub struct ContactMock {
     props: Props,
@@ -30,7 +62,10 @@ pub struct ContactMock {
     email: String,
     message: String,
     submitted: bool,
+    /// Why the last submission failed, until reported in the status bar
     error: Option<String>,
+    /// Fields and the Submit button on screen at the last render
+    hits: HitMap<ContactField>,
 }
 
 impl Default for ContactMock {
@@ -49,6 +84,16 @@ impl ContactMock {
             message: String::new(),
             submitted: false,
             error: None,
+            hits: HitMap::new(),
+        }
+    }
+
+    /// Text fields capture character keys; the submit button does not
+    fn input_mode(&self) -> InputMode {
+        match self.focused_field {
+            ContactField::Submit => InputMode::Normal,
+            _ if self.submitted => InputMode::Normal,
+            _ => InputMode::TextInput,
         }
     }
 
@@ -125,13 +170,29 @@ impl ContactMock {
         self.focused_field = ContactField::Name;
     }
 
-    fn render_form(&self, frame: &mut Frame, area: Rect) {
+    fn render_form(&mut self, frame: &mut Frame, area: Rect) {
         PageLayout::new("📧 Contact")
-            .help("tab next • shift+tab prev • enter submit • esc back")
+            .breadcrumbs(["Home"])
+            .help(keymap().help(ViewId::Contact, self.input_mode()))
             .render(frame, area, |f, content_area| {
+                // The contact card goes beside the form when there is room
+                let content_area = match card(content_area) {
+                    Some((lines, width)) => {
+                        let [form_area, _, card_area] = Layout::horizontal([
+                            Constraint::Min(FORM_MIN_WIDTH),
+                            Constraint::Length(2),
+                            Constraint::Length(width),
+                        ])
+                        .areas(content_area);
+                        render_card(f, card_area, lines);
+                        form_area
+                    }
+                    None => content_area,
+                };
+
                 let chunks = Layout::vertical([
                     Constraint::Length(2),  // Intro
-                    Constraint::Length(2),  // Error (if any)
+                    Constraint::Length(2),  // Gap
                     Constraint::Length(3),  // Name field
                     Constraint::Length(3),  // Email field
                     Constraint::Length(3),  // Message field
@@ -140,18 +201,27 @@ impl ContactMock {
                 ])
                 .split(content_area);
 
-                // Intro
-                let intro =
-                    Paragraph::new("Have a question or want to work together? Send me a message!")
-                        .style(styles::text());
+                // Intro, with the address for writing directly
+                let email = content::email();
+                let email_line = Line::from(vec![
+                    Span::styled("Or email ", styles::muted()),
+                    Span::styled(email, styles::link()),
+                ]);
+                let intro = Paragraph::new(vec![
+                    Line::styled(
+                        "Have a question or want to work together? Send me a message!",
+                        styles::text(),
+                    ),
+                    email_line.clone(),
+                ]);
                 f.render_widget(intro, chunks[0]);
-
-                // Error message
-                if let Some(err) = &self.error {
-                    let error_msg =
-                        Paragraph::new(format!("⚠ {}", err)).style(styles::error());
-                    f.render_widget(error_msg, chunks[1]);
-                }
+                let email_area = Rect {
+                    y: chunks[0].y + 1,
+                    height: chunks[0].height.saturating_sub(1),
+                    ..chunks[0]
+                };
+                let email_link = [(1, format!("mailto:{email}"))];
+                links::place(email_area, [(&email_line, &email_link[..])]);
 
                 // Form fields using TextInput widget
                 TextInput::new("Name", &self.name)
@@ -179,12 +249,23 @@ impl ContactMock {
                     .alignment(Alignment::Left);
                 let submit_area = Rect::new(chunks[5].x + 2, chunks[5].y, 12, 1);
                 f.render_widget(submit, submit_area);
+
+                self.hits.clear();
+                self.hits.push(chunks[2], ContactField::Name);
+                self.hits.push(chunks[3], ContactField::Email);
+                self.hits.push(chunks[4], ContactField::Message);
+                self.hits.push(submit_area.intersection(chunks[5]), ContactField::Submit);
             });
     }
 
     fn render_success(&self, frame: &mut Frame, area: Rect) {
         PageLayout::new("📧 Contact")
-            .help("esc back to menu")
+            .breadcrumbs(["Home"])
+            .help(
+                keymap()
+                    .describe(ViewId::Contact, InputMode::Normal, &Msg::GoBack)
+                    .unwrap_or_default(),
+            )
             .render(frame, area, |f, content_area| {
                 // Success box
                 let success_block = Block::default()
@@ -228,6 +309,91 @@ impl ContactMock {
     }
 }
 
+/// Name, email, website and social profiles for the contact card
+fn card_lines() -> Vec<Line<'static>> {
+    let email = content::email();
+    let site = content::site_url();
+
+    let mut lines = vec![
+        Line::styled(content::name(), styles::title()),
+        Line::from(vec![
+            Span::styled("Email    ", styles::muted()),
+            Span::styled(email, styles::link()),
+        ]),
+        Line::from(vec![
+            Span::styled("Website  ", styles::muted()),
+            Span::styled(display_url(site), styles::link()),
+        ]),
+    ];
+    for social in content::socials() {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{:<9}", social.label), styles::muted()),
+            Span::styled(display_url(&social.url), styles::link()),
+        ]));
+    }
+    lines
+}
+
+/// URLs of the links on the card's lines, which start with its name
+fn card_links() -> Vec<SpanLinks> {
+    let mut links = vec![
+        Vec::new(),
+        vec![(1, format!("mailto:{}", content::email()))],
+        vec![(1, content::site_url().to_string())],
+    ];
+    links.extend(content::socials().iter().map(|social| vec![(1, social.url.clone())]));
+    links
+}
+
+/// `url` without the scheme and `www.`, as shown on the card
+fn display_url(url: &str) -> &str {
+    let url = url.split_once("://").map_or(url, |(_, rest)| rest);
+    url.strip_prefix("www.").unwrap_or(url)
+}
+
+/// The card's lines, with a QR code when one fits, and its width beside
+/// the form in `area`; `None` when even the details do not fit
+fn card(area: Rect) -> Option<(Vec<Line<'static>>, u16)> {
+    let mut lines = card_lines();
+    let text_width = lines.iter().map(Line::width).max().unwrap_or(0) as u16;
+    // Borders and a column of padding on each side
+    let max_width = area.width.saturating_sub(FORM_MIN_WIDTH + 2);
+    if text_width + 4 > max_width || lines.len() as u16 + 2 > area.height {
+        return None;
+    }
+
+    // Below the details: a blank line, the code and its caption
+    let code = CARD_CODES.iter().find(|(qr, caption)| {
+        let width = qr.width().max(caption.len() as u16) + 4;
+        let height = lines.len() as u16 + qr.height() + 4;
+        width <= max_width && height <= area.height
+    });
+    let mut width = text_width + 4;
+    if let Some((qr, caption)) = code {
+        width = width.max(qr.width() + 4).max(caption.len() as u16 + 4);
+        lines.push(Line::from(""));
+        lines.extend(qr.to_lines().into_iter().map(|line| line.alignment(Alignment::Center)));
+        lines.push(Line::styled(*caption, styles::muted()).alignment(Alignment::Center));
+    }
+    Some((lines, width))
+}
+
+/// Draw the contact card at the top of `area`
+fn render_card(frame: &mut Frame, area: Rect, lines: Vec<Line<'static>>) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(styles::border())
+        .padding(Padding::horizontal(1))
+        .title(Span::styled(" Card ", styles::subtitle()));
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let card_area = Rect::new(area.x, area.y, area.width, height);
+    let links = card_links();
+    links::place(block.inner(card_area), lines.iter().zip(links.iter().map(Vec::as_slice)));
+    let card = Paragraph::new(lines).block(block);
+    frame.render_widget(card, card_area);
+}
+
 impl MockComponent for ContactMock {
     fn view(&mut self, frame: &mut Frame, area: Rect) {
         if self.submitted {
@@ -238,11 +404,39 @@ impl MockComponent for ContactMock {
     }
 
     fn query(&self, attr: Attribute) -> Option<AttrValue> {
-        self.props.get(attr)
+        match attr {
+            // Draft saved between visits; a sent message is not kept
+            Attribute::Value if !self.submitted => Some(AttrValue::Payload(PropPayload::Tup4((
+                PropValue::Str(self.name.clone()),
+                PropValue::Str(self.email.clone()),
+                PropValue::Str(self.message.clone()),
+                PropValue::Usize(self.focused_field.index()),
+            )))),
+            _ => self.props.get(attr),
+        }
     }
 
     fn attr(&mut self, attr: Attribute, value: AttrValue) {
-        self.props.set(attr, value);
+        match (attr, value) {
+            (
+                Attribute::Value,
+                AttrValue::Payload(PropPayload::Tup4((
+                    PropValue::Str(name),
+                    PropValue::Str(email),
+                    PropValue::Str(message),
+                    PropValue::Usize(field),
+                ))),
+            ) => {
+                self.name = name;
+                self.email = email;
+                self.message = message;
+                self.focused_field = ContactField::ALL
+                    .get(field)
+                    .copied()
+                    .unwrap_or(ContactField::Name);
+            }
+            (attr, value) => self.props.set(attr, value),
+        }
     }
 
     fn state(&self) -> State {
@@ -259,6 +453,12 @@ impl MockComponent for ContactMock {
                 self.focus_prev();
                 CmdResult::Changed(self.state())
             }
+            Cmd::GoTo(Position::At(idx)) => {
+                if let Some(field) = ContactField::ALL.get(idx) {
+                    self.focused_field = *field;
+                }
+                CmdResult::Changed(self.state())
+            }
             Cmd::Submit => {
                 self.submit();
                 CmdResult::Changed(self.state())
@@ -297,67 +497,61 @@ impl Contact {
             component: ContactMock::new(),
         }
     }
+
+    /// Submit the form, reporting why it was rejected
+    fn submit(&mut self) -> Msg {
+        self.component.perform(Cmd::Submit);
+        self.component.error.take().map_or(Msg::None, Msg::ShowError)
+    }
 }
 
 impl Component<Msg, NoUserEvent> for Contact {
     fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
-        // If submitted, only allow going back
+        let msg = route(ViewId::Contact, self.component.input_mode(), &ev)?;
+
+        // If submitted, only allow leaving the view
         if self.component.submitted {
-            match ev {
-                Event::Keyboard(KeyEvent {
-                    code: Key::Char('c'),
-                    modifiers,
-                }) if modifiers.contains(KeyModifiers::CONTROL) => Some(Msg::Quit),
-                Event::Keyboard(KeyEvent { code: Key::Esc, .. })
-                | Event::Keyboard(KeyEvent {
-                    code: Key::Char('q'),
-                    ..
-                }) => {
+            return match msg {
+                Msg::GoBack => {
                     self.component.reset();
                     Some(Msg::GoBack)
                 }
+                Msg::Quit | Msg::NavigateTo(_) => Some(msg),
+                Msg::Copy => Some(Msg::CopyText(content::email().to_string())),
                 _ => None,
+            };
+        }
+
+        match msg {
+            Msg::FocusNext => {
+                self.component.perform(Cmd::Move(Direction::Down));
+                Some(Msg::None)
             }
-        } else {
-            match ev {
-                Event::Keyboard(KeyEvent {
-                    code: Key::Char('c'),
-                    modifiers,
-                }) if modifiers.contains(KeyModifiers::CONTROL) => Some(Msg::Quit),
-                Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => Some(Msg::GoBack),
-                Event::Keyboard(KeyEvent { code: Key::Tab, .. }) => {
-                    self.component.perform(Cmd::Move(Direction::Down));
-                    Some(Msg::None)
-                }
-                Event::Keyboard(KeyEvent {
-                    code: Key::BackTab,
-                    ..
-                }) => {
-                    self.component.perform(Cmd::Move(Direction::Up));
-                    Some(Msg::None)
-                }
-                Event::Keyboard(KeyEvent {
-                    code: Key::Enter, ..
-                }) => {
-                    self.component.perform(Cmd::Submit);
-                    Some(Msg::None)
-                }
-                Event::Keyboard(KeyEvent {
-                    code: Key::Backspace,
-                    ..
-                }) => {
-                    self.component.perform(Cmd::Delete);
-                    Some(Msg::None)
-                }
-                Event::Keyboard(KeyEvent {
-                    code: Key::Char(c),
-                    modifiers,
-                }) if modifiers.is_empty() || modifiers.contains(KeyModifiers::SHIFT) => {
-                    self.component.perform(Cmd::Type(c));
+            Msg::FocusPrev => {
+                self.component.perform(Cmd::Move(Direction::Up));
+                Some(Msg::None)
+            }
+            Msg::Submit => Some(self.submit()),
+            // Clicking a field focuses it; clicking the button submits
+            Msg::Click(column, row) => match self.component.hits.at(column, row) {
+                Some(ContactField::Submit) => Some(self.submit()),
+                Some(field) => {
+                    self.component.perform(Cmd::GoTo(Position::At(field.index())));
                     Some(Msg::None)
                 }
-                _ => None,
+                None => Some(Msg::None),
+            },
+            Msg::Backspace => {
+                self.component.perform(Cmd::Delete);
+                Some(Msg::None)
+            }
+            Msg::TypeChar(c) => {
+                self.component.perform(Cmd::Type(c));
+                Some(Msg::None)
             }
+            Msg::Copy => Some(Msg::CopyText(content::email().to_string())),
+            Msg::Tick => None,
+            msg => Some(msg),
         }
     }
 }
@@ -0,0 +1,226 @@
+//! Help overlay listing every keybinding for the view underneath
+
+use ratatui::layout::Rect;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders, Clear, Paragraph};
+use tuirealm::command::{Cmd, CmdResult, Direction, Position};
+use tuirealm::props::{AttrValue, Attribute, Props};
+use tuirealm::{Component, Event, Frame, MockComponent, NoUserEvent, State, StateValue};
+
+use crate::keymap::{keymap, route, InputMode};
+use crate::msg::{Msg, ViewId};
+use crate::styles;
+use crate::widgets::PageLayout;
+
+/// Help MockComponent - handles the visual representation
+pub struct HelpMock {
+    props: Props,
+    /// View whose bindings are listed
+    view: ViewId,
+    viewport: usize,
+    max_scroll: usize,
+}
+
+impl HelpMock {
+    pub fn new(view: ViewId) -> Self {
+        Self {
+            props: Props::default(),
+            view,
+            viewport: 0,
+            max_scroll: 0,
+        }
+    }
+
+    fn scroll_up(&mut self) {
+        self.viewport = self.viewport.saturating_sub(1);
+    }
+
+    fn scroll_down(&mut self) {
+        self.viewport = (self.viewport + 1).min(self.max_scroll);
+    }
+
+    fn page_up(&mut self) {
+        self.viewport = self.viewport.saturating_sub(10);
+    }
+
+    fn page_down(&mut self) {
+        self.viewport = (self.viewport + 10).min(self.max_scroll);
+    }
+
+    fn top(&mut self) {
+        self.viewport = 0;
+    }
+
+    fn bottom(&mut self) {
+        self.viewport = self.max_scroll;
+    }
+
+    /// One line per binding, grouped under section headings
+    fn lines(&self) -> Vec<Line<'static>> {
+        let sections = keymap().sections(self.view);
+        let key_width = sections
+            .iter()
+            .flat_map(|s| s.entries.iter())
+            .map(|(keys, _)| keys.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let mut lines = Vec::new();
+        for section in sections {
+            if !lines.is_empty() {
+                lines.push(Line::from(""));
+            }
+            lines.push(Line::from(Span::styled(section.title, styles::subtitle())));
+            for (keys, label) in section.entries {
+                lines.push(Line::from(vec![
+                    Span::raw("  "),
+                    Span::styled(format!("{keys:<key_width$}"), styles::accent()),
+                    Span::raw("  "),
+                    Span::styled(label, styles::text()),
+                ]));
+            }
+        }
+        lines
+    }
+}
+
+impl MockComponent for HelpMock {
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(styles::border());
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let lines = self.lines();
+        let viewable_height = PageLayout::content_area(inner).height as usize;
+        let content_length = lines.len();
+        self.max_scroll = content_length.saturating_sub(viewable_height);
+        self.viewport = self.viewport.min(self.max_scroll);
+
+        PageLayout::new(format!("⌨ Keybindings · {}", self.view.title()))
+            .help(keymap().help(ViewId::Help, InputMode::Normal))
+            .scroll_state(self.viewport, content_length, viewable_height)
+            .render(frame, inner, |f, content_area| {
+                let visible: Vec<Line> = lines
+                    .into_iter()
+                    .skip(self.viewport)
+                    .take(viewable_height)
+                    .collect();
+                f.render_widget(Paragraph::new(visible), content_area);
+            });
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.props.get(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        self.props.set(attr, value);
+    }
+
+    fn state(&self) -> State {
+        State::One(StateValue::Usize(self.viewport))
+    }
+
+    fn perform(&mut self, cmd: Cmd) -> CmdResult {
+        match cmd {
+            Cmd::Scroll(Direction::Up) => {
+                self.scroll_up();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Scroll(Direction::Down) => {
+                self.scroll_down();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::GoTo(Position::Begin) => {
+                self.page_up();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::GoTo(Position::End) => {
+                self.page_down();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Custom("top") => {
+                self.top();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Custom("bottom") => {
+                self.bottom();
+                CmdResult::Changed(self.state())
+            }
+            _ => CmdResult::None,
+        }
+    }
+}
+
+/// Help Component - bridges MockComponent to application messages
+pub struct Help {
+    component: HelpMock,
+}
+
+impl Help {
+    pub fn new(view: ViewId) -> Self {
+        Self {
+            component: HelpMock::new(view),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for Help {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        match route(ViewId::Help, InputMode::Normal, &ev)? {
+            Msg::CursorUp | Msg::ScrollUp => {
+                self.component.perform(Cmd::Scroll(Direction::Up));
+                Some(Msg::None)
+            }
+            Msg::CursorDown | Msg::ScrollDown => {
+                self.component.perform(Cmd::Scroll(Direction::Down));
+                Some(Msg::None)
+            }
+            Msg::PageUp => {
+                self.component.perform(Cmd::GoTo(Position::Begin));
+                Some(Msg::None)
+            }
+            Msg::PageDown => {
+                self.component.perform(Cmd::GoTo(Position::End));
+                Some(Msg::None)
+            }
+            Msg::JumpTop => {
+                self.component.perform(Cmd::Custom("top"));
+                Some(Msg::None)
+            }
+            Msg::JumpBottom => {
+                self.component.perform(Cmd::Custom("bottom"));
+                Some(Msg::None)
+            }
+            Msg::GoBack | Msg::ShowHelp => Some(Msg::CloseOverlay),
+            Msg::Quit => Some(Msg::Quit),
+            _ => None,
+        }
+    }
+}
+
+impl MockComponent for Help {
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        self.component.view(frame, area);
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.component.query(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        self.component.attr(attr, value);
+    }
+
+    fn state(&self) -> State {
+        self.component.state()
+    }
+
+    fn perform(&mut self, cmd: Cmd) -> CmdResult {
+        self.component.perform(cmd)
+    }
+}
@@ -2,23 +2,23 @@
 
 use std::time::{Duration, Instant};
 
-use ratatui::layout::{Alignment, Constraint, Layout, Rect};
+use ratatui::layout::{Alignment, Constraint, Layout, Margin, Rect};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, BorderType, Borders, Paragraph};
-use tuirealm::command::{Cmd, CmdResult, Direction};
-use tuirealm::event::{Key, KeyEvent, KeyModifiers};
-use tuirealm::props::{AttrValue, Attribute, Props};
+use ratatui::widgets::{Block, BorderType, Borders, Paragraph, Wrap};
+use tuirealm::command::{Cmd, CmdResult, Direction, Position};
+use tuirealm::props::{AttrValue, Attribute, PropPayload, PropValue, Props};
 use tuirealm::{Component, Event, Frame, MockComponent, NoUserEvent, State, StateValue};
 
 use crate::content::{BIO, LOGO, TYPEWRITER_PHRASES};
+use crate::keymap::{key_label, keymap, route, InputMode};
 use crate::msg::{Msg, ViewId};
 use crate::styles;
+use crate::widgets::{stacked, Breakpoint, HitMap};
 
 /// Menu item configuration
 struct MenuConfig {
     view: ViewId,
-    shortcut: char,
     label: &'static str,
     description: &'static str,
 }
@@ -26,34 +26,34 @@ struct MenuConfig {
 const MENU_ITEMS: &[MenuConfig] = &[
     MenuConfig {
         view: ViewId::Home,
-        shortcut: 'h',
         label: "Home",
         description: "You are here",
     },
     MenuConfig {
         view: ViewId::Resume,
-        shortcut: 'r',
         label: "Resume",
         description: "View my professional experience",
     },
     MenuConfig {
         view: ViewId::Blog,
-        shortcut: 'b',
         label: "Blog",
         description: "Read my technical articles",
     },
     MenuConfig {
         view: ViewId::Projects,
-        shortcut: 'p',
         label: "Projects",
         description: "Explore my work",
     },
     MenuConfig {
         view: ViewId::Contact,
-        shortcut: 'c',
         label: "Contact",
         description: "Get in touch",
     },
+    MenuConfig {
+        view: ViewId::Settings,
+        label: "Settings",
+        description: "Choose your keybindings",
+    },
 ];
 
 /// Typewriter animation state
@@ -68,6 +68,8 @@ enum TypewriterState {
 pub struct HomeMock {
     props: Props,
     cursor: usize,
+    /// Menu rows at the last render, for mouse clicks
+    hits: HitMap<usize>,
     // Typewriter state
     typewriter_state: TypewriterState,
     phrase_idx: usize,
@@ -87,6 +89,7 @@ impl HomeMock {
         Self {
             props: Props::default(),
             cursor: 0,
+            hits: HitMap::new(),
             typewriter_state: TypewriterState::Typing,
             phrase_idx: 0,
             char_idx: 0,
@@ -159,11 +162,24 @@ impl HomeMock {
         }
     }
 
-    fn render_menu_item(&self, config: &MenuConfig, selected: bool) -> Line<'static> {
+    /// Menu entry; compact menus leave out the description
+    fn render_menu_item(
+        &self,
+        config: &MenuConfig,
+        selected: bool,
+        compact: bool,
+    ) -> Line<'static> {
         let pointer = if selected { "▶ " } else { "  " };
-        let shortcut = format!("[{}] ", config.shortcut);
+        let shortcut = match keymap().shortcut(&Msg::NavigateTo(config.view)) {
+            Some(key) => format!("[{}] ", key_label(&key)),
+            None => String::new(),
+        };
         let label = config.label.to_string();
-        let desc = format!(" - {}", config.description);
+        let desc = if compact {
+            String::new()
+        } else {
+            format!(" - {}", config.description)
+        };
 
         if selected {
             Line::from(vec![
@@ -185,14 +201,29 @@ impl HomeMock {
 
 impl MockComponent for HomeMock {
     fn view(&mut self, frame: &mut Frame, area: Rect) {
-        let chunks = Layout::vertical([
-            Constraint::Length(8),  // Logo
-            Constraint::Length(2),  // Typewriter
-            Constraint::Length(6),  // Bio
-            Constraint::Min(10),    // Menu
-            Constraint::Length(2),  // Help
-        ])
-        .split(area);
+        let compact = Breakpoint::of(area).is_compact();
+        let menu_height = MENU_ITEMS.len() as u16 + 2;
+        let chunks = if compact {
+            // One-line header; the bio only if the menu still fits
+            let bio_height = if area.height >= menu_height + 10 { 5 } else { 0 };
+            Layout::vertical([
+                Constraint::Length(0),          // Logo
+                Constraint::Length(2),          // Header
+                Constraint::Length(bio_height), // Bio
+                Constraint::Min(menu_height),   // Menu
+                Constraint::Length(1),          // Help
+            ])
+            .split(area)
+        } else {
+            Layout::vertical([
+                Constraint::Length(8),  // Logo
+                Constraint::Length(2),  // Typewriter
+                Constraint::Length(6),  // Bio
+                Constraint::Min(10),    // Menu
+                Constraint::Length(2),  // Help
+            ])
+            .split(area)
+        };
 
         // Logo
         let logo = Paragraph::new(*LOGO)
@@ -200,8 +231,13 @@ impl MockComponent for HomeMock {
             .alignment(Alignment::Center);
         frame.render_widget(logo, chunks[0]);
 
-        // Typewriter tagline
-        let typewriter_line = Line::from(vec![
+        // Typewriter tagline, after the name in compact layouts
+        let mut typewriter_spans = Vec::new();
+        if compact {
+            typewriter_spans.push(Span::styled("whoami", styles::title()));
+            typewriter_spans.push(Span::styled(" · ", styles::border()));
+        }
+        typewriter_spans.extend([
             Span::styled(
                 self.display_text.clone(),
                 Style::default()
@@ -210,13 +246,15 @@ impl MockComponent for HomeMock {
             ),
             Span::styled("█", Style::default().fg(styles::COLOR_PRIMARY)),
         ]);
-        let typewriter = Paragraph::new(typewriter_line).alignment(Alignment::Center);
+        let typewriter =
+            Paragraph::new(Line::from(typewriter_spans)).alignment(Alignment::Center);
         frame.render_widget(typewriter, chunks[1]);
 
         // Bio
         let bio = Paragraph::new(*BIO)
             .style(styles::text())
-            .alignment(Alignment::Center);
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
         frame.render_widget(bio, chunks[2]);
 
         // Menu
@@ -228,7 +266,7 @@ impl MockComponent for HomeMock {
         let menu_lines: Vec<Line> = MENU_ITEMS
             .iter()
             .enumerate()
-            .map(|(i, config)| self.render_menu_item(config, i == self.cursor))
+            .map(|(i, config)| self.render_menu_item(config, i == self.cursor, compact))
             .collect();
 
         let menu = Paragraph::new(menu_lines)
@@ -236,29 +274,47 @@ impl MockComponent for HomeMock {
             .alignment(Alignment::Left);
 
         // Center the menu horizontally
-        let menu_width = 60.min(area.width.saturating_sub(4));
-        let menu_x = (area.width.saturating_sub(menu_width)) / 2;
+        let menu_width = if compact { 32 } else { 60 }.min(area.width.saturating_sub(4));
+        let menu_x = area.x + (area.width.saturating_sub(menu_width)) / 2;
+        let menu_top = if compact { 0 } else { 1 };
         let menu_area = Rect::new(
             menu_x,
-            chunks[3].y + 1,
+            chunks[3].y + menu_top,
             menu_width,
-            (MENU_ITEMS.len() as u16 + 2).min(chunks[3].height),
+            menu_height.min(chunks[3].height),
         );
         frame.render_widget(menu, menu_area);
 
+        self.hits.clear();
+        let rows = stacked(menu_area.inner(Margin::new(1, 1)), &[1; MENU_ITEMS.len()]);
+        for (i, row) in rows.into_iter().enumerate() {
+            self.hits.push(row, i);
+        }
+
         // Help text
-        let help = Paragraph::new("↑/k up • ↓/j down • enter select • q quit")
+        let help = Paragraph::new(keymap().help(ViewId::Home, InputMode::Normal))
             .style(styles::muted())
             .alignment(Alignment::Center);
         frame.render_widget(help, chunks[4]);
     }
 
     fn query(&self, attr: Attribute) -> Option<AttrValue> {
-        self.props.get(attr)
+        match attr {
+            // Snapshot saved in the navigation history
+            Attribute::Value => Some(AttrValue::Payload(PropPayload::One(PropValue::Usize(
+                self.cursor,
+            )))),
+            _ => self.props.get(attr),
+        }
     }
 
     fn attr(&mut self, attr: Attribute, value: AttrValue) {
-        self.props.set(attr, value);
+        match (attr, value) {
+            (Attribute::Value, AttrValue::Payload(PropPayload::One(PropValue::Usize(cursor)))) => {
+                self.cursor = cursor.min(MENU_ITEMS.len() - 1);
+            }
+            (attr, value) => self.props.set(attr, value),
+        }
     }
 
     fn state(&self) -> State {
@@ -275,6 +331,10 @@ impl MockComponent for HomeMock {
                 self.cursor_down();
                 CmdResult::Changed(self.state())
             }
+            Cmd::GoTo(Position::At(idx)) => {
+                self.cursor = idx.min(MENU_ITEMS.len() - 1);
+                CmdResult::Changed(self.state())
+            }
             Cmd::Tick => {
                 self.tick_typewriter();
                 CmdResult::Changed(self.state())
@@ -305,57 +365,32 @@ impl Home {
 
 impl Component<Msg, NoUserEvent> for Home {
     fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
-        match ev {
-            Event::Tick => {
+        match route(ViewId::Home, InputMode::Normal, &ev)? {
+            Msg::Tick => {
                 self.component.perform(Cmd::Tick);
                 Some(Msg::None)
             }
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('c'),
-                modifiers,
-            }) if modifiers.contains(KeyModifiers::CONTROL) => Some(Msg::Quit),
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('q'),
-                ..
-            }) => Some(Msg::Quit),
-            Event::Keyboard(KeyEvent {
-                code: Key::Up, ..
-            })
-            | Event::Keyboard(KeyEvent {
-                code: Key::Char('k'),
-                ..
-            }) => {
+            Msg::CursorUp | Msg::ScrollUp => {
                 self.component.perform(Cmd::Move(Direction::Up));
                 Some(Msg::None)
             }
-            Event::Keyboard(KeyEvent {
-                code: Key::Down, ..
-            })
-            | Event::Keyboard(KeyEvent {
-                code: Key::Char('j'),
-                ..
-            }) => {
+            Msg::CursorDown | Msg::ScrollDown => {
                 self.component.perform(Cmd::Move(Direction::Down));
                 Some(Msg::None)
             }
-            Event::Keyboard(KeyEvent {
-                code: Key::Enter, ..
-            }) => {
-                let view = self.component.selected_view();
-                Some(Msg::NavigateTo(view))
-            }
-            Event::Keyboard(KeyEvent {
-                code: Key::Char(c),
-                ..
-            }) => {
-                // Check for navigation shortcuts
-                if let Some(view) = ViewId::from_shortcut(c) {
-                    Some(Msg::NavigateTo(view))
-                } else {
-                    None
+            Msg::Select => Some(Msg::NavigateTo(self.component.selected_view())),
+            // A click selects a menu item, a click on the selected one opens it
+            Msg::Click(column, row) => match self.component.hits.at(column, row) {
+                Some(idx) if idx == self.component.cursor => {
+                    Some(Msg::NavigateTo(self.component.selected_view()))
                 }
-            }
-            _ => None,
+                Some(idx) => {
+                    self.component.perform(Cmd::GoTo(Position::At(idx)));
+                    Some(Msg::None)
+                }
+                None => Some(Msg::None),
+            },
+            msg => Some(msg),
         }
     }
 }
@@ -7,11 +7,11 @@ use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::Paragraph;
 use tuirealm::command::{Cmd, CmdResult};
-use tuirealm::event::{Key, KeyEvent};
 use tuirealm::props::{AttrValue, Attribute, Props};
 use tuirealm::{Component, Event, Frame, MockComponent, NoUserEvent, State, StateValue};
 
-use crate::msg::Msg;
+use crate::keymap::{route, InputMode};
+use crate::msg::{Msg, ViewId};
 use crate::styles::{COLOR_ACCENT, COLOR_MUTED, COLOR_PRIMARY, COLOR_SECONDARY};
 
 /// Boot sequence messages — context-aware for the SSH TUI
@@ -202,20 +202,16 @@ impl Loading {
 
 impl Component<Msg, NoUserEvent> for Loading {
     fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
-        match ev {
-            Event::Tick => {
+        match route(ViewId::Loading, InputMode::Normal, &ev)? {
+            Msg::Tick => {
                 self.component.perform(Cmd::Tick);
                 if self.component.is_complete() {
-                    Some(Msg::NavigateTo(crate::msg::ViewId::Home))
+                    Some(Msg::NavigateTo(ViewId::Home))
                 } else {
                     Some(Msg::Tick)
                 }
             }
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('c'),
-                modifiers,
-            }) if modifiers.contains(tuirealm::event::KeyModifiers::CONTROL) => Some(Msg::Quit),
-            _ => None,
+            msg => Some(msg),
         }
     }
 }
@@ -5,14 +5,22 @@
 
 mod blog;
 mod contact;
+mod help;
 mod home;
 mod loading;
+mod palette;
 mod projects;
 mod resume;
+mod search;
+mod settings;
 
-pub use blog::Blog;
+pub use blog::{open_post_state, render_post_lines, Blog};
 pub use contact::Contact;
+pub use help::Help;
 pub use home::Home;
 pub use loading::Loading;
-pub use projects::Projects;
+pub use palette::Palette;
+pub use projects::{open_project_state, Projects};
 pub use resume::Resume;
+pub use search::Search;
+pub use settings::Settings;
@@ -0,0 +1,249 @@
+//! Command palette overlay with fuzzy search across views, posts and projects
+
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders, Clear, Paragraph};
+use tuirealm::command::{Cmd, CmdResult, Direction};
+use tuirealm::props::{AttrValue, Attribute, Props};
+use tuirealm::{Component, Event, Frame, MockComponent, NoUserEvent, State, StateValue};
+
+use crate::keymap::{keymap, route, InputMode};
+use crate::msg::{Msg, ViewId};
+use crate::palette::{search, PaletteMatch};
+use crate::styles;
+use crate::widgets::PageLayout;
+
+/// Palette MockComponent - handles the visual representation
+pub struct PaletteMock {
+    props: Props,
+    query: String,
+    results: Vec<PaletteMatch>,
+    cursor: usize,
+    /// First result shown, so the cursor stays visible
+    offset: usize,
+}
+
+impl Default for PaletteMock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PaletteMock {
+    pub fn new() -> Self {
+        Self {
+            props: Props::default(),
+            query: String::new(),
+            results: search(""),
+            cursor: 0,
+            offset: 0,
+        }
+    }
+
+    fn cursor_up(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    fn cursor_down(&mut self) {
+        if self.cursor + 1 < self.results.len() {
+            self.cursor += 1;
+        }
+    }
+
+    fn type_char(&mut self, c: char) {
+        self.query.push(c);
+        self.refresh();
+    }
+
+    fn backspace(&mut self) {
+        self.query.pop();
+        self.refresh();
+    }
+
+    /// Re-run the search and jump back to the best match
+    fn refresh(&mut self) {
+        self.results = search(&self.query);
+        self.cursor = 0;
+        self.offset = 0;
+    }
+
+    fn selected(&self) -> Option<&PaletteMatch> {
+        self.results.get(self.cursor)
+    }
+
+    fn result_line(&self, result: &PaletteMatch, selected: bool) -> Line<'static> {
+        let base = if selected {
+            styles::selected()
+        } else {
+            styles::text()
+        };
+        let highlight = if selected {
+            base.add_modifier(Modifier::UNDERLINED)
+        } else {
+            styles::accent().add_modifier(Modifier::BOLD)
+        };
+
+        let mut spans = vec![
+            Span::styled(if selected { "▸ " } else { "  " }, styles::cursor()),
+            Span::styled(format!("{:<8}", result.item.kind.label()), styles::muted()),
+        ];
+        for (i, c) in result.item.title.chars().enumerate() {
+            let style: Style = if result.positions.contains(&i) {
+                highlight
+            } else {
+                base
+            };
+            spans.push(Span::styled(c.to_string(), style));
+        }
+        if let Some(keyword) = &result.keyword {
+            spans.push(Span::styled(format!("  #{keyword}"), styles::tag()));
+        }
+        Line::from(spans)
+    }
+}
+
+impl MockComponent for PaletteMock {
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(styles::border());
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let height = PageLayout::content_area(inner).height as usize;
+        if self.cursor < self.offset {
+            self.offset = self.cursor;
+        } else if height > 0 && self.cursor >= self.offset + height {
+            self.offset = self.cursor + 1 - height;
+        }
+
+        let lines: Vec<Line> = if self.results.is_empty() {
+            vec![Line::from(Span::styled("  No matches", styles::muted()))]
+        } else {
+            self.results
+                .iter()
+                .enumerate()
+                .skip(self.offset)
+                .take(height)
+                .map(|(i, result)| self.result_line(result, i == self.cursor))
+                .collect()
+        };
+
+        PageLayout::new(format!("❯ {}▏", self.query))
+            .help(keymap().help(ViewId::Palette, InputMode::TextInput))
+            .scroll_state(self.offset, self.results.len(), height)
+            .render(frame, inner, |f, content_area| {
+                f.render_widget(Paragraph::new(lines), content_area);
+            });
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.props.get(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        self.props.set(attr, value);
+    }
+
+    fn state(&self) -> State {
+        State::One(StateValue::String(self.query.clone()))
+    }
+
+    fn perform(&mut self, cmd: Cmd) -> CmdResult {
+        match cmd {
+            Cmd::Move(Direction::Up) => {
+                self.cursor_up();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Move(Direction::Down) => {
+                self.cursor_down();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Type(c) => {
+                self.type_char(c);
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Delete => {
+                self.backspace();
+                CmdResult::Changed(self.state())
+            }
+            _ => CmdResult::None,
+        }
+    }
+}
+
+/// Palette Component - bridges MockComponent to application messages
+pub struct Palette {
+    component: PaletteMock,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Palette {
+    pub fn new() -> Self {
+        Self {
+            component: PaletteMock::new(),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for Palette {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        match route(ViewId::Palette, InputMode::TextInput, &ev)? {
+            Msg::CursorUp | Msg::ScrollUp => {
+                self.component.perform(Cmd::Move(Direction::Up));
+                Some(Msg::None)
+            }
+            Msg::CursorDown | Msg::ScrollDown => {
+                self.component.perform(Cmd::Move(Direction::Down));
+                Some(Msg::None)
+            }
+            Msg::TypeChar(c) => {
+                self.component.perform(Cmd::Type(c));
+                Some(Msg::None)
+            }
+            Msg::Backspace => {
+                self.component.perform(Cmd::Delete);
+                Some(Msg::None)
+            }
+            // The runtime closes the palette before handling the chosen item
+            Msg::Select => Some(
+                self.component
+                    .selected()
+                    .map_or(Msg::None, |result| result.item.msg.clone()),
+            ),
+            Msg::GoBack => Some(Msg::CloseOverlay),
+            Msg::Quit => Some(Msg::Quit),
+            _ => None,
+        }
+    }
+}
+
+impl MockComponent for Palette {
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        self.component.view(frame, area);
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.component.query(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        self.component.attr(attr, value);
+    }
+
+    fn state(&self) -> State {
+        self.component.state()
+    }
+
+    fn perform(&mut self, cmd: Cmd) -> CmdResult {
+        self.component.perform(cmd)
+    }
+}
@@ -1,22 +1,51 @@
-//! Projects view component with project cards and list selection
+//! Projects view component with list and detail modes
+
+use std::collections::HashMap;
 
 use ratatui::layout::Rect;
+use ratatui::style::Modifier;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::Paragraph;
-use tuirealm::command::{Cmd, CmdResult, Direction};
-use tuirealm::event::{Key, KeyEvent, KeyModifiers};
-use tuirealm::props::{AttrValue, Attribute, Props};
+use tuirealm::command::{Cmd, CmdResult, Direction, Position};
+use tuirealm::props::{AttrValue, Attribute, PropPayload, PropValue, Props};
 use tuirealm::{Component, Event, Frame, MockComponent, NoUserEvent, State, StateValue};
 
-use crate::content::PROJECTS;
+use crate::content::{projects_in_display_order, Project, PROJECTS};
+use crate::keymap::{keymap, route, InputMode};
+use crate::links::{self, hyperlinks_enabled, SpanLinks};
 use crate::msg::{Msg, ViewId};
+use crate::search::{Find, FIND_ATTR};
 use crate::styles;
-use crate::widgets::{accent_bold, featured_badge, PageLayout, SelectableItem, TagList};
+use crate::widgets::{
+    accent_bold, featured_badge, markdown_links, markdown_text, render_markdown_wrapped,
+    render_preview, stacked, wrap_text, Breakpoint, FindState, HitMap, Link, PageLayout, Passage,
+    ScrollList, SelectableItem, TagList,
+};
+
+/// Projects display mode
+#[derive(Clone, Copy, PartialEq)]
+pub enum ProjectsMode {
+    List,
+    Detail,
+}
 
 /// Projects MockComponent - handles the visual representation
 pub struct ProjectsMock {
     props: Props,
+    mode: ProjectsMode,
+    /// Position in display order (featured first); the open project in detail
     cursor: usize,
+    scroll: ScrollList,
+    /// Rows of each card on screen at the last render, by display position
+    hits: HitMap<usize>,
+    /// Focused entry of the open project's links
+    link: usize,
+    viewport: usize,
+    max_scroll: usize,
+    /// Search match being shown in the open project
+    find: Option<FindState>,
+    /// Width the open project was last wrapped to
+    wrap_width: usize,
 }
 
 impl Default for ProjectsMock {
@@ -29,87 +58,466 @@ impl ProjectsMock {
     pub fn new() -> Self {
         Self {
             props: Props::default(),
+            mode: ProjectsMode::List,
             cursor: 0,
+            scroll: ScrollList::new(),
+            hits: HitMap::new(),
+            link: 0,
+            viewport: 0,
+            max_scroll: 0,
+            find: None,
+            wrap_width: 0,
         }
     }
 
     fn cursor_up(&mut self) {
-        if self.cursor > 0 {
-            self.cursor -= 1;
+        match self.mode {
+            ProjectsMode::List => self.cursor = self.cursor.saturating_sub(1),
+            ProjectsMode::Detail => self.viewport = self.viewport.saturating_sub(1),
         }
     }
 
     fn cursor_down(&mut self) {
-        if self.cursor < PROJECTS.len().saturating_sub(1) {
-            self.cursor += 1;
+        match self.mode {
+            ProjectsMode::List => {
+                if self.cursor < PROJECTS.len().saturating_sub(1) {
+                    self.cursor += 1;
+                }
+            }
+            ProjectsMode::Detail => {
+                if self.viewport < self.max_scroll {
+                    self.viewport += 1;
+                }
+            }
         }
     }
-}
 
-impl MockComponent for ProjectsMock {
-    fn view(&mut self, frame: &mut Frame, area: Rect) {
+    fn page_up(&mut self) {
+        match self.mode {
+            ProjectsMode::List => self.cursor = self.cursor.saturating_sub(self.scroll.page()),
+            ProjectsMode::Detail => self.viewport = self.viewport.saturating_sub(10),
+        }
+    }
+
+    fn page_down(&mut self) {
+        match self.mode {
+            ProjectsMode::List => {
+                self.cursor =
+                    (self.cursor + self.scroll.page()).min(PROJECTS.len().saturating_sub(1));
+            }
+            ProjectsMode::Detail => self.viewport = (self.viewport + 10).min(self.max_scroll),
+        }
+    }
+
+    fn first(&mut self) {
+        match self.mode {
+            ProjectsMode::List => self.cursor = 0,
+            ProjectsMode::Detail => self.viewport = 0,
+        }
+    }
+
+    fn last(&mut self) {
+        match self.mode {
+            ProjectsMode::List => self.cursor = PROJECTS.len().saturating_sub(1),
+            ProjectsMode::Detail => self.viewport = self.max_scroll,
+        }
+    }
+
+    /// Project under the cursor (list) or open (detail)
+    fn current(&self) -> Option<&'static Project> {
+        projects_in_display_order().get(self.cursor).copied()
+    }
+
+    /// Open the project under the cursor
+    fn open(&mut self) {
+        if self.current().is_some() {
+            self.mode = ProjectsMode::Detail;
+            self.link = 0;
+            self.viewport = 0;
+            self.find = None;
+        }
+    }
+
+    /// Select the clicked card in the list, or open it if already selected
+    fn click(&mut self, column: u16, row: u16) {
+        match self.hits.at(column, row) {
+            Some(pos) if pos == self.cursor => self.open(),
+            Some(pos) => self.cursor = pos,
+            None => {}
+        }
+    }
+
+    /// Leave the detail page; returns whether there was one to leave
+    fn back(&mut self) -> bool {
+        if self.mode == ProjectsMode::Detail {
+            self.mode = ProjectsMode::List;
+            self.viewport = 0;
+            self.max_scroll = 0;
+            self.find = None;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Step to the next (`forward`) or previous project, keeping the mode
+    fn step(&mut self, forward: bool) {
+        let last = PROJECTS.len().saturating_sub(1);
+        let cursor = if forward {
+            (self.cursor + 1).min(last)
+        } else {
+            self.cursor.saturating_sub(1)
+        };
+        if cursor != self.cursor {
+            self.cursor = cursor;
+            self.link = 0;
+            self.viewport = 0;
+            self.find = None;
+        }
+    }
+
+    fn find_next(&mut self) {
+        if let Some(find) = &mut self.find {
+            find.next();
+        }
+    }
+
+    fn find_prev(&mut self) {
+        if let Some(find) = &mut self.find {
+            find.prev();
+        }
+    }
+
+    fn link_next(&mut self) {
+        let count = self.current().map_or(0, |p| project_links(p).len());
+        if count > 0 {
+            self.link = (self.link + 1) % count;
+        }
+    }
+
+    fn link_prev(&mut self) {
+        let count = self.current().map_or(0, |p| project_links(p).len());
+        if count > 0 {
+            self.link = (self.link + count - 1) % count;
+        }
+    }
+
+    /// URL of the focused link; the first link in the list
+    fn link_url(&self) -> Option<String> {
+        let links = project_links(self.current()?);
+        let link = match self.mode {
+            ProjectsMode::List => 0,
+            ProjectsMode::Detail => self.link,
+        };
+        links.get(link).or(links.first()).map(|link| link.url.clone())
+    }
+
+    /// URL of link `number` (1-based) of the project
+    fn numbered_url(&self, number: usize) -> Option<String> {
+        let links = project_links(self.current()?);
+        links.get(number.checked_sub(1)?).map(|link| link.url.clone())
+    }
+
+    /// Footer for the detail page
+    fn detail_help(&self) -> String {
+        let keymap = keymap();
+        let keys = |msg: &Msg| keymap.keys(ViewId::Projects, InputMode::Normal, msg);
+        let position = format!("{}/{}", self.cursor + 1, PROJECTS.len());
+        [
+            self.find.as_ref().map(FindState::status),
+            Some(position),
+            keys(&Msg::FocusNext).map(|k| format!("{k} link")),
+            keys(&Msg::Open).map(|k| format!("{k} open")),
+            keys(&Msg::Copy).map(|k| format!("{k} copy")),
+            match (keys(&Msg::PrevItem), keys(&Msg::NextItem)) {
+                (Some(prev), Some(next)) => Some(format!("{prev}/{next} prev/next")),
+                _ => None,
+            },
+            keymap.describe(ViewId::Projects, InputMode::Normal, &Msg::GoBack),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" • ")
+    }
+
+    fn render_list(&mut self, frame: &mut Frame, area: Rect) {
+        // Build project cards, featured first; descriptions are left out
+        // where space is short, or shown in the preview
+        let breakpoint = Breakpoint::of(area);
+        let brief = breakpoint != Breakpoint::Normal;
+        let cards: Vec<Vec<Line>> = projects_in_display_order()
+            .iter()
+            .enumerate()
+            .map(|(i, project)| project_card(project, i == self.cursor, brief))
+            .collect();
+        let heights: Vec<usize> = cards.iter().map(Vec::len).collect();
+
+        let content_area = PageLayout::content_area(area);
+        let height = content_area.height as usize;
+        let shown = self.scroll.follow(&heights, self.cursor, height);
+        let (position, total) = self.scroll.position(&heights);
+
+        let [list_area, preview_area] = breakpoint.panes(content_area);
+        let preview = match self.current() {
+            Some(project) if !preview_area.is_empty() => {
+                let width = preview_area.width.saturating_sub(3) as usize;
+                let mut lines = vec![Line::styled(project.name.to_string(), styles::title())];
+                lines.extend(project_detail(project, 0, width).0);
+                lines
+            }
+            _ => Vec::new(),
+        };
+
+        self.hits.clear();
+        let rows = stacked(list_area, &heights[shown.clone()]);
+        for (rect, pos) in rows.into_iter().zip(shown.clone()) {
+            self.hits.push(rect, pos);
+        }
+        // Clicking the preview opens the project
+        self.hits.push(preview_area, self.cursor);
+
         PageLayout::new("🚀 Projects")
-            .help("↑/k up • ↓/j down • esc back")
+            .breadcrumbs(["Home"])
+            .help(keymap().help(ViewId::Projects, InputMode::Normal))
+            .scroll_state(position, total, height)
             .render(frame, area, |f, content_area| {
-                // Sort projects: featured first, then others
-                let mut featured: Vec<_> = PROJECTS.iter().filter(|p| p.featured).collect();
-                let mut other: Vec<_> = PROJECTS.iter().filter(|p| !p.featured).collect();
-                featured.append(&mut other);
-
-                // Build project list
-                let mut lines: Vec<Line> = Vec::new();
-                for (i, project) in featured.iter().enumerate() {
-                    let is_selected = i == self.cursor;
-
-                    // Name line with featured badge
-                    let mut item = SelectableItem::new(project.name)
-                        .selected(is_selected)
-                        .unselected_style(accent_bold());
-
-                    if project.featured {
-                        item = item.badge(featured_badge());
-                    }
-
-                    lines.push(item.to_line());
-
-                    // Description
-                    lines.push(Line::from(vec![
-                        Span::raw("    "),
-                        Span::styled(project.description.to_string(), styles::text()),
-                    ]));
-
-                    // Tech stack tags
-                    lines.push(TagList::from_vec(&project.tech_stack).indent(4).to_line());
-
-                    // Links
-                    let mut link_parts = Vec::new();
-                    if let Some(github) = project.github_url {
-                        link_parts.push(format!("GitHub: {}", github));
-                    }
-                    if let Some(url) = project.url {
-                        link_parts.push(format!("Live: {}", url));
-                    }
-                    if !link_parts.is_empty() {
-                        lines.push(Line::from(vec![
-                            Span::raw("    "),
-                            Span::styled(link_parts.join(" • "), styles::muted()),
-                        ]));
-                    }
-
-                    lines.push(Line::from(""));
+                let lines: Vec<Line> = cards[shown].iter().flatten().cloned().collect();
+                let content = Paragraph::new(lines);
+                f.render_widget(content, list_area.intersection(content_area));
+
+                if !preview_area.is_empty() {
+                    render_preview(f, preview_area.intersection(content_area), preview);
                 }
+            });
+    }
 
-                let content = Paragraph::new(lines);
-                f.render_widget(content, content_area);
+    fn render_project(&mut self, frame: &mut Frame, area: Rect) {
+        self.hits.clear();
+        let Some(project) = self.current() else {
+            return;
+        };
+
+        let content_area = PageLayout::content_area(area);
+        // Account for scrollbar (1 col) when calculating wrap width
+        let wrap_width = content_area.width.saturating_sub(2) as usize;
+        let (lines, links, passages) = project_detail(project, self.link, wrap_width);
+
+        // Index search matches on first render and after a resize
+        if let Some(find) = &mut self.find {
+            if find.needs_index() || wrap_width != self.wrap_width {
+                find.index(&lines, &passages);
+            }
+            if let Some(line) = find.take_jump() {
+                self.viewport = line.saturating_sub(2);
+            }
+        }
+        self.wrap_width = wrap_width;
+
+        let height = content_area.height as usize;
+        self.max_scroll = lines.len().saturating_sub(height);
+        self.viewport = self.viewport.min(self.max_scroll);
+
+        let title = if project.featured {
+            format!("🚀 {} ★", project.name)
+        } else {
+            format!("🚀 {}", project.name)
+        };
+
+        PageLayout::new(title)
+            .breadcrumbs(["Home", "Projects"])
+            .help(self.detail_help())
+            .scroll_state(self.viewport, lines.len(), height)
+            .render(frame, area, |f, content_rect| {
+                let end = (self.viewport + height).min(lines.len());
+                let start = self.viewport.min(end);
+                let visible: Vec<Line> = (start..end)
+                    .map(|row| match &self.find {
+                        Some(find) => find.highlight(row, lines[row].clone()),
+                        None => lines[row].clone(),
+                    })
+                    .collect();
+                f.render_widget(Paragraph::new(visible), content_rect);
+                let links = links[start..end].iter().map(Vec::as_slice);
+                links::place(content_rect, lines[start..end].iter().zip(links));
             });
     }
+}
+
+/// Links of a project: source, live site, then links in the description
+fn project_links(project: &Project) -> Vec<Link> {
+    let mut links: Vec<Link> = [("GitHub", project.github_url), ("Live", project.url)]
+        .into_iter()
+        .filter_map(|(label, url)| {
+            url.map(|url| Link {
+                text: label.to_string(),
+                url: url.to_string(),
+            })
+        })
+        .collect();
+    for link in markdown_links(project.description) {
+        if !links.iter().any(|l| l.url == link.url) {
+            links.push(link);
+        }
+    }
+    links
+}
+
+/// Lines of the detail page: excerpt, tech stack, links and the description,
+/// with the URLs of the links on each line and the description's passages
+/// for in-page find
+fn project_detail(
+    project: &Project,
+    link: usize,
+    width: usize,
+) -> (Vec<Line<'static>>, Vec<SpanLinks>, Vec<Passage>) {
+    let mut rows: Vec<(Line, SpanLinks)> = wrap_text(project.excerpt, width)
+        .into_iter()
+        .map(|text| {
+            let style = styles::muted().add_modifier(Modifier::ITALIC);
+            (Line::from(Span::styled(text, style)), Vec::new())
+        })
+        .collect();
+    rows.push((Line::from(""), Vec::new()));
+    rows.push((TagList::from_vec(&project.tech_stack).to_line(), Vec::new()));
+    rows.push((Line::from(""), Vec::new()));
+
+    // Numbered for copying with the digit keys where links aren't clickable
+    let numbered = !hyperlinks_enabled();
+    let links = project_links(project);
+    for (i, item) in links.iter().enumerate() {
+        let focused = i == link;
+        let mut spans = vec![Span::styled(
+            if focused { "▸ " } else { "  " },
+            styles::cursor(),
+        )];
+        if numbered {
+            spans.push(Span::styled(format!("[{}] ", i + 1), styles::muted()));
+        }
+        spans.push(Span::styled(
+            format!("{:<8}", item.text),
+            if focused { styles::selected() } else { styles::muted() },
+        ));
+        spans.push(Span::raw(" "));
+        let url = vec![(spans.len(), item.url.clone())];
+        spans.push(Span::styled(item.url.clone(), styles::link()));
+        rows.push((Line::from(spans), url));
+    }
+    if !links.is_empty() {
+        rows.push((Line::from(""), Vec::new()));
+    }
+
+    let mut passages = Vec::new();
+    for line in project.description.lines() {
+        let start = rows.len();
+        rows.extend(render_markdown_wrapped(line, width));
+        passages.push(Passage {
+            text: markdown_text(line),
+            rows: start..rows.len(),
+        });
+    }
+    let (lines, links) = rows.into_iter().unzip();
+    (lines, links, passages)
+}
+
+/// History snapshot that opens a project's detail page
+pub fn open_project_state(project: usize) -> AttrValue {
+    snapshot_value(project, true, 0)
+}
+
+fn snapshot_value(project: usize, detail: bool, viewport: usize) -> AttrValue {
+    AttrValue::Payload(PropPayload::Map(HashMap::from([
+        ("project".to_string(), PropValue::Usize(project)),
+        ("detail".to_string(), PropValue::Bool(detail)),
+        ("viewport".to_string(), PropValue::Usize(viewport)),
+    ])))
+}
+
+/// List entry for a project; brief entries are a single line with its name
+fn project_card(project: &Project, selected: bool, brief: bool) -> Vec<Line<'static>> {
+    // Name line with featured badge
+    let mut item = SelectableItem::new(project.name)
+        .selected(selected)
+        .unselected_style(accent_bold());
+
+    if project.featured {
+        item = item.badge(featured_badge());
+    }
+    if brief {
+        return vec![item.to_line()];
+    }
+
+    let mut lines = vec![
+        item.to_line(),
+        // Description
+        Line::from(vec![
+            Span::raw("    "),
+            Span::styled(project.description.to_string(), styles::text()),
+        ]),
+        // Tech stack tags
+        TagList::from_vec(&project.tech_stack).indent(4).to_line(),
+    ];
+
+    // Links
+    let mut link_spans = Vec::new();
+    for (label, url) in [("GitHub", project.github_url), ("Live", project.url)] {
+        let Some(url) = url else {
+            continue;
+        };
+        link_spans.push(Span::styled(
+            if link_spans.is_empty() { format!("{label}: ") } else { format!(" • {label}: ") },
+            styles::muted(),
+        ));
+        link_spans.push(Span::styled(url.to_string(), styles::link()));
+    }
+    if !link_spans.is_empty() {
+        link_spans.insert(0, Span::raw("    "));
+        lines.push(Line::from(link_spans));
+    }
+
+    lines.push(Line::from(""));
+    lines
+}
+
+impl MockComponent for ProjectsMock {
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        match self.mode {
+            ProjectsMode::List => self.render_list(frame, area),
+            ProjectsMode::Detail => self.render_project(frame, area),
+        }
+    }
 
     fn query(&self, attr: Attribute) -> Option<AttrValue> {
-        self.props.get(attr)
+        match attr {
+            // Snapshot saved in the navigation history: project under the
+            // cursor (or open) and scroll
+            Attribute::Value => Some(snapshot_value(
+                self.cursor,
+                self.mode == ProjectsMode::Detail,
+                self.viewport,
+            )),
+            _ => self.props.get(attr),
+        }
     }
 
     fn attr(&mut self, attr: Attribute, value: AttrValue) {
-        self.props.set(attr, value);
+        match (attr, value) {
+            (Attribute::Value, AttrValue::Payload(PropPayload::Map(map))) => {
+                let usize_of = |key: &str| match map.get(key) {
+                    Some(PropValue::Usize(n)) => *n,
+                    _ => 0,
+                };
+                self.cursor = usize_of("project").min(PROJECTS.len().saturating_sub(1));
+                if matches!(map.get("detail"), Some(PropValue::Bool(true))) {
+                    self.open();
+                    self.viewport = usize_of("viewport");
+                }
+            }
+            (attr, value) if attr == FIND_ATTR && self.mode == ProjectsMode::Detail => {
+                self.find = Find::from_attr(value).map(FindState::new);
+            }
+            (attr, value) => self.props.set(attr, value),
+        }
     }
 
     fn state(&self) -> State {
@@ -126,6 +534,50 @@ impl MockComponent for ProjectsMock {
                 self.cursor_down();
                 CmdResult::Changed(self.state())
             }
+            Cmd::Scroll(Direction::Up) => {
+                self.page_up();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Scroll(Direction::Down) => {
+                self.page_down();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::GoTo(Position::Begin) => {
+                self.first();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::GoTo(Position::End) => {
+                self.last();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Submit => {
+                self.open();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Move(Direction::Right) => {
+                self.link_next();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Move(Direction::Left) => {
+                self.link_prev();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Custom("next_project") => {
+                self.step(true);
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Custom("prev_project") => {
+                self.step(false);
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Custom("find_next") => {
+                self.find_next();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Custom("find_prev") => {
+                self.find_prev();
+                CmdResult::Changed(self.state())
+            }
             _ => CmdResult::None,
         }
     }
@@ -152,48 +604,83 @@ impl Projects {
 
 impl Component<Msg, NoUserEvent> for Projects {
     fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
-        match ev {
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('c'),
-                modifiers,
-            }) if modifiers.contains(KeyModifiers::CONTROL) => Some(Msg::Quit),
-            Event::Keyboard(KeyEvent { code: Key::Esc, .. })
-            | Event::Keyboard(KeyEvent {
-                code: Key::Char('q'),
-                ..
-            }) => Some(Msg::GoBack),
-            Event::Keyboard(KeyEvent {
-                code: Key::Up, ..
-            })
-            | Event::Keyboard(KeyEvent {
-                code: Key::Char('k'),
-                ..
-            }) => {
+        match route(ViewId::Projects, InputMode::Normal, &ev)? {
+            Msg::GoBack => {
+                if self.component.back() {
+                    Some(Msg::None)
+                } else {
+                    Some(Msg::GoBack)
+                }
+            }
+            // Enter on an open project follows its focused link
+            Msg::Select if self.component.mode == ProjectsMode::Detail => {
+                Some(self.component.link_url().map_or(Msg::None, Msg::OpenUrl))
+            }
+            Msg::Select => {
+                self.component.perform(Cmd::Submit);
+                Some(Msg::None)
+            }
+            Msg::Click(column, row) => {
+                self.component.click(column, row);
+                Some(Msg::None)
+            }
+            Msg::Open => Some(self.component.link_url().map_or(Msg::None, Msg::OpenUrl)),
+            Msg::Copy => Some(self.component.link_url().map_or(Msg::None, Msg::CopyText)),
+            Msg::CopyLink(number) => Some(
+                self.component
+                    .numbered_url(number)
+                    .map_or(Msg::None, Msg::CopyText),
+            ),
+            Msg::FocusNext => {
+                self.component.perform(Cmd::Move(Direction::Right));
+                Some(Msg::None)
+            }
+            Msg::FocusPrev => {
+                self.component.perform(Cmd::Move(Direction::Left));
+                Some(Msg::None)
+            }
+            Msg::NextItem => {
+                self.component.perform(Cmd::Custom("next_project"));
+                Some(Msg::None)
+            }
+            Msg::PrevItem => {
+                self.component.perform(Cmd::Custom("prev_project"));
+                Some(Msg::None)
+            }
+            Msg::CursorUp | Msg::ScrollUp => {
                 self.component.perform(Cmd::Move(Direction::Up));
                 Some(Msg::None)
             }
-            Event::Keyboard(KeyEvent {
-                code: Key::Down, ..
-            })
-            | Event::Keyboard(KeyEvent {
-                code: Key::Char('j'),
-                ..
-            }) => {
+            Msg::CursorDown | Msg::ScrollDown => {
                 self.component.perform(Cmd::Move(Direction::Down));
                 Some(Msg::None)
             }
-            Event::Keyboard(KeyEvent {
-                code: Key::Char(c),
-                ..
-            }) => {
-                // Check for navigation shortcuts
-                if let Some(view) = ViewId::from_shortcut(c) {
-                    Some(Msg::NavigateTo(view))
-                } else {
-                    None
-                }
+            Msg::PageUp => {
+                self.component.perform(Cmd::Scroll(Direction::Up));
+                Some(Msg::None)
+            }
+            Msg::PageDown => {
+                self.component.perform(Cmd::Scroll(Direction::Down));
+                Some(Msg::None)
+            }
+            Msg::JumpTop => {
+                self.component.perform(Cmd::GoTo(Position::Begin));
+                Some(Msg::None)
+            }
+            Msg::JumpBottom => {
+                self.component.perform(Cmd::GoTo(Position::End));
+                Some(Msg::None)
+            }
+            Msg::FindNext => {
+                self.component.perform(Cmd::Custom("find_next"));
+                Some(Msg::None)
+            }
+            Msg::FindPrev => {
+                self.component.perform(Cmd::Custom("find_prev"));
+                Some(Msg::None)
             }
-            _ => None,
+            Msg::Tick => None,
+            msg => Some(msg),
         }
     }
 }
@@ -2,14 +2,15 @@
 
 use ratatui::layout::Rect;
 use ratatui::widgets::Paragraph;
-use tuirealm::command::{Cmd, CmdResult, Direction};
-use tuirealm::event::{Key, KeyEvent, KeyModifiers};
-use tuirealm::props::{AttrValue, Attribute, Props};
+use tuirealm::command::{Cmd, CmdResult, Direction, Position};
+use tuirealm::props::{AttrValue, Attribute, PropPayload, PropValue, Props};
 use tuirealm::{Component, Event, Frame, MockComponent, NoUserEvent, State, StateValue};
 
 use crate::content::RESUME;
+use crate::keymap::{keymap, route, InputMode};
 use crate::msg::{Msg, ViewId};
-use crate::widgets::{Markdown, PageLayout};
+use crate::search::{Find, FIND_ATTR};
+use crate::widgets::{FindState, Markdown, PageLayout, Toc};
 
 /// Resume MockComponent - handles the visual representation
 pub struct ResumeMock {
@@ -18,6 +19,10 @@ pub struct ResumeMock {
     max_scroll: usize,
     last_width: u16,
     markdown: Markdown,
+    /// Table of contents from the headings
+    toc: Toc,
+    /// Search phrase highlighted with `n`/`N` stepping
+    find: Option<FindState>,
 }
 
 impl Default for ResumeMock {
@@ -28,12 +33,17 @@ impl Default for ResumeMock {
 
 impl ResumeMock {
     pub fn new() -> Self {
+        let markdown = Markdown::new(&RESUME);
+        let mut toc = Toc::new();
+        toc.set_headings(markdown.headings());
         Self {
             props: Props::default(),
             viewport: 0,
             max_scroll: 0,
             last_width: 0,
-            markdown: Markdown::new(&RESUME),
+            markdown,
+            toc,
+            find: None,
         }
     }
 
@@ -57,15 +67,67 @@ impl ResumeMock {
         self.viewport = (self.viewport + 10).min(self.max_scroll);
     }
 
+    fn top(&mut self) {
+        self.viewport = 0;
+    }
+
+    fn bottom(&mut self) {
+        self.viewport = self.max_scroll;
+    }
+
     /// Re-wrap content if terminal width changed
     fn update_wrap(&mut self, width: u16) {
         if width != self.last_width && width > 0 {
+            let rewrap = self.last_width != 0;
             // Account for scrollbar
             let wrap_width = width.saturating_sub(2) as usize;
+            let section = self.toc.current(self.viewport);
             self.markdown = Markdown::wrapped(&RESUME, wrap_width);
+            self.toc.set_headings(self.markdown.headings());
             self.last_width = width;
-            // Reset viewport to avoid being past the end
-            self.viewport = 0;
+            // Line offsets shift on rewrap, so go to the start of the section
+            // being read; a restored viewport is kept on first wrap
+            if rewrap {
+                self.viewport = section.and_then(|idx| self.toc.line(idx)).unwrap_or(0);
+            }
+            if let Some(find) = &mut self.find {
+                find.invalidate();
+            }
+        }
+    }
+
+    /// Index matches after a rewrap and scroll to a pending match
+    fn update_find(&mut self) {
+        if let Some(find) = self.find.as_mut().filter(|f| f.needs_index()) {
+            find.index(&self.markdown.render_all(), self.markdown.passages());
+        }
+        if let Some(line) = self.find.as_mut().and_then(FindState::take_jump) {
+            // Keep a little context above the match
+            self.viewport = line.saturating_sub(2);
+        }
+    }
+
+    fn next_section(&mut self) {
+        if let Some(line) = self.toc.next_line(self.viewport) {
+            self.viewport = line.min(self.max_scroll);
+        }
+    }
+
+    fn prev_section(&mut self) {
+        if let Some(line) = self.toc.prev_line(self.viewport) {
+            self.viewport = line;
+        }
+    }
+
+    fn find_next(&mut self) {
+        if let Some(find) = &mut self.find {
+            find.next();
+        }
+    }
+
+    fn find_prev(&mut self) {
+        if let Some(find) = &mut self.find {
+            find.prev();
         }
     }
 }
@@ -73,9 +135,10 @@ impl ResumeMock {
 impl MockComponent for ResumeMock {
     fn view(&mut self, frame: &mut Frame, area: Rect) {
         let content_area = PageLayout::content_area(area);
+        let [sidebar, text_area] = self.toc.split(area, content_area);
 
         // Re-wrap if width changed
-        self.update_wrap(content_area.width);
+        self.update_wrap(text_area.width);
 
         let viewable_height = content_area.height as usize;
         let content_length = self.markdown.len();
@@ -83,26 +146,66 @@ impl MockComponent for ResumeMock {
         // Calculate and store scroll bounds for use in scroll methods
         self.max_scroll = content_length.saturating_sub(viewable_height);
 
+        self.update_find();
+
         // Clamp viewport to valid range
         self.viewport = self.viewport.min(self.max_scroll);
 
+        let mut help = keymap().help(ViewId::Resume, InputMode::Normal);
+        if let Some(find) = &self.find {
+            help = format!("{} • {help}", find.status());
+        }
+
         PageLayout::new("📄 Resume")
-            .help("↑/k up • ↓/j down • u/d page up/down • esc back")
+            .breadcrumbs(["Home"])
+            .help(help)
             .scroll_state(self.viewport, content_length, viewable_height)
             .render(frame, area, |f, content_rect| {
                 let end = (self.viewport + viewable_height).min(content_length);
-                let visible_lines = self.markdown.render_range(self.viewport, end);
+                let mut visible_lines = self.markdown.render_range(self.viewport, end);
+                if let Some(find) = &self.find {
+                    visible_lines = (self.viewport..)
+                        .zip(visible_lines)
+                        .map(|(row, line)| find.highlight(row, line))
+                        .collect();
+                }
                 let content = Paragraph::new(visible_lines);
-                f.render_widget(content, content_rect);
+                let indent = text_area.x - content_rect.x;
+                let text_rect = Rect {
+                    x: text_area.x,
+                    width: content_rect.width.saturating_sub(indent),
+                    ..content_rect
+                };
+                f.render_widget(content, text_rect);
+                self.markdown.place_links(text_rect, self.viewport);
+                self.toc.render_sidebar(f, sidebar, self.viewport);
             });
+        self.toc.render_popup(frame, area, self.viewport);
     }
 
     fn query(&self, attr: Attribute) -> Option<AttrValue> {
-        self.props.get(attr)
+        match attr {
+            // Snapshot saved in the navigation history
+            Attribute::Value => Some(AttrValue::Payload(PropPayload::One(PropValue::Usize(
+                self.viewport,
+            )))),
+            _ => self.props.get(attr),
+        }
     }
 
     fn attr(&mut self, attr: Attribute, value: AttrValue) {
-        self.props.set(attr, value);
+        match (attr, value) {
+            (
+                Attribute::Value,
+                AttrValue::Payload(PropPayload::One(PropValue::Usize(viewport))),
+            ) => {
+                self.viewport = viewport;
+            }
+            (attr, value) if attr == FIND_ATTR => {
+                self.find = Find::from_attr(value).map(FindState::new);
+            }
+            (attr, value) => self.props.set(attr, value),
+        }
     }
 
     fn state(&self) -> State {
@@ -119,14 +222,42 @@ impl MockComponent for ResumeMock {
                 self.scroll_down();
                 CmdResult::Changed(self.state())
             }
-            Cmd::GoTo(tuirealm::command::Position::Begin) => {
+            Cmd::GoTo(Position::Begin) => {
                 self.page_up();
                 CmdResult::Changed(self.state())
             }
-            Cmd::GoTo(tuirealm::command::Position::End) => {
+            Cmd::GoTo(Position::End) => {
                 self.page_down();
                 CmdResult::Changed(self.state())
             }
+            Cmd::Custom("top") => {
+                self.top();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Custom("bottom") => {
+                self.bottom();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Custom("next_section") => {
+                self.next_section();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Custom("prev_section") => {
+                self.prev_section();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Custom("toc") => {
+                self.toc.toggle(self.viewport);
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Custom("find_next") => {
+                self.find_next();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Custom("find_prev") => {
+                self.find_prev();
+                CmdResult::Changed(self.state())
+            }
             _ => CmdResult::None,
         }
     }
@@ -153,71 +284,100 @@ impl Resume {
 
 impl Component<Msg, NoUserEvent> for Resume {
     fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
-        match ev {
-            Event::Keyboard(KeyEvent {
-                code: Key::Char('c'),
-                modifiers,
-            }) if modifiers.contains(KeyModifiers::CONTROL) => Some(Msg::Quit),
-            Event::Keyboard(KeyEvent { code: Key::Esc, .. })
-            | Event::Keyboard(KeyEvent {
-                code: Key::Char('q'),
-                ..
-            }) => Some(Msg::GoBack),
-            Event::Keyboard(KeyEvent {
-                code: Key::Up, ..
-            })
-            | Event::Keyboard(KeyEvent {
-                code: Key::Char('k'),
-                ..
-            }) => {
+        let msg = route(ViewId::Resume, InputMode::Normal, &ev)?;
+
+        // The contents popup takes the movement keys while open
+        let toc = &mut self.component.toc;
+        if toc.is_open() {
+            return match msg {
+                Msg::CursorUp | Msg::ScrollUp => {
+                    toc.cursor_up();
+                    Some(Msg::None)
+                }
+                Msg::CursorDown | Msg::ScrollDown => {
+                    toc.cursor_down();
+                    Some(Msg::None)
+                }
+                Msg::Select => {
+                    if let Some(line) = toc.choose() {
+                        self.component.viewport = line;
+                    }
+                    Some(Msg::None)
+                }
+                Msg::Click(column, row) => {
+                    match toc.click(column, row) {
+                        Some(line) => self.component.viewport = line,
+                        None => toc.close(),
+                    }
+                    Some(Msg::None)
+                }
+                Msg::GoBack | Msg::ToggleToc => {
+                    toc.close();
+                    Some(Msg::None)
+                }
+                Msg::Tick => None,
+                msg => {
+                    toc.close();
+                    Some(msg)
+                }
+            };
+        }
+
+        match msg {
+            Msg::CursorUp | Msg::ScrollUp => {
                 self.component.perform(Cmd::Scroll(Direction::Up));
                 Some(Msg::None)
             }
-            Event::Keyboard(KeyEvent {
-                code: Key::Down, ..
-            })
-            | Event::Keyboard(KeyEvent {
-                code: Key::Char('j'),
-                ..
-            }) => {
+            Msg::CursorDown | Msg::ScrollDown => {
                 self.component.perform(Cmd::Scroll(Direction::Down));
                 Some(Msg::None)
             }
-            Event::Keyboard(KeyEvent {
-                code: Key::PageUp, ..
-            })
-            | Event::Keyboard(KeyEvent {
-                code: Key::Char('u'),
-                ..
-            }) => {
+            Msg::PageUp => {
                 self.component
-                    .perform(Cmd::GoTo(tuirealm::command::Position::Begin));
+                    .perform(Cmd::GoTo(Position::Begin));
                 Some(Msg::None)
             }
-            Event::Keyboard(KeyEvent {
-                code: Key::PageDown,
-                ..
-            })
-            | Event::Keyboard(KeyEvent {
-                code: Key::Char('d'),
-                ..
-            }) => {
+            Msg::PageDown => {
                 self.component
-                    .perform(Cmd::GoTo(tuirealm::command::Position::End));
+                    .perform(Cmd::GoTo(Position::End));
                 Some(Msg::None)
             }
-            Event::Keyboard(KeyEvent {
-                code: Key::Char(c),
-                ..
-            }) => {
-                // Check for navigation shortcuts
-                if let Some(view) = ViewId::from_shortcut(c) {
-                    Some(Msg::NavigateTo(view))
-                } else {
-                    None
+            Msg::JumpTop => {
+                self.component.perform(Cmd::Custom("top"));
+                Some(Msg::None)
+            }
+            Msg::JumpBottom => {
+                self.component.perform(Cmd::Custom("bottom"));
+                Some(Msg::None)
+            }
+            Msg::NextItem => {
+                self.component.perform(Cmd::Custom("next_section"));
+                Some(Msg::None)
+            }
+            Msg::PrevItem => {
+                self.component.perform(Cmd::Custom("prev_section"));
+                Some(Msg::None)
+            }
+            Msg::ToggleToc => {
+                self.component.perform(Cmd::Custom("toc"));
+                Some(Msg::None)
+            }
+            Msg::Click(column, row) => {
+                if let Some(line) = self.component.toc.click(column, row) {
+                    self.component.viewport = line;
                 }
+                Some(Msg::None)
+            }
+            Msg::FindNext => {
+                self.component.perform(Cmd::Custom("find_next"));
+                Some(Msg::None)
+            }
+            Msg::FindPrev => {
+                self.component.perform(Cmd::Custom("find_prev"));
+                Some(Msg::None)
             }
-            _ => None,
+            Msg::Tick => None,
+            msg => Some(msg),
         }
     }
 }
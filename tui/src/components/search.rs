@@ -0,0 +1,278 @@
+//! Full-text search view with highlighted snippets
+
+use ratatui::layout::Rect;
+use ratatui::style::Modifier;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+use tuirealm::command::{Cmd, CmdResult, Direction};
+use tuirealm::props::{AttrValue, Attribute, PropPayload, PropValue, Props};
+use tuirealm::{Component, Event, Frame, MockComponent, NoUserEvent, State, StateValue};
+
+use crate::keymap::{keymap, route, InputMode};
+use crate::msg::{Msg, ViewId};
+use crate::search::{search, SearchHit};
+use crate::styles;
+use crate::widgets::PageLayout;
+
+/// Lines each result takes: title, snippet, blank
+const RESULT_HEIGHT: usize = 3;
+
+/// Search MockComponent - handles the visual representation
+pub struct SearchMock {
+    props: Props,
+    query: String,
+    results: Vec<SearchHit>,
+    cursor: usize,
+    /// First result shown, so the cursor stays visible
+    offset: usize,
+}
+
+impl Default for SearchMock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SearchMock {
+    pub fn new() -> Self {
+        Self {
+            props: Props::default(),
+            query: String::new(),
+            results: Vec::new(),
+            cursor: 0,
+            offset: 0,
+        }
+    }
+
+    fn cursor_up(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    fn cursor_down(&mut self) {
+        if self.cursor + 1 < self.results.len() {
+            self.cursor += 1;
+        }
+    }
+
+    fn type_char(&mut self, c: char) {
+        self.query.push(c);
+        self.refresh();
+    }
+
+    fn backspace(&mut self) {
+        self.query.pop();
+        self.refresh();
+    }
+
+    /// Re-run the search and jump back to the best match
+    fn refresh(&mut self) {
+        self.results = search(&self.query);
+        self.cursor = 0;
+        self.offset = 0;
+    }
+
+    /// Restore a history snapshot: the query and the selected result
+    fn restore(&mut self, query: String, cursor: usize) {
+        self.query = query;
+        self.refresh();
+        self.cursor = cursor.min(self.results.len().saturating_sub(1));
+    }
+
+    fn selected(&self) -> Option<&SearchHit> {
+        self.results.get(self.cursor)
+    }
+
+    fn result_lines(&self, hit: &SearchHit, selected: bool) -> Vec<Line<'static>> {
+        let title_style = if selected {
+            styles::selected()
+        } else {
+            styles::text().add_modifier(Modifier::BOLD)
+        };
+
+        let mut snippet = vec![Span::raw("    ")];
+        let chars: Vec<char> = hit.snippet.chars().collect();
+        let (start, end) = (hit.highlight.start, hit.highlight.end.min(chars.len()));
+        snippet.push(Span::styled(
+            chars[..start].iter().collect::<String>(),
+            styles::muted(),
+        ));
+        snippet.push(Span::styled(
+            chars[start..end].iter().collect::<String>(),
+            styles::search_match(),
+        ));
+        snippet.push(Span::styled(
+            chars[end..].iter().collect::<String>(),
+            styles::muted(),
+        ));
+
+        vec![
+            Line::from(vec![
+                Span::styled(if selected { "▸ " } else { "  " }, styles::cursor()),
+                Span::styled(format!("{:<8}", hit.source.label()), styles::muted()),
+                Span::styled(hit.title.clone(), title_style),
+            ]),
+            Line::from(snippet),
+            Line::from(""),
+        ]
+    }
+}
+
+impl MockComponent for SearchMock {
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let height = PageLayout::content_area(area).height as usize;
+        let per_page = (height / RESULT_HEIGHT).max(1);
+        if self.cursor < self.offset {
+            self.offset = self.cursor;
+        } else if self.cursor >= self.offset + per_page {
+            self.offset = self.cursor + 1 - per_page;
+        }
+
+        let lines: Vec<Line> = if self.query.trim().is_empty() {
+            vec![Line::from(Span::styled(
+                "  Type to search posts, projects and the resume",
+                styles::muted(),
+            ))]
+        } else if self.results.is_empty() {
+            vec![Line::from(Span::styled("  No matches", styles::muted()))]
+        } else {
+            self.results
+                .iter()
+                .enumerate()
+                .skip(self.offset)
+                .take(per_page)
+                .flat_map(|(i, hit)| self.result_lines(hit, i == self.cursor))
+                .collect()
+        };
+
+        PageLayout::new(format!("🔍 {}▏", self.query))
+            .breadcrumbs(["Home"])
+            .help(keymap().help(ViewId::Search, InputMode::TextInput))
+            .scroll_state(self.offset, self.results.len(), per_page)
+            .render(frame, area, |f, content_area| {
+                f.render_widget(Paragraph::new(lines), content_area);
+            });
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        match attr {
+            // Snapshot saved in the navigation history: (query, selected result)
+            Attribute::Value => Some(AttrValue::Payload(PropPayload::Tup2((
+                PropValue::Str(self.query.clone()),
+                PropValue::Usize(self.cursor),
+            )))),
+            _ => self.props.get(attr),
+        }
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        match (attr, value) {
+            (
+                Attribute::Value,
+                AttrValue::Payload(PropPayload::Tup2((
+                    PropValue::Str(query),
+                    PropValue::Usize(cursor),
+                ))),
+            ) => self.restore(query, cursor),
+            (attr, value) => self.props.set(attr, value),
+        }
+    }
+
+    fn state(&self) -> State {
+        State::One(StateValue::String(self.query.clone()))
+    }
+
+    fn perform(&mut self, cmd: Cmd) -> CmdResult {
+        match cmd {
+            Cmd::Move(Direction::Up) => {
+                self.cursor_up();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Move(Direction::Down) => {
+                self.cursor_down();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Type(c) => {
+                self.type_char(c);
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Delete => {
+                self.backspace();
+                CmdResult::Changed(self.state())
+            }
+            _ => CmdResult::None,
+        }
+    }
+}
+
+/// Search Component - bridges MockComponent to application messages
+pub struct Search {
+    component: SearchMock,
+}
+
+impl Default for Search {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Search {
+    pub fn new() -> Self {
+        Self {
+            component: SearchMock::new(),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for Search {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        match route(ViewId::Search, InputMode::TextInput, &ev)? {
+            Msg::CursorUp | Msg::ScrollUp => {
+                self.component.perform(Cmd::Move(Direction::Up));
+                Some(Msg::None)
+            }
+            Msg::CursorDown | Msg::ScrollDown => {
+                self.component.perform(Cmd::Move(Direction::Down));
+                Some(Msg::None)
+            }
+            Msg::TypeChar(c) => {
+                self.component.perform(Cmd::Type(c));
+                Some(Msg::None)
+            }
+            Msg::Backspace => {
+                self.component.perform(Cmd::Delete);
+                Some(Msg::None)
+            }
+            Msg::Select => Some(self.component.selected().map_or(
+                Msg::None,
+                |hit| match &hit.find {
+                    Some(find) => Msg::OpenMatch(hit.route.clone(), find.clone()),
+                    None => Msg::OpenRoute(hit.route.clone()),
+                },
+            )),
+            Msg::Tick => None,
+            msg => Some(msg),
+        }
+    }
+}
+
+impl MockComponent for Search {
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        self.component.view(frame, area);
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.component.query(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        self.component.attr(attr, value);
+    }
+
+    fn state(&self) -> State {
+        self.component.state()
+    }
+
+    fn perform(&mut self, cmd: Cmd) -> CmdResult {
+        self.component.perform(cmd)
+    }
+}
@@ -0,0 +1,206 @@
+//! Settings view component for choosing a keybinding preset
+
+use ratatui::layout::Rect;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+use tuirealm::command::{Cmd, CmdResult, Direction};
+use tuirealm::props::{AttrValue, Attribute, PropPayload, PropValue, Props};
+use tuirealm::{Component, Event, Frame, MockComponent, NoUserEvent, State, StateValue};
+
+use crate::keymap::{keymap, route, InputMode, Keymap, Preset};
+use crate::msg::{Msg, ViewId};
+use crate::styles;
+use crate::widgets::{PageLayout, SelectableItem};
+
+/// Settings MockComponent - handles the visual representation
+pub struct SettingsMock {
+    props: Props,
+    cursor: usize,
+}
+
+impl Default for SettingsMock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SettingsMock {
+    pub fn new() -> Self {
+        // Start on the preset currently in use
+        let active = keymap().active_preset();
+        let cursor = Preset::ALL
+            .iter()
+            .position(|p| Some(*p) == active)
+            .unwrap_or(0);
+
+        Self {
+            props: Props::default(),
+            cursor,
+        }
+    }
+
+    fn cursor_up(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+        }
+    }
+
+    fn cursor_down(&mut self) {
+        if self.cursor < Preset::ALL.len() - 1 {
+            self.cursor += 1;
+        }
+    }
+
+    fn selected_preset(&self) -> Preset {
+        Preset::ALL[self.cursor]
+    }
+}
+
+impl MockComponent for SettingsMock {
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        let active = keymap().active_preset();
+
+        PageLayout::new("⚙ Settings")
+            .breadcrumbs(["Home"])
+            .help(keymap().help(ViewId::Settings, InputMode::Normal))
+            .render(frame, area, |f, content_area| {
+                let mut lines: Vec<Line> = vec![
+                    Line::from(Span::styled("Keybindings", styles::subtitle())),
+                    Line::from(Span::styled(
+                        "Applies to this session only.",
+                        styles::muted(),
+                    )),
+                    Line::from(""),
+                ];
+
+                for (i, preset) in Preset::ALL.iter().enumerate() {
+                    let mut item = SelectableItem::new(preset.name()).selected(i == self.cursor);
+                    if Some(*preset) == active {
+                        item = item.badge(Span::styled(" ● active", styles::accent()));
+                    }
+                    lines.push(item.to_line());
+
+                    lines.push(Line::from(vec![
+                        Span::raw("    "),
+                        Span::styled(preset.description(), styles::text()),
+                    ]));
+
+                    // Preview of the footer this preset produces
+                    lines.push(Line::from(vec![
+                        Span::raw("    "),
+                        Span::styled(
+                            Keymap::preset(*preset).help(ViewId::Resume, InputMode::Normal),
+                            styles::muted(),
+                        ),
+                    ]));
+                    lines.push(Line::from(""));
+                }
+
+                if active.is_none() {
+                    lines.push(Line::from(Span::styled(
+                        "A custom keymap file is in use; choosing a preset replaces it.",
+                        styles::warning(),
+                    )));
+                }
+
+                let content = Paragraph::new(lines);
+                f.render_widget(content, content_area);
+            });
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        match attr {
+            // Snapshot saved in the navigation history
+            Attribute::Value => Some(AttrValue::Payload(PropPayload::One(PropValue::Usize(
+                self.cursor,
+            )))),
+            _ => self.props.get(attr),
+        }
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        match (attr, value) {
+            (Attribute::Value, AttrValue::Payload(PropPayload::One(PropValue::Usize(cursor)))) => {
+                self.cursor = cursor.min(Preset::ALL.len() - 1);
+            }
+            (attr, value) => self.props.set(attr, value),
+        }
+    }
+
+    fn state(&self) -> State {
+        State::One(StateValue::Usize(self.cursor))
+    }
+
+    fn perform(&mut self, cmd: Cmd) -> CmdResult {
+        match cmd {
+            Cmd::Move(Direction::Up) => {
+                self.cursor_up();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Move(Direction::Down) => {
+                self.cursor_down();
+                CmdResult::Changed(self.state())
+            }
+            _ => CmdResult::None,
+        }
+    }
+}
+
+/// Settings Component - bridges MockComponent to application messages
+pub struct Settings {
+    component: SettingsMock,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Settings {
+    pub fn new() -> Self {
+        Self {
+            component: SettingsMock::new(),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for Settings {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        match route(ViewId::Settings, InputMode::Normal, &ev)? {
+            Msg::CursorUp | Msg::ScrollUp => {
+                self.component.perform(Cmd::Move(Direction::Up));
+                Some(Msg::None)
+            }
+            Msg::CursorDown | Msg::ScrollDown => {
+                self.component.perform(Cmd::Move(Direction::Down));
+                Some(Msg::None)
+            }
+            Msg::Select => Some(Msg::SetKeymap(self.component.selected_preset())),
+            Msg::Tick => None,
+            msg => Some(msg),
+        }
+    }
+}
+
+impl MockComponent for Settings {
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        self.component.view(frame, area);
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.component.query(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        self.component.attr(attr, value);
+    }
+
+    fn state(&self) -> State {
+        self.component.state()
+    }
+
+    fn perform(&mut self, cmd: Cmd) -> CmdResult {
+        self.component.perform(cmd)
+    }
+}
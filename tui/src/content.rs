@@ -1,23 +1,57 @@
 //! Content loaded from JSON
 //! This will later be replaced with API calls
 
-use once_cell::sync::Lazy;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use once_cell::sync::{Lazy, OnceCell};
 use serde::Deserialize;
 
 /// Raw JSON content embedded at compile time
 const CONTENT_JSON: &str = include_str!("../content.json");
 
+/// JSON the content is parsed from: the compiled-in content unless [`load`]
+/// chose other content first
+static SOURCE: OnceCell<&'static str> = OnceCell::new();
+
+/// Use `json` in place of the compiled-in content, e.g. fixtures in tests
+///
+/// Content is read once, so this only works before anything reads it.
+/// Returns whether `json` is the content in use.
+pub fn load(json: &'static str) -> bool {
+    *SOURCE.get_or_init(|| json) == json
+}
+
+fn source() -> &'static str {
+    SOURCE.get_or_init(|| CONTENT_JSON)
+}
+
 /// Root content structure matching the JSON schema
 #[derive(Debug, Deserialize)]
 struct ContentData {
     resume: String,
     bio: String,
     logo: String,
+    /// Full name on the contact card
+    name: String,
+    /// Address shown on the Contact view
+    email: String,
+    /// Root of the website, for web links to posts and projects
+    site_url: String,
+    /// Profiles listed on the contact card
+    #[serde(default)]
+    socials: Vec<SocialLink>,
     typewriter_phrases: Vec<String>,
     posts: Vec<PostData>,
     projects: Vec<ProjectData>,
 }
 
+/// Social profile from JSON, e.g. `GitHub` and its URL
+#[derive(Debug, Clone, Deserialize)]
+pub struct SocialLink {
+    pub label: String,
+    pub url: String,
+}
+
 /// Blog post from JSON
 #[derive(Debug, Clone, Deserialize)]
 pub struct PostData {
@@ -34,7 +68,6 @@ pub struct PostData {
 pub struct ProjectData {
     pub slug: String,
     pub name: String,
-    #[allow(dead_code)]
     pub excerpt: String,
     pub description: String,
     pub tech_stack: Vec<String>,
@@ -45,9 +78,19 @@ pub struct ProjectData {
 
 /// Parsed content data (lazily initialized)
 static CONTENT: Lazy<ContentData> = Lazy::new(|| {
-    serde_json::from_str(CONTENT_JSON).expect("Failed to parse content.json")
+    serde_json::from_str(source()).expect("Failed to parse content.json")
 });
 
+/// Fingerprint of the content, shown as its version in the status bar
+pub fn revision() -> u64 {
+    static REVISION: Lazy<u64> = Lazy::new(|| {
+        let mut hasher = DefaultHasher::new();
+        source().hash(&mut hasher);
+        hasher.finish()
+    });
+    *REVISION
+}
+
 // ============================================================================
 // Public API - Compatible with existing code
 // ============================================================================
@@ -70,6 +113,26 @@ pub fn logo() -> &'static str {
     &CONTENT.logo
 }
 
+/// Full name for the contact card
+pub fn name() -> &'static str {
+    &CONTENT.name
+}
+
+/// Contact email address
+pub fn email() -> &'static str {
+    &CONTENT.email
+}
+
+/// Website root without a trailing slash, e.g. `https://example.com`
+pub fn site_url() -> &'static str {
+    CONTENT.site_url.trim_end_matches('/')
+}
+
+/// Social profiles for the contact card
+pub fn socials() -> &'static [SocialLink] {
+    &CONTENT.socials
+}
+
 /// Typewriter phrases for the home view
 #[allow(dead_code)]
 pub fn typewriter_phrases() -> &'static [String] {
@@ -93,13 +156,13 @@ pub fn projects() -> &'static [ProjectData] {
 // ============================================================================
 
 /// Resume content (static reference)
-pub static RESUME: Lazy<&'static str> = Lazy::new(|| resume());
+pub static RESUME: Lazy<&'static str> = Lazy::new(resume);
 
 /// Bio text (static reference)
-pub static BIO: Lazy<&'static str> = Lazy::new(|| bio());
+pub static BIO: Lazy<&'static str> = Lazy::new(bio);
 
 /// Logo (static reference)
-pub static LOGO: Lazy<&'static str> = Lazy::new(|| logo());
+pub static LOGO: Lazy<&'static str> = Lazy::new(logo);
 
 /// Typewriter phrases (static slice reference)
 pub static TYPEWRITER_PHRASES: Lazy<Vec<&'static str>> = Lazy::new(|| {
@@ -132,6 +195,13 @@ pub static PROJECTS: Lazy<Vec<Project>> = Lazy::new(|| {
     }).collect()
 });
 
+/// Projects in the order they are listed: featured first
+pub fn projects_in_display_order() -> Vec<&'static Project> {
+    let mut projects: Vec<&'static Project> = PROJECTS.iter().collect();
+    projects.sort_by_key(|p| !p.featured);
+    projects
+}
+
 // ============================================================================
 // Legacy structs for backwards compatibility with existing views
 // ============================================================================
@@ -139,7 +209,6 @@ pub static PROJECTS: Lazy<Vec<Project>> = Lazy::new(|| {
 /// Blog post structure (references into CONTENT)
 #[derive(Clone)]
 pub struct Post {
-    #[allow(dead_code)]
     pub slug: &'static str,
     pub title: &'static str,
     pub excerpt: &'static str,
@@ -151,10 +220,8 @@ pub struct Post {
 /// Project structure (references into CONTENT)
 #[derive(Clone)]
 pub struct Project {
-    #[allow(dead_code)]
     pub slug: &'static str,
     pub name: &'static str,
-    #[allow(dead_code)]
     pub excerpt: &'static str,
     pub description: &'static str,
     pub tech_stack: Vec<&'static str>,
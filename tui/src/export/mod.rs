@@ -0,0 +1,515 @@
+//! Downloadable copies of the content: the resume as plain text, Markdown,
+//! HTML and PDF, posts and projects as Markdown, and a contact card
+//!
+//! Every format is generated from the same data in `content.json`.
+//! Exports are served by `ssh host resume --format <format>` and over SFTP.
+
+mod pdf;
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Error, Result};
+use once_cell::sync::Lazy;
+
+use crate::content::{email, name, site_url, socials, Post, Project, RESUME};
+use crate::widgets::{markdown_heading as heading, split_markdown_links, wrap_text};
+
+/// Width of the plain text export
+const TEXT_WIDTH: usize = 80;
+
+/// Usage line for a malformed export command
+const USAGE: &str = "usage: resume --format txt|md|html|pdf";
+
+/// A format the resume can be exported in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Markdown,
+    Html,
+    Pdf,
+}
+
+impl Format {
+    pub const ALL: [Format; 4] = [Format::Pdf, Format::Markdown, Format::Text, Format::Html];
+
+    /// File extension, also accepted as the format's name
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Text => "txt",
+            Format::Markdown => "md",
+            Format::Html => "html",
+            Format::Pdf => "pdf",
+        }
+    }
+
+    /// Name of the resume file in this format, e.g. `resume.pdf`
+    pub fn file_name(self) -> String {
+        format!("resume.{}", self.extension())
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.extension())
+    }
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.to_ascii_lowercase().as_str() {
+            "txt" | "text" => Ok(Format::Text),
+            "md" | "markdown" => Ok(Format::Markdown),
+            "html" => Ok(Format::Html),
+            "pdf" => Ok(Format::Pdf),
+            _ => Err(anyhow!("unknown format: {name} ({USAGE})")),
+        }
+    }
+}
+
+/// Format asked for by an SSH exec command such as `resume --format pdf`
+///
+/// `None` if the command isn't an export, e.g. the route `resume` alone.
+pub fn export_command(command: &str) -> Option<Result<Format>> {
+    let mut words = command.split_whitespace();
+    if words.next() != Some("resume") {
+        return None;
+    }
+    let args: Vec<&str> = words.collect();
+    if args.is_empty() {
+        return None;
+    }
+    Some(parse_args(&args))
+}
+
+fn parse_args(args: &[&str]) -> Result<Format> {
+    match args {
+        ["--format" | "-f", name] => name.parse(),
+        [arg] => match arg.strip_prefix("--format=") {
+            Some(name) => name.parse(),
+            None => bail!("{USAGE}"),
+        },
+        _ => bail!("{USAGE}"),
+    }
+}
+
+/// The resume in `format`, generated once and kept for later requests
+pub fn resume(format: Format) -> &'static [u8] {
+    static TEXT: Lazy<Vec<u8>> = Lazy::new(|| to_text(&RESUME).into_bytes());
+    static HTML: Lazy<Vec<u8>> = Lazy::new(|| to_html(&RESUME).into_bytes());
+    static PDF: Lazy<Vec<u8>> = Lazy::new(|| pdf::render(&title(&RESUME), &blocks(&RESUME)));
+
+    match format {
+        Format::Text => &TEXT,
+        Format::Markdown => RESUME.as_bytes(),
+        Format::Html => &HTML,
+        Format::Pdf => &PDF,
+    }
+}
+
+/// A post as Markdown, with its title, date and tags as YAML front matter
+pub fn post_markdown(post: &Post) -> String {
+    let front_matter = [
+        ("title", quote(post.title)),
+        ("slug", quote(post.slug)),
+        ("published", quote(post.published)),
+        ("tags", quote_all(&post.tags)),
+        ("excerpt", quote(post.excerpt)),
+    ];
+    with_front_matter(&front_matter, post.content)
+}
+
+/// A project as Markdown: its description under YAML front matter
+pub fn project_markdown(project: &Project) -> String {
+    let mut front_matter = vec![
+        ("name", quote(project.name)),
+        ("slug", quote(project.slug)),
+        ("tech_stack", quote_all(&project.tech_stack)),
+    ];
+    if let Some(url) = project.url {
+        front_matter.push(("url", quote(url)));
+    }
+    if let Some(url) = project.github_url {
+        front_matter.push(("github_url", quote(url)));
+    }
+    front_matter.push(("featured", project.featured.to_string()));
+    front_matter.push(("excerpt", quote(project.excerpt)));
+    with_front_matter(&front_matter, project.description)
+}
+
+fn with_front_matter(fields: &[(&str, String)], body: &str) -> String {
+    let mut out = String::from("---\n");
+    for (key, value) in fields {
+        out.push_str(&format!("{key}: {value}\n"));
+    }
+    out.push_str("---\n\n");
+    out.push_str(body.trim_end());
+    out.push('\n');
+    out
+}
+
+/// A YAML double-quoted string; JSON strings are valid YAML
+fn quote(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+fn quote_all(values: &[&str]) -> String {
+    serde_json::to_string(values).unwrap_or_default()
+}
+
+/// Contact card (vCard 3.0) with the name, email address, website and
+/// social profiles from the content
+pub fn vcard() -> String {
+    let name = name();
+    let (given, family) = name.rsplit_once(' ').unwrap_or((name, ""));
+    let mut lines = vec![
+        "BEGIN:VCARD".to_string(),
+        "VERSION:3.0".to_string(),
+        format!("N:{};{};;;", vcard_escape(family), vcard_escape(given)),
+        format!("FN:{}", vcard_escape(name)),
+        format!("EMAIL;TYPE=INTERNET:{}", vcard_escape(email())),
+        format!("URL:{}", vcard_escape(site_url())),
+    ];
+    lines.extend(socials().iter().map(|social| {
+        let kind = social.label.to_lowercase();
+        format!("X-SOCIALPROFILE;TYPE={}:{}", vcard_escape(&kind), vcard_escape(&social.url))
+    }));
+    lines.push("END:VCARD".to_string());
+    // vCard lines end in CRLF
+    lines.iter().map(|line| format!("{line}\r\n")).collect()
+}
+
+fn vcard_escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(',', "\\,")
+        .replace(';', "\\;")
+        .replace('\n', "\\n")
+}
+
+/// Text of the first `# ` heading, used as the document title
+fn title(markdown: &str) -> String {
+    markdown
+        .lines()
+        .find_map(|line| match heading(line) {
+            Some((1, title)) => Some(plain(title)),
+            _ => None,
+        })
+        .unwrap_or_else(|| "Resume".to_string())
+}
+
+/// A run of text in one style
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Inline {
+    Text { text: String, bold: bool, italic: bool },
+    Link { text: String, url: String },
+}
+
+/// Parse `**bold**`, `*italic*`, `` `code` `` and links in a line
+fn inlines(line: &str) -> Vec<Inline> {
+    let mut result = Vec::new();
+    let (mut bold, mut italic) = (false, false);
+    for (segment, link) in split_markdown_links(line) {
+        if let Some(link) = link {
+            result.push(Inline::Link {
+                text: link.text,
+                url: link.url,
+            });
+            continue;
+        }
+
+        let mut text = String::new();
+        let mut chars = segment.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '`' => continue,
+                '*' => {}
+                c => {
+                    text.push(c);
+                    continue;
+                }
+            }
+            if !text.is_empty() {
+                result.push(Inline::Text {
+                    text: std::mem::take(&mut text),
+                    bold,
+                    italic,
+                });
+            }
+            if chars.next_if_eq(&'*').is_some() {
+                bold = !bold;
+            } else {
+                italic = !italic;
+            }
+        }
+        if !text.is_empty() {
+            result.push(Inline::Text { text, bold, italic });
+        }
+    }
+    result
+}
+
+impl Inline {
+    /// Text as written out in plain text: links keep their address
+    fn plain(&self) -> String {
+        match self {
+            Inline::Text { text, .. } => text.clone(),
+            Inline::Link { text, url } if shows_url(text, url) => text.clone(),
+            Inline::Link { text, url } => format!("{text} <{url}>"),
+        }
+    }
+}
+
+/// Whether a link's text already is its address (bare URLs and emails)
+fn shows_url(text: &str, url: &str) -> bool {
+    url == text || url.strip_prefix("mailto:") == Some(text)
+}
+
+/// A line of Markdown as plain text
+fn plain(line: &str) -> String {
+    inlines(line).iter().map(Inline::plain).collect()
+}
+
+/// A block of the resume, as laid out in the HTML and PDF exports
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Block {
+    Heading(usize, Vec<Inline>),
+    /// Lines of a paragraph, kept as they are in the source
+    Paragraph(Vec<Vec<Inline>>),
+    Item(Vec<Inline>),
+    Rule,
+}
+
+/// Split Markdown into blocks
+///
+/// Consecutive lines form a paragraph but keep their line breaks, as they
+/// do in the TUI (e.g. the `**Languages:** ...` lines under Skills).
+fn blocks(markdown: &str) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let flush = |paragraph: &mut Vec<&str>, blocks: &mut Vec<Block>| {
+        if !paragraph.is_empty() {
+            blocks.push(Block::Paragraph(paragraph.iter().map(|line| inlines(line)).collect()));
+            paragraph.clear();
+        }
+    };
+
+    for line in markdown.lines() {
+        let line = line.trim_end();
+        if let Some((level, text)) = heading(line) {
+            flush(&mut paragraph, &mut blocks);
+            blocks.push(Block::Heading(level, inlines(text)));
+        } else if let Some(item) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+            flush(&mut paragraph, &mut blocks);
+            blocks.push(Block::Item(inlines(item)));
+        } else if line == "---" {
+            flush(&mut paragraph, &mut blocks);
+            blocks.push(Block::Rule);
+        } else if line.is_empty() {
+            flush(&mut paragraph, &mut blocks);
+        } else {
+            paragraph.push(line.trim_start());
+        }
+    }
+    flush(&mut paragraph, &mut blocks);
+    blocks
+}
+
+/// Plain text, wrapped to 80 columns, with underlined top-level headings
+fn to_text(markdown: &str) -> String {
+    let mut out = String::new();
+    for line in markdown.lines() {
+        let line = line.trim_end();
+        let text = match heading(line) {
+            Some((level, title)) => {
+                let title = plain(title);
+                let underline = match level {
+                    1 => "=",
+                    2 => "-",
+                    _ => "",
+                };
+                let rule = underline.repeat(title.chars().count());
+                out.push_str(&title);
+                out.push('\n');
+                if !rule.is_empty() {
+                    out.push_str(&rule);
+                    out.push('\n');
+                }
+                continue;
+            }
+            None if line == "---" => "-".repeat(TEXT_WIDTH / 2),
+            None => match line.strip_prefix("- ") {
+                Some(item) => format!("  • {}", plain(item)),
+                None => plain(line),
+            },
+        };
+        // Blank lines wrap to nothing
+        if text.is_empty() {
+            out.push('\n');
+        }
+        for wrapped in wrap_text(&text, TEXT_WIDTH) {
+            out.push_str(&wrapped);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// A standalone HTML page with inline styles
+fn to_html(markdown: &str) -> String {
+    let mut body = String::new();
+    let mut in_list = false;
+    for block in blocks(markdown) {
+        let is_item = matches!(block, Block::Item(_));
+        if is_item != in_list {
+            body.push_str(if is_item { "<ul>\n" } else { "</ul>\n" });
+            in_list = is_item;
+        }
+        match block {
+            Block::Heading(level, text) => {
+                body.push_str(&format!("<h{level}>{}</h{level}>\n", html_inlines(&text)));
+            }
+            Block::Paragraph(lines) => {
+                let lines: Vec<String> = lines.iter().map(|line| html_inlines(line)).collect();
+                body.push_str(&format!("<p>{}</p>\n", lines.join("<br>\n")));
+            }
+            Block::Item(text) => body.push_str(&format!("<li>{}</li>\n", html_inlines(&text))),
+            Block::Rule => body.push_str("<hr>\n"),
+        }
+    }
+    if in_list {
+        body.push_str("</ul>\n");
+    }
+
+    format!(
+        "<!DOCTYPE html>\n\
+         <html lang=\"en\">\n\
+         <head>\n\
+         <meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{}</title>\n\
+         <style>{HTML_STYLE}</style>\n\
+         </head>\n\
+         <body>\n\
+         <main>\n\
+         {body}\
+         </main>\n\
+         </body>\n\
+         </html>\n",
+        escape(&title(markdown))
+    )
+}
+
+const HTML_STYLE: &str = "\
+body{margin:0;background:#fff;color:#24283b;\
+font:16px/1.55 system-ui,-apple-system,'Segoe UI',sans-serif}\
+main{max-width:46rem;margin:0 auto;padding:2.5rem 1.5rem}\
+h1{margin:0;font-size:2.2rem}h2{color:#2e7de9;margin:1.6rem 0 .4rem}\
+h3{margin:1.2rem 0 .2rem}em{color:#6172b0}ul{padding-left:1.3rem}\
+hr{border:0;border-top:1px solid #c4c8da;margin:1.8rem 0}a{color:#2e7de9}\
+@media print{main{padding:0}}";
+
+fn html_inlines(inlines: &[Inline]) -> String {
+    let mut out = String::new();
+    for inline in inlines {
+        match inline {
+            Inline::Text { text, bold, italic } => {
+                let text = escape(text);
+                out.push_str(&match (bold, italic) {
+                    (true, true) => format!("<strong><em>{text}</em></strong>"),
+                    (true, false) => format!("<strong>{text}</strong>"),
+                    (false, true) => format!("<em>{text}</em>"),
+                    (false, false) => text,
+                });
+            }
+            Inline::Link { text, url } => {
+                out.push_str(&format!("<a href=\"{}\">{}</a>", escape(url), escape(text)));
+            }
+        }
+    }
+    out
+}
+
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "# Jane Doe\n\n## Experience\n\n### Engineer | Acme\n\
+        *Remote • 2020 – Present*\n\n- Shipped **fast** things\n- See [site](https://example.com)\n\n\
+        ---\n\n**Languages:** Rust, Go\n**Tools:** Git\nMail jane@example.com";
+
+    #[test]
+    fn test_export_commands() {
+        assert_eq!(export_command("resume --format pdf").unwrap().unwrap(), Format::Pdf);
+        assert_eq!(export_command("resume -f md").unwrap().unwrap(), Format::Markdown);
+        assert_eq!(export_command("resume --format=TXT").unwrap().unwrap(), Format::Text);
+        assert!(export_command("resume --format doc").unwrap().is_err());
+        assert!(export_command("resume --pdf").unwrap().is_err());
+        // Without arguments it is the route to the Resume view
+        assert!(export_command("resume").is_none());
+        assert!(export_command("/blog/post").is_none());
+    }
+
+    #[test]
+    fn test_text_and_html_drop_markdown_syntax() {
+        let text = to_text(SAMPLE);
+        assert!(text.starts_with("Jane Doe\n========\n\nExperience\n"));
+        assert!(text.contains("  • Shipped fast things\n"));
+        assert!(text.contains("  • See site <https://example.com>\n"));
+        assert!(text.contains("Languages: Rust, Go\n"));
+        assert!(!text.contains('*'));
+
+        let html = to_html(SAMPLE);
+        assert!(html.contains("<title>Jane Doe</title>"));
+        assert!(html.contains("<ul>\n<li>Shipped <strong>fast</strong> things</li>\n"));
+        assert!(html.contains("<a href=\"https://example.com\">site</a>"));
+        assert!(html.contains("<p><strong>Languages:</strong> Rust, Go<br>\n<strong>Tools:</strong> Git<br>\nMail "));
+        assert!(html.contains("<em>Remote • 2020 – Present</em>"));
+    }
+
+    #[test]
+    fn test_front_matter_and_vcard() {
+        let post = Post {
+            slug: "hello",
+            title: "Say \"hi\"",
+            excerpt: "First",
+            tags: vec!["Rust", "TUI"],
+            content: "# Hello\n\nBody\n\n",
+            published: "2026-01-02",
+        };
+        assert_eq!(
+            post_markdown(&post),
+            "---\ntitle: \"Say \\\"hi\\\"\"\nslug: \"hello\"\npublished: \"2026-01-02\"\n\
+             tags: [\"Rust\",\"TUI\"]\nexcerpt: \"First\"\n---\n\n# Hello\n\nBody\n"
+        );
+
+        let card = vcard();
+        assert!(card.starts_with("BEGIN:VCARD\r\nVERSION:3.0\r\n"));
+        assert!(card.contains(&format!("FN:{}\r\n", name())));
+        assert!(card.contains(&format!("EMAIL;TYPE=INTERNET:{}\r\n", email())));
+        let profiles = card.matches("X-SOCIALPROFILE;TYPE=").count();
+        assert_eq!(profiles, socials().len());
+        assert!(card.ends_with("END:VCARD\r\n"));
+        assert_eq!(vcard_escape("a,b;c"), "a\\,b\\;c");
+    }
+
+    #[test]
+    fn test_resume_exports() {
+        assert_eq!(resume(Format::Markdown), RESUME.as_bytes());
+        let pdf = resume(Format::Pdf);
+        assert!(pdf.starts_with(b"%PDF-1.4\n"));
+        assert!(pdf.ends_with(b"%%EOF\n"));
+        for format in Format::ALL {
+            assert!(!resume(format).is_empty(), "{format}");
+        }
+    }
+}
@@ -0,0 +1,378 @@
+//! A small PDF writer for the resume
+//!
+//! Text is set in the standard Helvetica fonts, which every PDF reader has,
+//! so nothing is embedded. Lines are broken using the fonts' metrics.
+
+use super::{Block, Inline};
+
+/// US Letter, in points
+const PAGE_WIDTH: f32 = 612.0;
+const PAGE_HEIGHT: f32 = 792.0;
+const MARGIN: f32 = 54.0;
+
+const BODY_SIZE: f32 = 10.0;
+const LEADING: f32 = 1.4;
+/// Indent of list item text, after the bullet
+const ITEM_INDENT: f32 = 14.0;
+
+/// Heading color, a dark blue
+const ACCENT: &str = "0.18 0.31 0.58";
+/// Rule color
+const MUTED: &str = "0.55 0.57 0.65";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Font {
+    Regular,
+    Bold,
+    Italic,
+}
+
+impl Font {
+    fn of(bold: bool, italic: bool) -> Self {
+        match (bold, italic) {
+            (true, _) => Font::Bold,
+            (false, true) => Font::Italic,
+            (false, false) => Font::Regular,
+        }
+    }
+
+    /// Resource name in the page's font dictionary
+    fn name(self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+            Font::Italic => "F3",
+        }
+    }
+
+    /// Width of a WinAnsi byte in thousandths of the font size
+    fn width(self, byte: u8) -> u16 {
+        let table = match self {
+            Font::Bold => &BOLD_WIDTHS,
+            Font::Regular | Font::Italic => &REGULAR_WIDTHS,
+        };
+        match byte {
+            32..=126 => table[(byte - 32) as usize],
+            0x95 => 350,
+            0x96 => 556,
+            0x97 => 1000,
+            0x91..=0x94 => 333,
+            _ => 556,
+        }
+    }
+
+    fn text_width(self, text: &[u8], size: f32) -> f32 {
+        text.iter().map(|&b| self.width(b) as f32).sum::<f32>() * size / 1000.0
+    }
+}
+
+/// Helvetica widths of ASCII 32-126, from the Adobe font metrics
+const REGULAR_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, //
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, //
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778, //
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556, //
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556, //
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+/// Helvetica-Bold widths of ASCII 32-126
+const BOLD_WIDTHS: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278, //
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611, //
+    975, 722, 722, 722, 722, 667, 611, 778, 722, 278, 556, 722, 611, 833, 722, 778, //
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 333, 278, 333, 584, 556, //
+    333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556, 278, 889, 611, 611, //
+    611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584,
+];
+
+/// Encode text as WinAnsi, the encoding of the standard fonts
+///
+/// Latin-1 maps to itself; typographic punctuation has its own codes and
+/// anything else becomes `?`.
+fn win_ansi(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c {
+            ' '..='~' => c as u8,
+            '\u{a0}'..='\u{ff}' => c as u32 as u8,
+            '‘' => 0x91,
+            '’' => 0x92,
+            '“' => 0x93,
+            '”' => 0x94,
+            '•' => 0x95,
+            '–' => 0x96,
+            '—' => 0x97,
+            '…' => 0x85,
+            _ => b'?',
+        })
+        .collect()
+}
+
+/// A word in one font, with the space before it
+struct Word {
+    font: Font,
+    text: Vec<u8>,
+    /// Follows a space in the source
+    spaced: bool,
+}
+
+fn words(inlines: &[Inline], base: Font) -> Vec<Word> {
+    let mut words = Vec::new();
+    let mut spaced = false;
+    for inline in inlines {
+        let (text, font) = match inline {
+            Inline::Text { text, bold, italic } if base != Font::Bold => {
+                (text.clone(), Font::of(*bold, *italic))
+            }
+            _ => (inline.plain(), base),
+        };
+        spaced |= text.starts_with(char::is_whitespace);
+        for word in text.split_whitespace() {
+            words.push(Word {
+                font,
+                text: win_ansi(word),
+                spaced,
+            });
+            spaced = true;
+        }
+        spaced = text.ends_with(char::is_whitespace);
+    }
+    words
+}
+
+/// Content streams of the pages, filled top to bottom
+struct Layout {
+    pages: Vec<String>,
+    /// Baseline of the last line drawn
+    y: f32,
+}
+
+impl Layout {
+    fn new() -> Self {
+        Self {
+            pages: vec![String::new()],
+            y: PAGE_HEIGHT - MARGIN,
+        }
+    }
+
+    fn page(&mut self) -> &mut String {
+        self.pages.last_mut().expect("a page is always open")
+    }
+
+    /// Start a new page unless `height` fits on this one
+    fn reserve(&mut self, height: f32) {
+        if self.y - height < MARGIN {
+            self.pages.push(String::new());
+            self.y = PAGE_HEIGHT - MARGIN;
+        }
+    }
+
+    /// Move down by `height`, returning the new baseline
+    fn advance(&mut self, height: f32) -> f32 {
+        self.reserve(height);
+        self.y -= height;
+        self.y
+    }
+
+    /// Set words in lines no wider than the text column less `indent`
+    fn paragraph(&mut self, words: &[Word], size: f32, indent: f32, color: Option<&str>) {
+        let max = PAGE_WIDTH - 2.0 * MARGIN - indent;
+        let space = |font: Font| font.width(b' ') as f32 * size / 1000.0;
+
+        let mut start = 0;
+        while start < words.len() {
+            let mut end = start + 1;
+            let mut width = words[start].font.text_width(&words[start].text, size);
+            while end < words.len() {
+                let word = &words[end];
+                let gap = if word.spaced { space(word.font) } else { 0.0 };
+                let next = width + gap + word.font.text_width(&word.text, size);
+                if next > max {
+                    break;
+                }
+                width = next;
+                end += 1;
+            }
+
+            let y = self.advance(size * LEADING);
+            let color = color.unwrap_or("0 0 0");
+            let mut ops = format!("BT {color} rg {:.2} {y:.2} Td", MARGIN + indent);
+            let mut font = None;
+            for (i, word) in words[start..end].iter().enumerate() {
+                if font != Some(word.font) {
+                    ops.push_str(&format!(" /{} {size} Tf", word.font.name()));
+                    font = Some(word.font);
+                }
+                let spaced = i > 0 && word.spaced;
+                let gap = if spaced { " " } else { "" };
+                ops.push_str(&format!(" ({gap}{}) Tj", escape(&word.text)));
+            }
+            ops.push_str(" ET\n");
+            self.page().push_str(&ops);
+            start = end;
+        }
+    }
+
+    /// Bullet for a list item about to be set
+    fn bullet(&mut self, size: f32) {
+        self.reserve(size * LEADING);
+        let y = self.y - size * LEADING;
+        let x = MARGIN + 4.0;
+        let ops = format!("BT /F1 {size} Tf {x:.2} {y:.2} Td ({}) Tj ET\n", escape(&[0x95]));
+        self.page().push_str(&ops);
+    }
+
+    fn rule(&mut self) {
+        let y = self.advance(BODY_SIZE);
+        let ops = format!(
+            "{MUTED} RG 0.5 w {MARGIN} {y:.2} m {:.2} {y:.2} l S\n",
+            PAGE_WIDTH - MARGIN
+        );
+        self.page().push_str(&ops);
+        self.y -= BODY_SIZE * 0.4;
+    }
+}
+
+fn escape(text: &[u8]) -> String {
+    let mut out = String::new();
+    for &b in text {
+        match b {
+            b'(' | b')' | b'\\' => {
+                out.push('\\');
+                out.push(b as char);
+            }
+            32..=126 => out.push(b as char),
+            _ => out.push_str(&format!("\\{b:03o}")),
+        }
+    }
+    out
+}
+
+/// Lay out the blocks on as many pages as needed
+fn layout(blocks: &[Block]) -> Vec<String> {
+    let mut layout = Layout::new();
+    let mut first = true;
+    for block in blocks {
+        match block {
+            Block::Heading(level, text) => {
+                let size = match level {
+                    1 => 22.0,
+                    2 => 13.5,
+                    _ => 11.0,
+                };
+                if !first {
+                    layout.advance(size * 0.6);
+                }
+                let color = if *level == 2 { Some(ACCENT) } else { None };
+                layout.paragraph(&words(text, Font::Bold), size, 0.0, color);
+            }
+            Block::Paragraph(lines) => {
+                for line in lines {
+                    layout.paragraph(&words(line, Font::Regular), BODY_SIZE, 0.0, None);
+                }
+                layout.advance(BODY_SIZE * 0.3);
+            }
+            Block::Item(text) => {
+                layout.bullet(BODY_SIZE);
+                layout.paragraph(&words(text, Font::Regular), BODY_SIZE, ITEM_INDENT, None);
+            }
+            Block::Rule => layout.rule(),
+        }
+        first = false;
+    }
+    layout.pages
+}
+
+/// A complete PDF file of `blocks`
+pub(super) fn render(title: &str, blocks: &[Block]) -> Vec<u8> {
+    let pages = layout(blocks);
+
+    // Catalog, page tree and fonts come first; then a page and its
+    // content stream for each page, and the document info last
+    let first_page = 6;
+    let info = first_page + 2 * pages.len();
+    let kids: Vec<String> = (0..pages.len())
+        .map(|i| format!("{} 0 R", first_page + 2 * i))
+        .collect();
+
+    let mut objects = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids.join(" "), pages.len()),
+        font("Helvetica"),
+        font("Helvetica-Bold"),
+        font("Helvetica-Oblique"),
+    ];
+    for (i, content) in pages.iter().enumerate() {
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {PAGE_WIDTH} {PAGE_HEIGHT}] \
+             /Resources << /Font << /F1 3 0 R /F2 4 0 R /F3 5 0 R >> >> \
+             /Contents {} 0 R >>",
+            first_page + 2 * i + 1
+        ));
+        objects.push(format!(
+            "<< /Length {} >>\nstream\n{content}endstream",
+            content.len()
+        ));
+    }
+    objects.push(format!(
+        "<< /Title ({}) /Producer (whoami) >>",
+        escape(&win_ansi(title))
+    ));
+
+    let mut out = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec();
+    let mut offsets = Vec::new();
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(out.len());
+        out.extend_from_slice(format!("{} 0 obj\n{object}\nendobj\n", i + 1).as_bytes());
+    }
+
+    let xref = out.len();
+    let size = objects.len() + 1;
+    out.extend_from_slice(format!("xref\n0 {size}\n0000000000 65535 f \n").as_bytes());
+    for offset in offsets {
+        out.extend_from_slice(format!("{offset:010} 00000 n \n").as_bytes());
+    }
+    out.extend_from_slice(
+        format!(
+            "trailer\n<< /Size {size} /Root 1 0 R /Info {info} 0 R >>\nstartxref\n{xref}\n%%EOF\n"
+        )
+        .as_bytes(),
+    );
+    out
+}
+
+fn font(name: &str) -> String {
+    format!("<< /Type /Font /Subtype /Type1 /BaseFont /{name} /Encoding /WinAnsiEncoding >>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::blocks;
+
+    #[test]
+    fn test_xref_points_at_objects() {
+        let long = "- word ".repeat(80);
+        let pdf = render("Test (1)", &blocks(&format!("# Title\n\n{long}\n")));
+        // Offsets are in bytes, and the header has a few non-UTF-8 ones
+        let text = String::from_utf8_lossy(&pdf);
+        let tail = text.rsplit("startxref\n").next().unwrap();
+        let start: usize = tail.lines().next().unwrap().parse().unwrap();
+        assert!(pdf[start..].starts_with(b"xref\n"));
+        let xref = String::from_utf8_lossy(&pdf[start..]);
+        let entries = xref.lines().skip(3).take_while(|l| l.ends_with(" n "));
+        for (i, entry) in entries.enumerate() {
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(format!("{} 0 obj", i + 1).as_bytes()));
+        }
+        assert!(text.contains("/Title (Test \\(1\\))"));
+    }
+
+    #[test]
+    fn test_long_paragraphs_break_across_pages() {
+        let paragraph = "lorem ipsum dolor sit amet ".repeat(400);
+        let pages = layout(&blocks(&paragraph));
+        assert!(pages.len() > 1);
+        assert!(pages.iter().all(|page| page.lines().all(|l| l.starts_with("BT "))));
+    }
+}
@@ -0,0 +1,156 @@
+//! Browser-style navigation history and per-view saved state
+
+use std::collections::HashMap;
+
+use tuirealm::props::AttrValue;
+
+use crate::msg::ViewId;
+
+/// Maximum number of entries kept in each direction
+const MAX_DEPTH: usize = 50;
+
+/// A visited view and the state it was left in
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub view: ViewId,
+    /// Snapshot from the component's `Attribute::Value`, restored on return
+    pub state: Option<AttrValue>,
+}
+
+impl Entry {
+    /// Entry for a view opened fresh, with no saved state
+    pub fn new(view: ViewId) -> Self {
+        Self { view, state: None }
+    }
+}
+
+/// Back and forward stacks around the current view
+#[derive(Debug, Default)]
+pub struct History {
+    back: Vec<Entry>,
+    forward: Vec<Entry>,
+}
+
+impl History {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record leaving `from` for a new view; clears the forward stack
+    pub fn visit(&mut self, from: Entry) {
+        self.forward.clear();
+        // The loading screen is never a destination
+        if from.view != ViewId::Loading {
+            push_bounded(&mut self.back, from);
+        }
+    }
+
+    /// Step back from `from`, returning the entry to restore
+    pub fn back(&mut self, from: Entry) -> Option<Entry> {
+        let previous = self.back.pop()?;
+        push_bounded(&mut self.forward, from);
+        Some(previous)
+    }
+
+    /// Step forward from `from`, returning the entry to restore
+    pub fn forward(&mut self, from: Entry) -> Option<Entry> {
+        let next = self.forward.pop()?;
+        push_bounded(&mut self.back, from);
+        Some(next)
+    }
+
+    #[cfg(test)]
+    fn can_go_back(&self) -> bool {
+        !self.back.is_empty()
+    }
+
+    #[cfg(test)]
+    fn can_go_forward(&self) -> bool {
+        !self.forward.is_empty()
+    }
+}
+
+/// Last state of each view, restored when the view is opened again
+///
+/// Holds at most one snapshot per view for the life of the session. Content
+/// is compiled in, so a saved cursor always points at the same post.
+#[derive(Debug, Default)]
+pub struct ViewCache {
+    saved: HashMap<ViewId, AttrValue>,
+}
+
+impl ViewCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remember the state a view was left in; no state clears it
+    pub fn save(&mut self, entry: &Entry) {
+        match &entry.state {
+            Some(state) => {
+                self.saved.insert(entry.view, state.clone());
+            }
+            None => {
+                self.saved.remove(&entry.view);
+            }
+        }
+    }
+
+    /// Saved state for a view
+    pub fn get(&self, view: ViewId) -> Option<AttrValue> {
+        self.saved.get(&view).cloned()
+    }
+}
+
+/// Push onto a stack, dropping the oldest entry once it is full
+fn push_bounded(stack: &mut Vec<Entry>, entry: Entry) {
+    if stack.len() == MAX_DEPTH {
+        stack.remove(0);
+    }
+    stack.push(entry);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_back_and_forward_restore_entries() {
+        let mut history = History::new();
+        history.visit(Entry::new(ViewId::Loading));
+        history.visit(Entry::new(ViewId::Home));
+        history.visit(Entry::new(ViewId::Blog));
+
+        // Projects -> Blog -> Home, then forward to Blog again
+        let blog = history.back(Entry::new(ViewId::Projects)).unwrap();
+        assert_eq!(blog.view, ViewId::Blog);
+        let home = history.back(blog).unwrap();
+        assert_eq!(home.view, ViewId::Home);
+        assert!(!history.can_go_back());
+        assert!(history.back(home.clone()).is_none());
+        assert_eq!(history.forward(home).unwrap().view, ViewId::Blog);
+
+        // Visiting a new view drops the forward stack
+        history.visit(Entry::new(ViewId::Blog));
+        assert!(!history.can_go_forward());
+    }
+
+    #[test]
+    fn test_view_cache_keeps_latest_state() {
+        let state = |n| Some(AttrValue::Number(n));
+        let mut cache = ViewCache::new();
+        cache.save(&Entry {
+            view: ViewId::Resume,
+            state: state(3),
+        });
+        cache.save(&Entry {
+            view: ViewId::Resume,
+            state: state(7),
+        });
+        assert_eq!(cache.get(ViewId::Resume), state(7));
+
+        // Leaving a view without state (e.g. a sent contact form) drops it
+        cache.save(&Entry::new(ViewId::Resume));
+        assert_eq!(cache.get(ViewId::Resume), None);
+    }
+}
@@ -0,0 +1,1040 @@
+//! Central keymap - translates key events into semantic messages
+//!
+//! Components never match on raw keys. Every terminal event is routed through
+//! [`route`], which resolves it against a single binding table (global
+//! bindings plus per-view overrides) and hands the component a [`Msg`]. The
+//! same table generates the help footer shown by each `PageLayout`.
+//!
+//! The active table is built from a [`Preset`] and can be customised with a
+//! TOML file (stdio binary) or swapped from the Settings view (per session).
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+
+use anyhow::{anyhow, bail, Context, Result};
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use tuirealm::event::{Key, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use tuirealm::{Event, NoUserEvent};
+
+use crate::msg::{Msg, ViewId};
+
+/// Views that show content and can be left with `esc`/`q`
+const CONTENT_VIEWS: &[ViewId] = &[
+    ViewId::Resume,
+    ViewId::Blog,
+    ViewId::Projects,
+    ViewId::Contact,
+    ViewId::Settings,
+    ViewId::Search,
+];
+
+/// Views reachable by the one-letter navigation shortcuts
+const NAV_VIEWS: &[ViewId] = &[
+    ViewId::Home,
+    ViewId::Resume,
+    ViewId::Blog,
+    ViewId::Projects,
+    ViewId::Contact,
+    ViewId::Settings,
+];
+
+/// Views with a selectable list or scrollable body
+const LIST_VIEWS: &[ViewId] = &[
+    ViewId::Home,
+    ViewId::Resume,
+    ViewId::Blog,
+    ViewId::Projects,
+    ViewId::Settings,
+    ViewId::Help,
+];
+
+/// Views with paged scrolling
+const PAGER_VIEWS: &[ViewId] = &[
+    ViewId::Resume,
+    ViewId::Blog,
+    ViewId::Projects,
+    ViewId::Help,
+];
+
+/// Views that step through highlighted search matches
+const FIND_VIEWS: &[ViewId] = &[ViewId::Resume, ViewId::Blog, ViewId::Projects];
+
+/// Views whose pages number their links, copied with the digit keys
+const LINK_VIEWS: &[ViewId] = &[ViewId::Blog, ViewId::Projects];
+
+/// Views stepping through projects or sections with `]` and `[`
+const SECTION_VIEWS: &[ViewId] = &[ViewId::Resume, ViewId::Blog, ViewId::Projects];
+
+/// Views with a query typed into a result list
+const QUERY_VIEWS: &[ViewId] = &[ViewId::Palette, ViewId::Search];
+
+/// Overlays drawn above a view; `back` closes them
+const OVERLAYS: &[ViewId] = &[ViewId::Help, ViewId::Palette];
+
+/// Display order of the help overlay sections
+const CATEGORIES: &[&str] = &["Movement", "Actions", "Form", "Navigation", "General"];
+
+/// How a view wants plain character keys to be treated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputMode {
+    /// Characters are looked up in the binding table
+    Normal,
+    /// Characters are captured as text (`Msg::TypeChar`); only non-character
+    /// keys and modified keys are looked up in the binding table
+    TextInput,
+}
+
+/// Built-in binding sets a visitor can choose from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Preset {
+    /// `j`/`k` movement, `u`/`d` paging
+    Vim,
+    /// `ctrl+n`/`ctrl+p` movement, `ctrl+v`/`alt+v` paging, `ctrl+g` back
+    Emacs,
+    /// Arrow keys and PageUp/PageDown only
+    Arrows,
+}
+
+impl Preset {
+    /// All presets, in the order shown in Settings
+    pub const ALL: [Preset; 3] = [Preset::Vim, Preset::Emacs, Preset::Arrows];
+
+    /// Display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Preset::Vim => "Vim",
+            Preset::Emacs => "Emacs",
+            Preset::Arrows => "Arrows",
+        }
+    }
+
+    /// One-line description for the Settings view
+    pub fn description(&self) -> &'static str {
+        match self {
+            Preset::Vim => "j/k to move, u/d to page, q to go back",
+            Preset::Emacs => "ctrl+n/ctrl+p to move, ctrl+v/alt+v to page, ctrl+g to go back",
+            Preset::Arrows => "arrow keys and PageUp/PageDown only",
+        }
+    }
+
+    /// Movement keys that differ between presets
+    fn motion(&self) -> Motion {
+        match self {
+            Preset::Vim => Motion {
+                up: vec![key(Key::Up), ch('k')],
+                down: vec![key(Key::Down), ch('j')],
+                page_up: vec![key(Key::PageUp), ch('u')],
+                page_down: vec![key(Key::PageDown), ch('d')],
+                top: vec![key(Key::Home), ch('g')],
+                bottom: vec![key(Key::End), ch('G')],
+                back: vec![key(Key::Esc), ch('q')],
+                forward: vec![ch('L')],
+                palette: vec![ch(':'), ctrl('p')],
+            },
+            Preset::Emacs => Motion {
+                up: vec![key(Key::Up), ctrl('p')],
+                down: vec![key(Key::Down), ctrl('n')],
+                page_up: vec![key(Key::PageUp), alt('v')],
+                page_down: vec![key(Key::PageDown), ctrl('v')],
+                top: vec![key(Key::Home), alt('<')],
+                bottom: vec![key(Key::End), alt('>')],
+                back: vec![key(Key::Esc), ctrl('g'), ch('q')],
+                forward: vec![alt('f')],
+                palette: vec![ch(':'), alt('x')],
+            },
+            Preset::Arrows => Motion {
+                up: vec![key(Key::Up)],
+                down: vec![key(Key::Down)],
+                page_up: vec![key(Key::PageUp)],
+                page_down: vec![key(Key::PageDown)],
+                top: vec![key(Key::Home)],
+                bottom: vec![key(Key::End)],
+                back: vec![key(Key::Esc)],
+                forward: vec![key(Key::Right)],
+                palette: vec![ch(':'), ctrl('p')],
+            },
+        }
+    }
+}
+
+/// Preset-specific movement and navigation keys
+struct Motion {
+    up: Vec<KeyEvent>,
+    down: Vec<KeyEvent>,
+    page_up: Vec<KeyEvent>,
+    page_down: Vec<KeyEvent>,
+    top: Vec<KeyEvent>,
+    bottom: Vec<KeyEvent>,
+    back: Vec<KeyEvent>,
+    forward: Vec<KeyEvent>,
+    palette: Vec<KeyEvent>,
+}
+
+/// A single entry in the binding table
+#[derive(Debug, Clone)]
+pub struct Binding {
+    /// Views the binding applies to; empty means every view
+    views: Vec<ViewId>,
+    /// Keys that trigger the binding
+    keys: Vec<KeyEvent>,
+    /// Message produced when one of the keys is pressed
+    msg: Msg,
+    /// Label shown in the help footer (`None` hides the binding)
+    help: Option<&'static str>,
+}
+
+impl Binding {
+    /// Whether this binding is active in the given view
+    fn applies_to(&self, view: ViewId) -> bool {
+        self.views.is_empty() || self.views.contains(&view)
+    }
+
+    /// View-specific bindings take precedence over global ones
+    fn is_override(&self) -> bool {
+        !self.views.is_empty()
+    }
+
+    fn matches(&self, ev: &KeyEvent) -> bool {
+        self.keys.iter().any(|key| key_matches(key, ev))
+    }
+}
+
+/// Binding table with lookup and help generation
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    /// Preset the table was built from (`None` once customised by a file)
+    preset: Option<Preset>,
+    bindings: Vec<Binding>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::preset(Preset::Vim)
+    }
+}
+
+impl Keymap {
+    /// Create an empty keymap
+    pub fn new() -> Self {
+        Self {
+            preset: None,
+            bindings: Vec::new(),
+        }
+    }
+
+    /// Build the binding table for a preset
+    pub fn preset(preset: Preset) -> Self {
+        let motion = preset.motion();
+        let next_field = [&[key(Key::Tab)][..], &motion.down].concat();
+        let prev_field = [&[key(Key::BackTab)][..], &motion.up].concat();
+
+        let mut keymap = Self::new()
+            // Lists and scrolling
+            .bind(LIST_VIEWS, &motion.up, Msg::CursorUp, Some("up"))
+            .bind(LIST_VIEWS, &motion.down, Msg::CursorDown, Some("down"))
+            .bind(PAGER_VIEWS, &motion.page_up, Msg::PageUp, Some("page up"))
+            .bind(
+                PAGER_VIEWS,
+                &motion.page_down,
+                Msg::PageDown,
+                Some("page down"),
+            )
+            .bind(PAGER_VIEWS, &motion.top, Msg::JumpTop, None)
+            .bind(PAGER_VIEWS, &motion.bottom, Msg::JumpBottom, None)
+            .bind(
+                &[ViewId::Home],
+                &[key(Key::Enter)],
+                Msg::Select,
+                Some("select"),
+            )
+            .bind(
+                &[ViewId::Blog],
+                &[key(Key::Enter)],
+                Msg::Select,
+                Some("read"),
+            )
+            .bind(
+                &[ViewId::Projects],
+                &[key(Key::Enter)],
+                Msg::Select,
+                Some("details"),
+            )
+            .bind(
+                &[ViewId::Settings],
+                &[key(Key::Enter)],
+                Msg::Select,
+                Some("apply"),
+            )
+            .bind(&[ViewId::Blog], &[ch('t')], Msg::FocusTags, Some("tags"))
+            .bind(&[ViewId::Blog], &[ch('S')], Msg::CycleSort, Some("sort"))
+            .bind(&[ViewId::Blog], &[key(Key::Tab)], Msg::FocusNext, None)
+            .bind(&[ViewId::Blog], &[key(Key::BackTab)], Msg::FocusPrev, None)
+            .bind(&[ViewId::Projects], &[ch('o')], Msg::Open, Some("open"))
+            .bind(
+                &[ViewId::Blog, ViewId::Projects],
+                &[ch('y')],
+                Msg::Copy,
+                Some("copy link"),
+            )
+            .bind(&[ViewId::Blog], &[ch('Y')], Msg::CopyCode, None)
+            .bind(&[ViewId::Projects], &[key(Key::Tab)], Msg::FocusNext, None)
+            .bind(&[ViewId::Projects], &[key(Key::BackTab)], Msg::FocusPrev, None)
+            .bind(SECTION_VIEWS, &[ch(']')], Msg::NextItem, None)
+            .bind(SECTION_VIEWS, &[ch('[')], Msg::PrevItem, None)
+            .bind(&[ViewId::Resume], &[ch('T')], Msg::ToggleToc, Some("contents"))
+            .bind(&[ViewId::Blog], &[ch('T')], Msg::ToggleToc, None)
+            .bind(FIND_VIEWS, &[ch('n')], Msg::FindNext, None)
+            .bind(FIND_VIEWS, &[ch('N')], Msg::FindPrev, None)
+            // Contact form
+            .bind(
+                &[ViewId::Contact],
+                &next_field,
+                Msg::FocusNext,
+                Some("next"),
+            )
+            .bind(
+                &[ViewId::Contact],
+                &prev_field,
+                Msg::FocusPrev,
+                Some("prev"),
+            )
+            .bind(
+                &[ViewId::Contact],
+                &[key(Key::Enter)],
+                Msg::Submit,
+                Some("submit"),
+            )
+            .bind(
+                &[ViewId::Contact],
+                &[ctrl('y'), ch('y')],
+                Msg::Copy,
+                Some("copy email"),
+            )
+            .bind(
+                &[ViewId::Contact, ViewId::Palette, ViewId::Search],
+                &[key(Key::Backspace)],
+                Msg::Backspace,
+                None,
+            )
+            // Palette and search: typed characters go to the query
+            .bind(
+                QUERY_VIEWS,
+                &[key(Key::Up), key(Key::BackTab)],
+                Msg::CursorUp,
+                Some("up"),
+            )
+            .bind(
+                QUERY_VIEWS,
+                &[key(Key::Down), key(Key::Tab)],
+                Msg::CursorDown,
+                Some("down"),
+            )
+            .bind(
+                &[ViewId::Palette],
+                &[key(Key::Enter)],
+                Msg::Select,
+                Some("go"),
+            )
+            .bind(
+                &[ViewId::Search],
+                &[key(Key::Enter)],
+                Msg::Select,
+                Some("open"),
+            )
+            // Global
+            .bind(CONTENT_VIEWS, &motion.back, Msg::GoBack, Some("back"))
+            .bind(OVERLAYS, &motion.back, Msg::GoBack, Some("close"))
+            .bind(NAV_VIEWS, &motion.forward, Msg::GoForward, None)
+            .bind(&[ViewId::Home], &[ch('q')], Msg::Quit, Some("quit"))
+            .bind(&[], &[ctrl('c')], Msg::Quit, None)
+            .bind(NAV_VIEWS, &[ch('h')], Msg::NavigateTo(ViewId::Home), None)
+            .bind(NAV_VIEWS, &[ch('r')], Msg::NavigateTo(ViewId::Resume), None)
+            .bind(NAV_VIEWS, &[ch('b')], Msg::NavigateTo(ViewId::Blog), None)
+            .bind(
+                NAV_VIEWS,
+                &[ch('p')],
+                Msg::NavigateTo(ViewId::Projects),
+                None,
+            )
+            .bind(
+                NAV_VIEWS,
+                &[ch('c')],
+                Msg::NavigateTo(ViewId::Contact),
+                None,
+            )
+            .bind(
+                NAV_VIEWS,
+                &[ch('s')],
+                Msg::NavigateTo(ViewId::Settings),
+                None,
+            )
+            .bind(
+                &[NAV_VIEWS, &[ViewId::Help, ViewId::Search]].concat(),
+                &[ch('?'), key(Key::Function(1))],
+                Msg::ShowHelp,
+                Some("help"),
+            )
+            .bind(NAV_VIEWS, &motion.palette, Msg::ShowPalette, None)
+            .bind(NAV_VIEWS, &[ch('/')], Msg::NavigateTo(ViewId::Search), None);
+
+        for (i, digit) in ('1'..='9').enumerate() {
+            keymap = keymap.bind(LINK_VIEWS, &[ch(digit)], Msg::CopyLink(i + 1), None);
+        }
+
+        keymap.preset = Some(preset);
+        keymap
+    }
+
+    /// Load a keymap from a TOML file
+    ///
+    /// ```toml
+    /// # Base preset: "vim" (default), "emacs" or "arrows"
+    /// preset = "emacs"
+    ///
+    /// # Replace the keys for an action in every view
+    /// [keys]
+    /// down = ["ctrl+n", "down"]
+    ///
+    /// # Add keys for an action in a single view
+    /// [views.blog]
+    /// select = ["enter", "o"]
+    /// ```
+    pub fn load(path: &Path) -> Result<Self> {
+        let source = std::fs::read_to_string(path)
+            .with_context(|| format!("read keymap file {}", path.display()))?;
+        Self::from_toml(&source).with_context(|| format!("parse keymap file {}", path.display()))
+    }
+
+    /// Build a keymap from TOML source (see [`Keymap::load`] for the format)
+    pub fn from_toml(source: &str) -> Result<Self> {
+        let file: KeymapFile = toml::from_str(source)?;
+        let mut keymap = Self::preset(file.preset.unwrap_or(Preset::Vim));
+
+        for (action, keys) in &file.keys {
+            let msg = action_msg(action)?;
+            let keys = parse_keys(keys)?;
+            keymap.rebind(&msg, &keys);
+        }
+
+        for (view, actions) in &file.views {
+            let view = view_id(view)?;
+            for (action, keys) in actions {
+                let msg = action_msg(action)?;
+                let keys = parse_keys(keys)?;
+                keymap.override_view(view, msg, &keys);
+            }
+        }
+
+        Ok(keymap)
+    }
+
+    /// Add a binding; an empty `views` slice makes it global
+    pub fn bind(
+        mut self,
+        views: &[ViewId],
+        keys: &[KeyEvent],
+        msg: Msg,
+        help: Option<&'static str>,
+    ) -> Self {
+        self.bindings.push(Binding {
+            views: views.to_vec(),
+            keys: keys.to_vec(),
+            msg,
+            help,
+        });
+        self
+    }
+
+    /// Replace the keys of every binding that produces `msg`
+    fn rebind(&mut self, msg: &Msg, keys: &[KeyEvent]) {
+        self.preset = None;
+        for binding in self.bindings.iter_mut().filter(|b| &b.msg == msg) {
+            binding.keys = keys.to_vec();
+        }
+    }
+
+    /// Bind extra keys for `msg` in one view, ahead of the preset bindings
+    fn override_view(&mut self, view: ViewId, msg: Msg, keys: &[KeyEvent]) {
+        self.preset = None;
+        let help = self
+            .bindings
+            .iter()
+            .find(|b| b.applies_to(view) && b.msg == msg)
+            .and_then(|b| b.help);
+        self.bindings.insert(
+            0,
+            Binding {
+                views: vec![view],
+                keys: keys.to_vec(),
+                msg,
+                help,
+            },
+        );
+    }
+
+    /// Preset this keymap was built from, or `None` if it was customised
+    pub fn active_preset(&self) -> Option<Preset> {
+        self.preset
+    }
+
+    /// Resolve a key event for the given view, preferring view overrides
+    pub fn lookup(&self, view: ViewId, mode: InputMode, ev: &KeyEvent) -> Option<Msg> {
+        if let (InputMode::TextInput, Some(c)) = (mode, typed_char(ev)) {
+            return Some(Msg::TypeChar(c));
+        }
+
+        let candidates = || {
+            self.bindings
+                .iter()
+                .filter(move |b| b.applies_to(view) && b.matches(ev))
+        };
+
+        candidates()
+            .find(|b| b.is_override())
+            .or_else(|| candidates().next())
+            .map(|b| b.msg.clone())
+    }
+
+    /// First key bound to `msg` in any view, for menus that display shortcuts
+    pub fn shortcut(&self, msg: &Msg) -> Option<KeyEvent> {
+        self.bindings
+            .iter()
+            .find(|b| &b.msg == msg)
+            .and_then(|b| b.keys.first().copied())
+    }
+
+    /// Build the help footer for a view, e.g. `↑/k up • ↓/j down • esc back`
+    pub fn help(&self, view: ViewId, mode: InputMode) -> String {
+        let mut seen: Vec<&Msg> = Vec::new();
+        let mut parts = Vec::new();
+
+        for binding in self.bindings.iter().filter(|b| b.applies_to(view)) {
+            if seen.contains(&&binding.msg) {
+                continue;
+            }
+            if let Some(part) = self.describe_binding(binding, view, mode) {
+                seen.push(&binding.msg);
+                parts.push(part);
+            }
+        }
+
+        parts.join(" • ")
+    }
+
+    /// Every binding active in a view, grouped by category for the help overlay
+    pub fn sections(&self, view: ViewId) -> Vec<HelpSection> {
+        let mut sections: Vec<HelpSection> = CATEGORIES
+            .iter()
+            .map(|title| HelpSection {
+                title,
+                entries: Vec::new(),
+            })
+            .collect();
+
+        for binding in self.bindings.iter().filter(|b| b.applies_to(view)) {
+            let keys: Vec<String> = binding
+                .keys
+                .iter()
+                .filter(|k| {
+                    self.lookup(view, InputMode::Normal, k).as_ref() == Some(&binding.msg)
+                })
+                .map(key_label)
+                .collect();
+            if keys.is_empty() {
+                continue;
+            }
+
+            let label = binding
+                .help
+                .map(str::to_string)
+                .unwrap_or_else(|| msg_label(view, &binding.msg));
+            let category = category(view, &binding.msg);
+            if let Some(section) = sections.iter_mut().find(|s| s.title == category) {
+                // Bindings sharing a label (e.g. the digit keys) share a line
+                match section.entries.iter_mut().find(|(_, l)| *l == label) {
+                    Some((existing, _)) => {
+                        existing.push('/');
+                        existing.push_str(&keys.join("/"));
+                    }
+                    None => section.entries.push((keys.join("/"), label)),
+                }
+            }
+        }
+
+        sections.retain(|s| !s.entries.is_empty());
+        sections
+    }
+
+    /// Keys bound to `msg` in a view, e.g. `esc/q`, for custom help lines
+    pub fn keys(&self, view: ViewId, mode: InputMode, msg: &Msg) -> Option<String> {
+        let keys: Vec<String> = self
+            .bindings
+            .iter()
+            .filter(|b| b.applies_to(view) && &b.msg == msg)
+            .flat_map(|b| b.keys.iter())
+            .filter(|k| self.lookup(view, mode, k).as_ref() == Some(msg))
+            .map(key_label)
+            .collect();
+        (!keys.is_empty()).then(|| keys.join("/"))
+    }
+
+    /// Help entry for a single message, e.g. `esc/q back`
+    pub fn describe(&self, view: ViewId, mode: InputMode, msg: &Msg) -> Option<String> {
+        self.bindings
+            .iter()
+            .filter(|b| b.applies_to(view) && &b.msg == msg)
+            .find_map(|b| self.describe_binding(b, view, mode))
+    }
+
+    fn describe_binding(&self, binding: &Binding, view: ViewId, mode: InputMode) -> Option<String> {
+        let label = binding.help?;
+
+        // Only list keys that actually resolve to this binding in this view
+        let keys: Vec<String> = binding
+            .keys
+            .iter()
+            .filter(|k| self.lookup(view, mode, k).as_ref() == Some(&binding.msg))
+            .map(key_label)
+            .collect();
+
+        if keys.is_empty() {
+            None
+        } else {
+            Some(format!("{} {}", keys.join("/"), label))
+        }
+    }
+}
+
+/// A titled group of `(keys, description)` entries in the help overlay
+#[derive(Debug, Clone)]
+pub struct HelpSection {
+    pub title: &'static str,
+    pub entries: Vec<(String, String)>,
+}
+
+/// Help overlay category for a message
+///
+/// Field focus keys are form keys on Contact; elsewhere (e.g. the Blog tag
+/// bar) they move between items.
+fn category(view: ViewId, msg: &Msg) -> &'static str {
+    match msg {
+        Msg::FocusNext | Msg::FocusPrev if view != ViewId::Contact => "Movement",
+        Msg::CursorUp
+        | Msg::CursorDown
+        | Msg::ScrollUp
+        | Msg::ScrollDown
+        | Msg::PageUp
+        | Msg::PageDown
+        | Msg::JumpTop
+        | Msg::JumpBottom
+        | Msg::NextItem
+        | Msg::PrevItem
+        | Msg::FindNext
+        | Msg::FindPrev => "Movement",
+        Msg::FocusNext | Msg::FocusPrev | Msg::Submit | Msg::Backspace | Msg::TypeChar(_) => {
+            "Form"
+        }
+        Msg::NavigateTo(_) | Msg::GoBack | Msg::GoForward => "Navigation",
+        Msg::Quit | Msg::ShowHelp | Msg::ShowPalette | Msg::CloseOverlay => "General",
+        _ => "Actions",
+    }
+}
+
+/// Fallback description for bindings hidden from the footer
+fn msg_label(view: ViewId, msg: &Msg) -> String {
+    match msg {
+        Msg::FocusNext if view == ViewId::Projects => "next link".to_string(),
+        Msg::FocusPrev if view == ViewId::Projects => "previous link".to_string(),
+        Msg::NavigateTo(ViewId::Search) => "search everything".to_string(),
+        Msg::NavigateTo(view) => format!("go to {}", view.title()),
+        Msg::GoForward => "forward".to_string(),
+        Msg::FindNext => "next match".to_string(),
+        Msg::FindPrev => "previous match".to_string(),
+        Msg::JumpTop => "first item / top".to_string(),
+        Msg::JumpBottom => "last item / bottom".to_string(),
+        Msg::FocusNext => "next tag".to_string(),
+        Msg::FocusPrev => "previous tag".to_string(),
+        Msg::CopyLink(_) => "copy numbered link".to_string(),
+        Msg::CopyCode => "copy code block".to_string(),
+        Msg::NextItem if view == ViewId::Projects => "next project".to_string(),
+        Msg::PrevItem if view == ViewId::Projects => "previous project".to_string(),
+        Msg::NextItem => "next section".to_string(),
+        Msg::PrevItem => "previous section".to_string(),
+        Msg::ToggleToc => "table of contents".to_string(),
+        Msg::ShowPalette => "command palette".to_string(),
+        Msg::Quit => "quit".to_string(),
+        Msg::Backspace => "delete character".to_string(),
+        other => format!("{other:?}").to_lowercase(),
+    }
+}
+
+/// On-disk keymap format
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct KeymapFile {
+    preset: Option<Preset>,
+    keys: HashMap<String, Vec<String>>,
+    views: HashMap<String, HashMap<String, Vec<String>>>,
+}
+
+/// Map an action name from a keymap file to the message it produces
+fn action_msg(action: &str) -> Result<Msg> {
+    let msg = match action {
+        "quit" => Msg::Quit,
+        "back" => Msg::GoBack,
+        "forward" => Msg::GoForward,
+        "up" => Msg::CursorUp,
+        "down" => Msg::CursorDown,
+        "page_up" => Msg::PageUp,
+        "page_down" => Msg::PageDown,
+        "top" => Msg::JumpTop,
+        "bottom" => Msg::JumpBottom,
+        "select" => Msg::Select,
+        "next_field" => Msg::FocusNext,
+        "prev_field" => Msg::FocusPrev,
+        "submit" => Msg::Submit,
+        "help" => Msg::ShowHelp,
+        "palette" => Msg::ShowPalette,
+        "find_next" => Msg::FindNext,
+        "find_prev" => Msg::FindPrev,
+        "tags" => Msg::FocusTags,
+        "sort" => Msg::CycleSort,
+        "open" => Msg::Open,
+        "copy" => Msg::Copy,
+        "copy_code" => Msg::CopyCode,
+        "next_item" => Msg::NextItem,
+        "prev_item" => Msg::PrevItem,
+        "toc" => Msg::ToggleToc,
+        other => Msg::NavigateTo(view_id(other).map_err(|_| anyhow!("unknown action `{other}`"))?),
+    };
+    Ok(msg)
+}
+
+/// Map a view name from a keymap file to its identifier
+fn view_id(name: &str) -> Result<ViewId> {
+    match name {
+        "home" => Ok(ViewId::Home),
+        "resume" => Ok(ViewId::Resume),
+        "blog" => Ok(ViewId::Blog),
+        "projects" => Ok(ViewId::Projects),
+        "contact" => Ok(ViewId::Contact),
+        "settings" => Ok(ViewId::Settings),
+        "search" => Ok(ViewId::Search),
+        other => bail!("unknown view `{other}`"),
+    }
+}
+
+fn parse_keys(keys: &[String]) -> Result<Vec<KeyEvent>> {
+    keys.iter().map(|k| parse_key(k)).collect()
+}
+
+/// Parse a key description such as `ctrl+n`, `shift+tab`, `pgdn` or `j`
+pub fn parse_key(text: &str) -> Result<KeyEvent> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut rest = text;
+
+    loop {
+        if let Some(r) = rest.strip_prefix("ctrl+") {
+            modifiers |= KeyModifiers::CONTROL;
+            rest = r;
+        } else if let Some(r) = rest.strip_prefix("alt+") {
+            modifiers |= KeyModifiers::ALT;
+            rest = r;
+        } else if let Some(r) = rest.strip_prefix("shift+") {
+            modifiers |= KeyModifiers::SHIFT;
+            rest = r;
+        } else {
+            break;
+        }
+    }
+
+    let code = match rest {
+        "up" | "↑" => Key::Up,
+        "down" | "↓" => Key::Down,
+        "left" | "←" => Key::Left,
+        "right" | "→" => Key::Right,
+        "enter" | "return" => Key::Enter,
+        "esc" | "escape" => Key::Esc,
+        "tab" if modifiers.contains(KeyModifiers::SHIFT) => {
+            modifiers -= KeyModifiers::SHIFT;
+            Key::BackTab
+        }
+        "tab" => Key::Tab,
+        "backspace" => Key::Backspace,
+        "pgup" | "pageup" => Key::PageUp,
+        "pgdn" | "pagedown" => Key::PageDown,
+        "home" => Key::Home,
+        "end" => Key::End,
+        "space" => Key::Char(' '),
+        f if f.len() > 1 && f.starts_with('f') && f[1..].parse::<u8>().is_ok() => {
+            Key::Function(f[1..].parse()?)
+        }
+        s => {
+            let mut chars = s.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Key::Char(c),
+                _ => bail!("unknown key `{text}`"),
+            }
+        }
+    };
+
+    Ok(KeyEvent { code, modifiers })
+}
+
+/// The keymap in effect for this process (one SSH session per process)
+static ACTIVE: Lazy<RwLock<Arc<Keymap>>> = Lazy::new(|| RwLock::new(Arc::new(Keymap::default())));
+
+/// Get the active keymap
+pub fn keymap() -> Arc<Keymap> {
+    Arc::clone(&ACTIVE.read().unwrap_or_else(|e| e.into_inner()))
+}
+
+/// Replace the active keymap
+pub fn set_keymap(keymap: Keymap) {
+    *ACTIVE.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(keymap);
+}
+
+/// Translate a terminal event into a message for `view`
+///
+/// The mouse wheel scrolls in every view and a left click becomes
+/// [`Msg::Click`] for the view to hit-test. Returns `None` for events that
+/// have no meaning in this view.
+pub fn route(view: ViewId, mode: InputMode, ev: &Event<NoUserEvent>) -> Option<Msg> {
+    match ev {
+        Event::Tick => Some(Msg::Tick),
+        Event::Keyboard(key_event) => keymap().lookup(view, mode, key_event),
+        Event::Mouse(MouseEvent {
+            kind: MouseEventKind::ScrollUp,
+            ..
+        }) => Some(Msg::ScrollUp),
+        Event::Mouse(MouseEvent {
+            kind: MouseEventKind::ScrollDown,
+            ..
+        }) => Some(Msg::ScrollDown),
+        Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            ..
+        }) => Some(Msg::Click(*column, *row)),
+        _ => None,
+    }
+}
+
+/// Key with no modifiers
+const fn key(code: Key) -> KeyEvent {
+    KeyEvent {
+        code,
+        modifiers: KeyModifiers::NONE,
+    }
+}
+
+/// Plain character key
+const fn ch(c: char) -> KeyEvent {
+    key(Key::Char(c))
+}
+
+/// Ctrl + character
+const fn ctrl(c: char) -> KeyEvent {
+    KeyEvent {
+        code: Key::Char(c),
+        modifiers: KeyModifiers::CONTROL,
+    }
+}
+
+/// Alt + character
+const fn alt(c: char) -> KeyEvent {
+    KeyEvent {
+        code: Key::Char(c),
+        modifiers: KeyModifiers::ALT,
+    }
+}
+
+/// Compare a bound key against an incoming event
+///
+/// Shift is ignored for characters since it is already reflected in the
+/// character itself (`Q` vs `q`).
+fn key_matches(bound: &KeyEvent, ev: &KeyEvent) -> bool {
+    if bound.code != ev.code {
+        return false;
+    }
+    match ev.code {
+        Key::Char(_) => {
+            (bound.modifiers - KeyModifiers::SHIFT) == (ev.modifiers - KeyModifiers::SHIFT)
+        }
+        _ => bound.modifiers == ev.modifiers,
+    }
+}
+
+/// The character a key event types, if it is plain text input
+fn typed_char(ev: &KeyEvent) -> Option<char> {
+    match ev.code {
+        Key::Char(c) if (ev.modifiers - KeyModifiers::SHIFT).is_empty() => Some(c),
+        _ => None,
+    }
+}
+
+/// Human-readable key label for help text
+pub fn key_label(ev: &KeyEvent) -> String {
+    let base = match ev.code {
+        Key::Up => "↑".to_string(),
+        Key::Down => "↓".to_string(),
+        Key::Left => "←".to_string(),
+        Key::Right => "→".to_string(),
+        Key::Enter => "enter".to_string(),
+        Key::Esc => "esc".to_string(),
+        Key::Tab => "tab".to_string(),
+        Key::BackTab => "shift+tab".to_string(),
+        Key::Backspace => "backspace".to_string(),
+        Key::PageUp => "pgup".to_string(),
+        Key::PageDown => "pgdn".to_string(),
+        Key::Home => "home".to_string(),
+        Key::End => "end".to_string(),
+        Key::Function(n) => format!("f{n}"),
+        Key::Char(' ') => "space".to_string(),
+        Key::Char(c) => c.to_string(),
+        other => format!("{other:?}").to_lowercase(),
+    };
+
+    if ev.modifiers.contains(KeyModifiers::CONTROL) {
+        format!("ctrl+{base}")
+    } else if ev.modifiers.contains(KeyModifiers::ALT) {
+        format!("alt+{base}")
+    } else {
+        base
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_view_override_wins() {
+        let keymap = Keymap::default();
+        assert_eq!(
+            keymap.lookup(ViewId::Home, InputMode::Normal, &ch('q')),
+            Some(Msg::Quit)
+        );
+        assert_eq!(
+            keymap.lookup(ViewId::Resume, InputMode::Normal, &ch('q')),
+            Some(Msg::GoBack)
+        );
+    }
+
+    #[test]
+    fn test_text_input_captures_characters() {
+        let keymap = Keymap::default();
+        assert_eq!(
+            keymap.lookup(ViewId::Contact, InputMode::TextInput, &ch('c')),
+            Some(Msg::TypeChar('c'))
+        );
+        assert_eq!(
+            keymap.lookup(ViewId::Contact, InputMode::Normal, &ch('c')),
+            Some(Msg::NavigateTo(ViewId::Contact))
+        );
+        assert_eq!(
+            keymap.lookup(ViewId::Contact, InputMode::TextInput, &ctrl('c')),
+            Some(Msg::Quit)
+        );
+    }
+
+    #[test]
+    fn test_help_generated_from_table() {
+        let keymap = Keymap::default();
+        assert_eq!(
+            keymap.help(ViewId::Home, InputMode::Normal),
+            "↑/k up • ↓/j down • enter select • q quit • ?/f1 help"
+        );
+        assert_eq!(
+            keymap.help(ViewId::Contact, InputMode::TextInput),
+            "tab/↓ next • shift+tab/↑ prev • enter submit • ctrl+y copy email • esc back • f1 help"
+        );
+    }
+
+    #[test]
+    fn test_list_views_share_letter_meanings() {
+        let keymap = Keymap::default();
+        let lookup = |view, c| keymap.lookup(view, InputMode::Normal, &ch(c));
+        assert_eq!(lookup(ViewId::Projects, 'o'), Some(Msg::Open));
+        assert_eq!(lookup(ViewId::Blog, 'o'), None);
+        assert_eq!(lookup(ViewId::Blog, 'S'), Some(Msg::CycleSort));
+        assert_eq!(lookup(ViewId::Blog, 's'), Some(Msg::NavigateTo(ViewId::Settings)));
+
+        let help = keymap.help(ViewId::Blog, InputMode::Normal);
+        assert!(help.contains("S sort") && !help.contains("o sort"), "{help}");
+    }
+
+    #[test]
+    fn test_presets_change_movement_keys() {
+        let emacs = Keymap::preset(Preset::Emacs);
+        assert_eq!(
+            emacs.lookup(ViewId::Resume, InputMode::Normal, &ctrl('n')),
+            Some(Msg::CursorDown)
+        );
+        assert_eq!(
+            emacs.lookup(ViewId::Resume, InputMode::Normal, &ch('j')),
+            None
+        );
+
+        let arrows = Keymap::preset(Preset::Arrows);
+        assert_eq!(
+            arrows.help(ViewId::Resume, InputMode::Normal),
+            "↑ up • ↓ down • pgup page up • pgdn page down • T contents • esc back • ?/f1 help"
+        );
+    }
+
+    #[test]
+    fn test_keymap_file_overrides() {
+        let keymap = Keymap::from_toml(
+            r#"
+            preset = "arrows"
+
+            [keys]
+            down = ["ctrl+n", "down"]
+
+            [views.blog]
+            select = ["enter", "o"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(keymap.active_preset(), None);
+        assert_eq!(
+            keymap.lookup(ViewId::Projects, InputMode::Normal, &ctrl('n')),
+            Some(Msg::CursorDown)
+        );
+        assert_eq!(
+            keymap.lookup(ViewId::Blog, InputMode::Normal, &ch('o')),
+            Some(Msg::Select)
+        );
+        assert!(keymap
+            .help(ViewId::Blog, InputMode::Normal)
+            .contains("enter/o read"));
+
+        assert!(Keymap::from_toml("[keys]\nfly = [\"f\"]").is_err());
+        assert!(Keymap::from_toml("[keys]\nup = [\"hyper+k\"]").is_err());
+    }
+
+    #[test]
+    fn test_parse_key() {
+        assert_eq!(parse_key("f1").unwrap(), key(Key::Function(1)));
+        assert_eq!(parse_key("ctrl+n").unwrap(), ctrl('n'));
+        assert_eq!(parse_key("shift+tab").unwrap(), key(Key::BackTab));
+        assert!(parse_key("fly").is_err());
+    }
+
+    #[test]
+    fn test_help_sections_list_hidden_bindings() {
+        let sections = Keymap::default().sections(ViewId::Blog);
+        let titles: Vec<&str> = sections.iter().map(|s| s.title).collect();
+        assert_eq!(titles, vec!["Movement", "Actions", "Navigation", "General"]);
+
+        let navigation = &sections[2].entries;
+        assert!(navigation.contains(&("r".to_string(), "go to Resume".to_string())));
+        assert!(navigation.contains(&("esc/q".to_string(), "back".to_string())));
+    }
+}
@@ -2,9 +2,19 @@
 
 pub mod components;
 pub mod content;
+pub mod export;
+pub mod history;
+pub mod keymap;
+pub mod links;
 pub mod model;
 pub mod msg;
+pub mod palette;
+pub mod recording;
+pub mod render;
+pub mod route;
 pub mod runtime;
+pub mod search;
+pub mod sftp;
 pub mod styles;
 pub mod ui;
 pub mod widgets;
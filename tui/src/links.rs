@@ -0,0 +1,305 @@
+//! Copying text to the visitor's clipboard, opening URLs and hyperlinks
+//!
+//! Copying uses the OSC 52 escape sequence, which the visitor's terminal
+//! handles, so it also works over SSH. Opening a URL starts a browser on the
+//! machine running the TUI and is therefore only done for local sessions.
+//!
+//! Views draw links in [`styles::link`] and [`place`] each one where it is
+//! drawn, with its URL. After each frame the runtime redraws those cells
+//! wrapped in OSC 8 escape sequences ([`hyperlink_cells`]), so the terminal
+//! makes them clickable without ratatui's buffer ever holding zero-width
+//! escape sequences.
+
+use std::cell::RefCell;
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use ratatui::buffer::{Buffer, Cell};
+use ratatui::layout::Rect;
+use ratatui::text::Line;
+
+use crate::styles;
+
+/// Set for sessions served by the SSH server
+static REMOTE: AtomicBool = AtomicBool::new(false);
+
+/// Set when links are drawn as OSC 8 hyperlinks
+static HYPERLINKS: AtomicBool = AtomicBool::new(false);
+
+/// Mark this process as serving a remote visitor
+pub fn set_remote(remote: bool) {
+    REMOTE.store(remote, Ordering::Relaxed);
+}
+
+/// Whether the visitor is on another machine, where a browser we start
+/// would never be seen
+pub fn is_remote() -> bool {
+    REMOTE.load(Ordering::Relaxed)
+        || std::env::var_os("SSH_CONNECTION").is_some()
+        || std::env::var_os("SSH_TTY").is_some()
+}
+
+/// Ask the terminal to put `text` on the clipboard (OSC 52)
+pub fn copy(out: &mut impl Write, text: &str) -> io::Result<()> {
+    write!(out, "\x1b]52;c;{}\x07", base64(text.as_bytes()))?;
+    out.flush()
+}
+
+/// Open `url` in the local browser
+pub fn open(url: &str) -> io::Result<()> {
+    if is_remote() {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "cannot open a browser over SSH",
+        ));
+    }
+
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    Command::new(opener)
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(drop)
+}
+
+/// URLs of the links in a line, by the index of the span drawing each
+pub type SpanLinks = Vec<(usize, String)>;
+
+/// A link drawn in the current frame: its row, columns and URL
+struct Placed {
+    x: u16,
+    y: u16,
+    width: u16,
+    url: String,
+}
+
+thread_local! {
+    /// Links placed since the frame started
+    static PLACED: RefCell<Vec<Placed>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Start a frame, forgetting the links placed in the previous one
+pub fn clear() {
+    PLACED.with_borrow_mut(Vec::clear);
+}
+
+/// Record the links of `rows` drawn top to bottom from the corner of `area`
+///
+/// Each row is a line and the URLs of its link spans. Parts outside `area`
+/// are clipped, as ratatui clips the text, and URLs holding control
+/// characters are dropped: they would end the escape sequence early.
+pub fn place<'a>(area: Rect, rows: impl IntoIterator<Item = (&'a Line<'a>, &'a [(usize, String)])>) {
+    PLACED.with_borrow_mut(|placed| {
+        for ((line, links), y) in rows.into_iter().zip(area.top()..area.bottom()) {
+            for (span, url) in links {
+                if url.chars().any(char::is_control) {
+                    continue;
+                }
+                let Some(width) = line.spans.get(*span).map(|s| s.width() as u16) else {
+                    continue;
+                };
+                let before: usize = line.spans[..*span].iter().map(|s| s.width()).sum();
+                let x = area.x.saturating_add(before as u16);
+                let width = width.min(area.right().saturating_sub(x));
+                if width > 0 {
+                    placed.push(Placed {
+                        x,
+                        y,
+                        width,
+                        url: url.clone(),
+                    });
+                }
+            }
+        }
+    });
+}
+
+/// Draw links as clickable hyperlinks (`true`) or number them for copying
+///
+/// Off until set; the runtime sets it from [`hyperlinks_supported`].
+pub fn set_hyperlinks(enabled: bool) {
+    HYPERLINKS.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether links are drawn as clickable hyperlinks, so views need not
+/// number them
+pub fn hyperlinks_enabled() -> bool {
+    HYPERLINKS.load(Ordering::Relaxed)
+}
+
+/// Whether the terminal is known to support OSC 8 hyperlinks
+///
+/// There is no way to query this, so it is guessed from the environment.
+/// `FORCE_HYPERLINK=1` or `0` overrides the guess.
+pub fn hyperlinks_supported() -> bool {
+    detect_hyperlinks(|name| std::env::var(name).ok())
+}
+
+fn detect_hyperlinks(var: impl Fn(&str) -> Option<String>) -> bool {
+    if let Some(force) = var("FORCE_HYPERLINK") {
+        return force != "0";
+    }
+
+    let program = var("TERM_PROGRAM").unwrap_or_default();
+    let term = var("TERM").unwrap_or_default();
+    let vte = var("VTE_VERSION").and_then(|v| v.parse::<u32>().ok());
+
+    ["iTerm.app", "WezTerm", "vscode", "ghostty", "Tabby", "Hyper"].contains(&program.as_str())
+        || var("LC_TERMINAL").is_some_and(|t| t == "iTerm2")
+        || var("WT_SESSION").is_some()
+        || ["kitty", "alacritty", "foot", "wezterm", "ghostty"]
+            .iter()
+            .any(|name| term.contains(name))
+        || vte.is_some_and(|v| v >= 5000)
+}
+
+/// Cells that redraw every link placed in this frame as an OSC 8 hyperlink
+///
+/// The escape sequences ride along in the first and last cell of each link,
+/// for [`ratatui::backend::Backend::draw`] after the frame is flushed. Only
+/// cells still drawn in the link style count, so a popup drawn over a link
+/// hides it.
+pub fn hyperlink_cells(buffer: &Buffer) -> Vec<(u16, u16, Cell)> {
+    let area = buffer.area;
+    let link = styles::link();
+    let is_link = |cell: &Cell| Some(cell.fg) == link.fg && cell.modifier == link.add_modifier;
+    let mut cells = Vec::new();
+
+    PLACED.with_borrow(|placed| {
+        for placed in placed {
+            let y = placed.y;
+            let end = placed.x.saturating_add(placed.width).min(area.right());
+            if y < area.top() || y >= area.bottom() {
+                continue;
+            }
+            let mut x = placed.x.max(area.left());
+            while x < end {
+                if !is_link(&buffer[(x, y)]) {
+                    x += 1;
+                    continue;
+                }
+
+                let start = x;
+                while x < end && is_link(&buffer[(x, y)]) {
+                    x += 1;
+                }
+                let last = x - 1;
+                for x in start..x {
+                    let mut cell = buffer[(x, y)].clone();
+                    let mut symbol = cell.symbol().to_string();
+                    if x == start {
+                        symbol = format!("\x1b]8;;{}\x07{symbol}", placed.url);
+                    }
+                    if x == last {
+                        symbol.push_str("\x1b]8;;\x07");
+                    }
+                    cell.set_symbol(&symbol);
+                    cells.push((x, y, cell));
+                }
+            }
+        }
+    });
+
+    cells
+}
+
+/// Standard base64 with padding, as OSC 52 expects
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] =
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::text::Span;
+
+    use super::*;
+
+    #[test]
+    fn test_hyperlink_cells_wrap_links() {
+        let area = Rect::new(0, 0, 30, 2);
+        let mut buffer = Buffer::empty(area);
+        let lines = [
+            Line::from(vec![
+                Span::raw("see "),
+                Span::styled("site", styles::link()),
+                Span::raw(" or "),
+                Span::styled("site", styles::link()),
+            ]),
+            Line::from(vec![Span::styled("bad", styles::link())]),
+        ];
+        for (y, line) in lines.iter().enumerate() {
+            buffer.set_line(0, y as u16, line, 30);
+        }
+        let links: [SpanLinks; 2] = [
+            vec![(1, "http://a.io".to_string()), (3, "http://b.io".to_string())],
+            vec![(0, "http://c.io\x1b]8;;evil\x07".to_string())],
+        ];
+
+        clear();
+        place(area, lines.iter().zip(links.iter().map(Vec::as_slice)));
+        let cells = hyperlink_cells(&buffer);
+        clear();
+
+        // The same text links to where each span points
+        assert_eq!(cells.len(), 8);
+        assert_eq!(cells[0].0, 4);
+        assert_eq!(cells[0].2.symbol(), "\x1b]8;;http://a.io\x07s");
+        assert_eq!(cells[3].2.symbol(), "e\x1b]8;;\x07");
+        assert_eq!(cells[4].0, 12);
+        assert_eq!(cells[4].2.symbol(), "\x1b]8;;http://b.io\x07s");
+        // A URL that would end the escape sequence is never emitted
+        assert!(cells.iter().all(|(_, y, _)| *y == 0));
+    }
+
+    #[test]
+    fn test_detect_hyperlinks_from_environment() {
+        let env = |pairs: &'static [(&str, &str)]| {
+            move |name: &str| {
+                pairs
+                    .iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+        assert!(detect_hyperlinks(env(&[("TERM", "xterm-kitty")])));
+        assert!(detect_hyperlinks(env(&[("VTE_VERSION", "6003")])));
+        assert!(!detect_hyperlinks(env(&[("TERM", "xterm-256color")])));
+        assert!(!detect_hyperlinks(env(&[("TERM", "xterm-kitty"), ("FORCE_HYPERLINK", "0")])));
+    }
+
+    #[test]
+    fn test_copy_writes_osc52() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+
+        let mut out = Vec::new();
+        copy(&mut out, "hi").unwrap();
+        assert_eq!(out, b"\x1b]52;c;aGk=\x07");
+    }
+}
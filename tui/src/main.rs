@@ -1,7 +1,77 @@
 //! TUI Portfolio Application entrypoint.
 
-use std::io;
+use std::env;
+use std::path::PathBuf;
 
-fn main() -> io::Result<()> {
-    whoami_tui::runtime::run_stdio_tui()
+use anyhow::{Context, Result};
+use whoami_tui::keymap::{set_keymap, Keymap};
+use whoami_tui::render::RenderRequest;
+use whoami_tui::route::Route;
+
+fn main() -> Result<()> {
+    if let Some(path) = keymap_path()? {
+        set_keymap(Keymap::load(&path)?);
+    }
+
+    if let Some(request) = render_request()? {
+        print!("{}", request.render()?);
+        return Ok(());
+    }
+
+    whoami_tui::runtime::run_stdio_tui(open_route()?)?;
+    Ok(())
+}
+
+/// Deep link from `--open <path>`, e.g. `--open /blog/my-post`
+fn open_route() -> Result<Option<Route>> {
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--open" {
+            let path = args.next().context("--open requires a path")?;
+            let route = path.parse().with_context(|| format!("--open {path}"))?;
+            return Ok(Some(route));
+        }
+    }
+    Ok(None)
+}
+
+/// `tui render --view <route> ...` draws the view once to stdout instead of
+/// starting the interactive TUI
+fn render_request() -> Result<Option<RenderRequest>> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.first().map(String::as_str) != Some("render") {
+        return Ok(None);
+    }
+    // `--keymap <path>` is read by `keymap_path`
+    let mut render_args = Vec::new();
+    let mut rest = args[1..].iter().map(String::as_str);
+    while let Some(arg) = rest.next() {
+        match arg {
+            "--keymap" => {
+                rest.next();
+            }
+            arg => render_args.push(arg),
+        }
+    }
+    RenderRequest::parse(render_args).map(Some)
+}
+
+/// Keymap file from `--keymap <path>`, or `$XDG_CONFIG_HOME/whoami/keymap.toml`
+/// (falling back to `~/.config`) when it exists
+fn keymap_path() -> Result<Option<PathBuf>> {
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--keymap" {
+            let path = args.next().context("--keymap requires a path")?;
+            return Ok(Some(PathBuf::from(path)));
+        }
+    }
+
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")));
+
+    Ok(config_dir
+        .map(|dir| dir.join("whoami").join("keymap.toml"))
+        .filter(|path| path.is_file()))
 }
@@ -1,11 +1,20 @@
 //! Application model with state management via Update trait
 
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use tuirealm::Update;
 
+use crate::history::{Entry, History, ViewCache};
+use crate::keymap::{set_keymap, Keymap};
 use crate::msg::{Msg, ViewId};
-use crate::widgets::LoadingState;
+use crate::widgets::{LoadingState, Severity};
+
+/// A transient message for the status bar
+struct Status {
+    text: String,
+    severity: Severity,
+    until: Instant,
+}
 
 /// Main application state model
 pub struct Model {
@@ -13,10 +22,24 @@ pub struct Model {
     pub quit: bool,
     /// Currently active view
     pub current_view: ViewId,
+    /// Views visited before and after the current one
+    pub history: History,
+    /// State each view was last left in
+    pub saved_views: ViewCache,
     /// Whether a redraw is needed
     pub redraw: bool,
     /// Loading animation state
     pub loading: LoadingState,
+    /// Message shown in the status bar until it expires
+    status: Option<Status>,
+    /// When the session started, for the session clock
+    pub started_at: Instant,
+    /// Last user input, for the idle countdown
+    pub last_input: Instant,
+    /// Inactivity after which the session is closed (SSH sessions)
+    pub idle_timeout: Option<Duration>,
+    /// Longest the session may last (SSH sessions)
+    pub max_session: Option<Duration>,
     /// Terminal dimensions
     pub width: u16,
     pub height: u16,
@@ -28,20 +51,99 @@ impl Model {
         Self {
             quit: false,
             current_view: ViewId::Loading,
+            history: History::new(),
+            saved_views: ViewCache::new(),
             redraw: true,
             loading: LoadingState::new(Duration::from_secs(3)),
+            status: None,
+            started_at: Instant::now(),
+            last_input: Instant::now(),
+            idle_timeout: None,
+            max_session: None,
             width: 80,
             height: 24,
         }
     }
 
-    /// Update terminal dimensions
-    pub fn set_size(&mut self, width: u16, height: u16) {
-        self.width = width;
-        self.height = height;
+    /// Apply a navigation message, returning the entry to show next
+    ///
+    /// `from` is the current view with its state snapshot, saved so that
+    /// back and forward restore it exactly and reopening the view resumes it.
+    pub fn navigate(&mut self, msg: &Msg, from: Entry) -> Option<Entry> {
+        self.saved_views.save(&from);
+
+        let target = match msg {
+            Msg::NavigateTo(view) if *view != from.view => {
+                self.history.visit(from);
+                Some(Entry {
+                    view: *view,
+                    state: self.saved_views.get(*view),
+                })
+            }
+            Msg::OpenRoute(route) | Msg::OpenMatch(route, _) => {
+                self.history.visit(from);
+                Some(route.entry())
+            }
+            Msg::GoBack => match self.history.back(from.clone()) {
+                Some(entry) => Some(entry),
+                // Nothing to go back to: fall back to the home screen
+                None if from.view != ViewId::Home => {
+                    self.history.visit(from);
+                    Some(Entry {
+                        view: ViewId::Home,
+                        state: self.saved_views.get(ViewId::Home),
+                    })
+                }
+                None => None,
+            },
+            Msg::GoForward => self.history.forward(from),
+            _ => None,
+        };
+
+        if let Some(entry) = &target {
+            self.current_view = entry.view;
+            self.redraw = true;
+        }
+        target
+    }
+
+    /// Show a message in the status bar; more serious ones stay up longer
+    pub fn notify(&mut self, severity: Severity, text: impl Into<String>) {
+        let duration = match severity {
+            Severity::Info => Duration::from_secs(3),
+            Severity::Warning => Duration::from_secs(5),
+            Severity::Error => Duration::from_secs(8),
+        };
+        self.status = Some(Status {
+            text: text.into(),
+            severity,
+            until: Instant::now() + duration,
+        });
         self.redraw = true;
     }
 
+    /// Status bar message, until it expires
+    pub fn status(&self) -> Option<(&str, Severity)> {
+        self.status
+            .as_ref()
+            .filter(|status| Instant::now() < status.until)
+            .map(|status| (status.text.as_str(), status.severity))
+    }
+
+    /// Time until the server closes the session, whichever limit comes first
+    pub fn time_left(&self) -> Option<Duration> {
+        let idle = self
+            .idle_timeout
+            .map(|limit| limit.saturating_sub(self.last_input.elapsed()));
+        let session = self
+            .max_session
+            .map(|limit| limit.saturating_sub(self.started_at.elapsed()));
+        match (idle, session) {
+            (Some(idle), Some(session)) => Some(idle.min(session)),
+            (left, None) | (None, left) => left,
+        }
+    }
+
     /// Check if loading is complete and transition to Home
     pub fn check_loading_complete(&mut self) {
         if self.current_view == ViewId::Loading && self.loading.is_complete() {
@@ -66,12 +168,16 @@ impl Update<Msg> for Model {
                 self.quit = true;
                 None
             }
-            Some(Msg::NavigateTo(view_id)) => {
-                self.current_view = view_id;
+            Some(Msg::UpdateStatus(text)) => {
+                self.notify(Severity::Info, text);
+                None
+            }
+            Some(Msg::ShowError(text)) => {
+                self.notify(Severity::Error, text);
                 None
             }
-            Some(Msg::GoBack) => {
-                self.current_view = ViewId::Home;
+            Some(Msg::SetKeymap(preset)) => {
+                set_keymap(Keymap::preset(preset));
                 None
             }
             Some(Msg::Tick) => {
@@ -90,3 +196,53 @@ impl Update<Msg> for Model {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_left_uses_the_nearest_limit() {
+        let mut model = Model::new();
+        assert_eq!(model.time_left(), None);
+
+        model.idle_timeout = Some(Duration::from_secs(180));
+        model.max_session = Some(Duration::from_secs(900));
+        model.last_input = Instant::now() - Duration::from_secs(60);
+        let left = model.time_left().unwrap();
+        assert!(left <= Duration::from_secs(120) && left > Duration::from_secs(110));
+
+        model.started_at = Instant::now() - Duration::from_secs(850);
+        assert!(model.time_left().unwrap() <= Duration::from_secs(50));
+    }
+
+    #[test]
+    fn test_show_error_sets_the_status() {
+        let mut model = Model::new();
+        assert_eq!(model.status(), None);
+
+        model.update(Some(Msg::ShowError("Message is required".to_string())));
+        assert_eq!(model.status(), Some(("Message is required", Severity::Error)));
+    }
+
+    #[test]
+    fn test_status_expires_by_severity() {
+        let mut model = Model::new();
+        let shown_for = |model: &Model| {
+            let status = model.status.as_ref().unwrap();
+            status.until.duration_since(Instant::now()).as_secs_f32().round() as u64
+        };
+
+        model.notify(Severity::Info, "Copied");
+        assert_eq!(shown_for(&model), 3);
+        model.notify(Severity::Warning, "Recording");
+        assert_eq!(shown_for(&model), 5);
+        model.notify(Severity::Error, "Copy failed");
+        assert_eq!(shown_for(&model), 8);
+        assert_eq!(model.status(), Some(("Copy failed", Severity::Error)));
+
+        // Past its time the message is gone
+        model.status.as_mut().unwrap().until = Instant::now() - Duration::from_millis(1);
+        assert_eq!(model.status(), None);
+    }
+}
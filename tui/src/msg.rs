@@ -1,11 +1,20 @@
 //! Message protocol for inter-component communication
 
+use crate::keymap::Preset;
+use crate::route::Route;
+use crate::search::Find;
+
 /// Application messages for component communication
 #[derive(Debug, PartialEq, Clone)]
 pub enum Msg {
     // Navigation
     NavigateTo(ViewId),
     GoBack,
+    GoForward,
+    /// Open a route, e.g. a specific post chosen in the command palette
+    OpenRoute(Route),
+    /// Open a route scrolled to a search match, highlighting the phrase
+    OpenMatch(Route, Find),
 
     // Application lifecycle
     Quit,
@@ -16,9 +25,44 @@ pub enum Msg {
     ScrollDown,
     PageUp,
     PageDown,
+    /// First/last item or top/bottom of the page
+    JumpTop,
+    JumpBottom,
     CursorUp,
     CursorDown,
     Select,
+    /// Next/previous match of the highlighted search phrase
+    FindNext,
+    FindPrev,
+    /// Left mouse button pressed at this column and row
+    Click(u16, u16),
+
+    // Blog list
+    /// Move focus between the post list and its tag filter bar
+    FocusTags,
+    /// Cycle the post order: newest, oldest, title
+    CycleSort,
+
+    // Projects, Resume and posts
+    /// Next/previous project while one is open, or next/previous section
+    NextItem,
+    PrevItem,
+    /// Show or hide the table of contents
+    ToggleToc,
+
+    // Links
+    /// Open the link under the cursor in a browser
+    Open,
+    /// Copy the link under the cursor (or the page's address) to the clipboard
+    Copy,
+    /// Copy the code block under the cursor
+    CopyCode,
+    /// Copy the link with this number (1-based) in the page's link list
+    CopyLink(usize),
+    /// Open a URL in the local browser (copied instead over SSH)
+    OpenUrl(String),
+    /// Put text on the visitor's clipboard
+    CopyText(String),
 
     // Form-specific
     FocusNext,
@@ -27,6 +71,14 @@ pub enum Msg {
     Backspace,
     Submit,
 
+    // Overlays
+    ShowHelp,
+    ShowPalette,
+    CloseOverlay,
+
+    // Settings
+    SetKeymap(Preset),
+
     // Status
     UpdateStatus(String),
     ShowError(String),
@@ -44,30 +96,29 @@ pub enum ViewId {
     Blog,
     Projects,
     Contact,
+    Settings,
+    /// Full-text search
+    Search,
+    /// Keybinding help overlay
+    Help,
+    /// Command palette overlay
+    Palette,
 }
 
 impl ViewId {
-    /// Get the shortcut key for this view
-    pub fn shortcut(&self) -> Option<char> {
+    /// Human-readable view name
+    pub fn title(&self) -> &'static str {
         match self {
-            ViewId::Home => Some('h'),
-            ViewId::Resume => Some('r'),
-            ViewId::Blog => Some('b'),
-            ViewId::Projects => Some('p'),
-            ViewId::Contact => Some('c'),
-            ViewId::Loading => None,
-        }
-    }
-
-    /// Look up a view by its shortcut key
-    pub fn from_shortcut(c: char) -> Option<ViewId> {
-        match c {
-            'h' => Some(ViewId::Home),
-            'r' => Some(ViewId::Resume),
-            'b' => Some(ViewId::Blog),
-            'p' => Some(ViewId::Projects),
-            'c' => Some(ViewId::Contact),
-            _ => None,
+            ViewId::Loading => "Loading",
+            ViewId::Home => "Home",
+            ViewId::Resume => "Resume",
+            ViewId::Blog => "Blog",
+            ViewId::Projects => "Projects",
+            ViewId::Contact => "Contact",
+            ViewId::Settings => "Settings",
+            ViewId::Search => "Search",
+            ViewId::Help => "Help",
+            ViewId::Palette => "Palette",
         }
     }
 }
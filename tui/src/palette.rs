@@ -0,0 +1,245 @@
+//! Command palette index and fuzzy matching
+//!
+//! The index lists every view, post, project and app action. It is built
+//! from the content module on first use; content is compiled in, so it never
+//! changes for the life of the process.
+
+use std::cmp::Reverse;
+
+use once_cell::sync::Lazy;
+
+use crate::content::{self, projects_in_display_order, POSTS};
+use crate::keymap::Preset;
+use crate::msg::{Msg, ViewId};
+use crate::route::Route;
+
+/// Views reachable from the palette
+const VIEWS: &[ViewId] = &[
+    ViewId::Home,
+    ViewId::Resume,
+    ViewId::Blog,
+    ViewId::Projects,
+    ViewId::Contact,
+    ViewId::Settings,
+    ViewId::Search,
+];
+
+/// Kind of palette entry, shown as a column in the results
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemKind {
+    View,
+    Post,
+    Project,
+    Action,
+}
+
+impl ItemKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ItemKind::View => "view",
+            ItemKind::Post => "post",
+            ItemKind::Project => "project",
+            ItemKind::Action => "action",
+        }
+    }
+}
+
+/// A single jump target or action
+#[derive(Debug, Clone)]
+pub struct PaletteItem {
+    pub kind: ItemKind,
+    pub title: String,
+    /// Tags or tech stack entries, also matched against the query
+    pub keywords: Vec<String>,
+    /// Message sent when the item is chosen
+    pub msg: Msg,
+}
+
+/// A search result with the positions of matched characters in the title
+#[derive(Debug, Clone)]
+pub struct PaletteMatch {
+    pub item: PaletteItem,
+    pub score: i64,
+    pub positions: Vec<usize>,
+    /// Keyword that matched when the title did not
+    pub keyword: Option<String>,
+}
+
+static INDEX: Lazy<Vec<PaletteItem>> = Lazy::new(build_index);
+
+/// The palette index
+pub fn index() -> &'static [PaletteItem] {
+    &INDEX
+}
+
+/// Build the index from the content module
+fn build_index() -> Vec<PaletteItem> {
+    let mut items: Vec<PaletteItem> = VIEWS
+        .iter()
+        .map(|view| PaletteItem {
+            kind: ItemKind::View,
+            title: view.title().to_string(),
+            keywords: Vec::new(),
+            msg: Msg::NavigateTo(*view),
+        })
+        .collect();
+
+    items.extend(POSTS.iter().map(|post| PaletteItem {
+        kind: ItemKind::Post,
+        title: post.title.to_string(),
+        keywords: post.tags.iter().map(|t| t.to_string()).collect(),
+        msg: Msg::OpenRoute(Route::BlogPost(post.slug.to_string())),
+    }));
+
+    items.extend(projects_in_display_order().iter().map(|project| PaletteItem {
+        kind: ItemKind::Project,
+        title: project.name.to_string(),
+        keywords: project.tech_stack.iter().map(|t| t.to_string()).collect(),
+        msg: Msg::OpenRoute(Route::Project(project.slug.to_string())),
+    }));
+
+    items.extend(Preset::ALL.iter().map(|preset| PaletteItem {
+        kind: ItemKind::Action,
+        title: format!("Use {} keybindings", preset.name()),
+        keywords: vec!["keymap".to_string()],
+        msg: Msg::SetKeymap(*preset),
+    }));
+    items.push(PaletteItem {
+        kind: ItemKind::Action,
+        title: "Copy email address".to_string(),
+        keywords: vec!["contact".to_string(), "clipboard".to_string()],
+        msg: Msg::CopyText(content::email().to_string()),
+    });
+    items.push(PaletteItem {
+        kind: ItemKind::Action,
+        title: "Show keybindings".to_string(),
+        keywords: vec!["help".to_string()],
+        msg: Msg::ShowHelp,
+    });
+    items.push(PaletteItem {
+        kind: ItemKind::Action,
+        title: "Quit".to_string(),
+        keywords: vec!["exit".to_string()],
+        msg: Msg::Quit,
+    });
+
+    items
+}
+
+/// Items matching `query`, best first; an empty query lists everything
+pub fn search(query: &str) -> Vec<PaletteMatch> {
+    let mut matches: Vec<PaletteMatch> = index()
+        .iter()
+        .filter_map(|item| match_item(item, query))
+        .collect();
+    // Stable sort keeps index order (views, posts, projects, actions) on ties
+    matches.sort_by_key(|m| Reverse(m.score));
+    matches
+}
+
+fn match_item(item: &PaletteItem, query: &str) -> Option<PaletteMatch> {
+    if let Some((score, positions)) = fuzzy_match(query, &item.title) {
+        return Some(PaletteMatch {
+            item: item.clone(),
+            score,
+            positions,
+            keyword: None,
+        });
+    }
+
+    // Keyword hits rank below title hits of the same quality
+    item.keywords
+        .iter()
+        .filter_map(|kw| fuzzy_match(query, kw).map(|(score, _)| (score, kw)))
+        .max_by_key(|(score, _)| *score)
+        .map(|(score, kw)| PaletteMatch {
+            item: item.clone(),
+            score: score - 10,
+            positions: Vec::new(),
+            keyword: Some(kw.clone()),
+        })
+}
+
+/// Case-insensitive subsequence match of `query` in `text`
+///
+/// Returns a score (higher is better) and the char positions matched.
+/// Consecutive characters and matches at word starts score higher.
+pub fn fuzzy_match(query: &str, text: &str) -> Option<(i64, Vec<usize>)> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if query.is_empty() {
+        return Some((0, Vec::new()));
+    }
+
+    let chars: Vec<char> = text.chars().collect();
+    let mut positions = Vec::with_capacity(query.len());
+    let mut score = 0;
+    let mut qi = 0;
+
+    for (i, c) in chars.iter().enumerate() {
+        if qi == query.len() {
+            break;
+        }
+        if !c.to_lowercase().eq(query[qi].to_lowercase()) {
+            continue;
+        }
+
+        score += 1;
+        if positions.last().is_some_and(|last| last + 1 == i) {
+            score += 5;
+        }
+        if i == 0 || !chars[i - 1].is_alphanumeric() {
+            score += 8;
+        }
+        positions.push(i);
+        qi += 1;
+    }
+
+    if qi < query.len() {
+        return None;
+    }
+    // Prefer shorter titles when the match is otherwise equal
+    score -= (chars.len() / 8) as i64;
+    Some((score, positions))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_match_prefers_word_starts() {
+        let (tight, positions) = fuzzy_match("bl", "Blog").unwrap();
+        assert_eq!(positions, vec![0, 1]);
+        let (loose, _) = fuzzy_match("bl", "Tumbleweed").unwrap();
+        assert!(tight > loose);
+        assert!(fuzzy_match("xyz", "Blog").is_none());
+    }
+
+    #[test]
+    fn test_search_covers_posts_projects_and_keywords() {
+        let post = &POSTS[0];
+        let results = search(post.title);
+        assert_eq!(results[0].item.title, post.title);
+
+        // Tags match even when the title does not
+        let tagged = search("rspec");
+        assert_eq!(tagged[0].item.kind, ItemKind::Post);
+        assert_eq!(tagged[0].keyword.as_deref(), Some("RSpec"));
+
+        assert_eq!(search("").len(), index().len());
+    }
+
+    #[test]
+    fn test_copy_email_action() {
+        let results = search("copy email");
+        assert_eq!(results[0].item.kind, ItemKind::Action);
+        assert_eq!(
+            results[0].item.msg,
+            Msg::CopyText(content::email().to_string())
+        );
+    }
+}
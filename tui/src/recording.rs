@@ -0,0 +1,324 @@
+//! Session recordings in asciicast v2, for demo GIFs and for reproducing
+//! rendering bugs visitors report
+//!
+//! Recording is off unless `RECORD_DIR` is set. Each session goes to its own
+//! file in that directory, named by a random id so that nothing about the
+//! visitor ends up in the name, and only the newest `RECORD_KEEP` recordings
+//! are kept. Output is always recorded; with `RECORD_INPUT=1` the SSH server
+//! records the keys typed as well.
+//!
+//! Format: <https://docs.asciinema.org/manual/asciicast/v2/>
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use serde_json::json;
+
+/// Recordings kept when `RECORD_KEEP` isn't set
+pub const DEFAULT_KEEP: usize = 50;
+
+/// Where and what to record
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordingConfig {
+    pub dir: PathBuf,
+    /// Most recordings kept; older ones are removed as new ones start
+    pub keep: usize,
+    /// Whether the keys typed are recorded too
+    pub input: bool,
+}
+
+impl RecordingConfig {
+    /// Variables the config is read from, not passed on to child sessions
+    pub const ENV: [&str; 3] = ["RECORD_DIR", "RECORD_KEEP", "RECORD_INPUT"];
+
+    /// Config from the environment, or `None` when recording is off
+    pub fn from_env() -> Option<Self> {
+        Self::from_vars(|name| std::env::var(name).ok())
+    }
+
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        let dir = var("RECORD_DIR").filter(|dir| !dir.is_empty())?;
+        let keep = var("RECORD_KEEP")
+            .and_then(|keep| keep.parse().ok())
+            .unwrap_or(DEFAULT_KEEP);
+        Some(Self {
+            dir: PathBuf::from(dir),
+            keep: keep.max(1),
+            input: var("RECORD_INPUT").is_some_and(|input| input != "0"),
+        })
+    }
+
+    /// One-line notice telling visitors the session is recorded
+    pub fn notice(&self) -> String {
+        let what = if self.input {
+            "the screen and the keys you type"
+        } else {
+            "the screen"
+        };
+        format!("This session is recorded ({what}) for demos and debugging.")
+    }
+
+    /// [`Self::notice`] with how long recordings are kept, shown before the
+    /// session starts
+    pub fn banner(&self) -> String {
+        format!(
+            "{}\r\nRecordings are anonymous and only the latest {} are kept.\r\n",
+            self.notice(),
+            self.keep
+        )
+    }
+}
+
+/// An asciicast v2 file being written for one session
+pub struct Recorder {
+    file: BufWriter<File>,
+    path: PathBuf,
+    started: Instant,
+    input: bool,
+    /// Start of a UTF-8 sequence split across reads, per stream
+    pending_output: Vec<u8>,
+    pending_input: Vec<u8>,
+}
+
+impl Recorder {
+    /// Start recording a `width` x `height` terminal in a new file, removing
+    /// the oldest recordings beyond the config's limit
+    pub fn create(config: &RecordingConfig, width: u16, height: u16) -> io::Result<Self> {
+        fs::create_dir_all(&config.dir)?;
+        let path = config.dir.join(format!("{:016x}.cast", rand::random::<u64>()));
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = BufWriter::new(options.open(&path)?);
+        prune(&config.dir, config.keep, &path)?;
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs());
+        let header = json!({
+            "version": 2,
+            "width": width,
+            "height": height,
+            "timestamp": timestamp,
+        });
+        writeln!(file, "{header}")?;
+
+        Ok(Self {
+            file,
+            path,
+            started: Instant::now(),
+            input: config.input,
+            pending_output: Vec::new(),
+            pending_input: Vec::new(),
+        })
+    }
+
+    /// File the recording is written to
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Record bytes written to the terminal
+    pub fn output(&mut self, bytes: &[u8]) {
+        let text = decode(&mut self.pending_output, bytes);
+        self.event("o", &text);
+    }
+
+    /// Record bytes typed, if the config asks for input
+    pub fn input(&mut self, bytes: &[u8]) {
+        if self.input {
+            let text = decode(&mut self.pending_input, bytes);
+            self.event("i", &text);
+        }
+    }
+
+    /// Record a change of terminal size
+    pub fn resize(&mut self, width: u16, height: u16) {
+        self.event("r", &format!("{width}x{height}"));
+    }
+
+    /// Write an event line; a failing recording must not end the session,
+    /// so errors are dropped
+    fn event(&mut self, kind: &str, data: &str) {
+        if data.is_empty() {
+            return;
+        }
+        let time = self.started.elapsed().as_micros() as f64 / 1e6;
+        let _ = writeln!(self.file, "{}", json!([time, kind, data]));
+    }
+
+    /// Write out buffered events
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Remove the oldest `.cast` files in `dir` so that, with `current`, at most
+/// `keep` are left
+fn prune(dir: &Path, keep: usize, current: &Path) -> io::Result<()> {
+    let mut recordings: Vec<(SystemTime, PathBuf)> = fs::read_dir(dir)?
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let is_cast = path.extension().is_some_and(|ext| ext == "cast");
+            let modified = path.metadata().and_then(|meta| meta.modified()).ok()?;
+            (is_cast && path != current).then_some((modified, path))
+        })
+        .collect();
+    recordings.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+    for (_, path) in recordings.into_iter().skip(keep.saturating_sub(1)) {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// Text of `bytes` after whatever was left `pending` by the previous call,
+/// keeping back an incomplete UTF-8 sequence at the end; invalid bytes
+/// become U+FFFD
+fn decode(pending: &mut Vec<u8>, bytes: &[u8]) -> String {
+    pending.extend_from_slice(bytes);
+    let mut text = String::new();
+    let mut rest: &[u8] = pending;
+    loop {
+        match std::str::from_utf8(rest) {
+            Ok(valid) => {
+                text.push_str(valid);
+                rest = &[];
+                break;
+            }
+            Err(err) => {
+                let (valid, after) = rest.split_at(err.valid_up_to());
+                text.push_str(&String::from_utf8_lossy(valid));
+                match err.error_len() {
+                    Some(len) => {
+                        text.push(char::REPLACEMENT_CHARACTER);
+                        rest = &after[len..];
+                    }
+                    // The sequence continues in the next read
+                    None => {
+                        rest = after;
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    *pending = rest.to_vec();
+    text
+}
+
+/// A writer recording everything written through it, e.g. the stdio
+/// runtime's stdout
+pub struct RecordedWriter<W> {
+    inner: W,
+    recorder: Option<Recorder>,
+    /// Terminal size last recorded, checked once per frame
+    size: Option<(u16, u16)>,
+    frame_start: bool,
+}
+
+impl<W> RecordedWriter<W> {
+    pub fn new(inner: W, recorder: Option<Recorder>) -> Self {
+        Self {
+            inner,
+            recorder,
+            size: None,
+            frame_start: true,
+        }
+    }
+}
+
+impl<W: Write> Write for RecordedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        if let Some(recorder) = &mut self.recorder {
+            // Draws are flushed once per frame, so a resize shows up at the
+            // first write after a flush
+            if std::mem::take(&mut self.frame_start) {
+                let size = crossterm::terminal::size().ok();
+                if size != self.size {
+                    if let (Some((width, height)), Some(_)) = (size, self.size) {
+                        recorder.resize(width, height);
+                    }
+                    self.size = size;
+                }
+            }
+            recorder.output(&buf[..written]);
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.frame_start = true;
+        if let Some(recorder) = &mut self.recorder {
+            let _ = recorder.flush();
+        }
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recording_is_opt_in() {
+        assert_eq!(RecordingConfig::from_vars(|_| None), None);
+
+        let config = RecordingConfig::from_vars(|name| match name {
+            "RECORD_DIR" => Some("/tmp/casts".to_string()),
+            "RECORD_KEEP" => Some("0".to_string()),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!(config.keep, 1);
+        assert!(!config.input);
+        assert!(config.banner().contains("recorded (the screen)"));
+        // The notice fits on the status bar's single line
+        assert!(config.banner().starts_with(&config.notice()));
+        assert!(!config.notice().contains('\n'));
+    }
+
+    #[test]
+    fn test_split_utf8_is_kept_for_the_next_read() {
+        let mut pending = Vec::new();
+        let bytes = "a│b".as_bytes();
+        assert_eq!(decode(&mut pending, &bytes[..2]), "a");
+        assert_eq!(decode(&mut pending, &bytes[2..]), "│b");
+        assert!(pending.is_empty());
+        assert_eq!(decode(&mut pending, b"\xffx"), "\u{fffd}x");
+    }
+
+    #[test]
+    fn test_recordings_are_pruned_to_the_limit() {
+        let dir = std::env::temp_dir().join(format!("whoami-casts-{}", std::process::id()));
+        let config = RecordingConfig {
+            dir: dir.clone(),
+            keep: 2,
+            input: true,
+        };
+        let mut paths = Vec::new();
+        for _ in 0..3 {
+            let mut recorder = Recorder::create(&config, 80, 24).unwrap();
+            recorder.output(b"\x1b[1mhi");
+            recorder.input(b"j");
+            recorder.flush().unwrap();
+            paths.push(recorder.path().to_path_buf());
+            // Modification times must differ for the oldest to be known
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+        assert!(!paths[0].exists() && paths[1].exists() && paths[2].exists());
+
+        let cast = fs::read_to_string(&paths[2]).unwrap();
+        let lines: Vec<serde_json::Value> =
+            cast.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(lines[0]["version"], 2);
+        assert_eq!(lines[0]["width"], 80);
+        assert_eq!(lines[1][1], "o");
+        assert_eq!(lines[1][2], "\x1b[1mhi");
+        assert_eq!(lines[2][1], "i");
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,412 @@
+//! Headless rendering: draw a view once into an off-screen buffer and write
+//! it out as plain text, ANSI escapes or HTML
+//!
+//! Served by `tui render --view <route>` and `ssh host render --view <route>`,
+//! for previews and READMEs. The view is drawn by the same code as the
+//! interactive runtime, but the terminal is never touched: no raw mode, no
+//! alternate screen.
+
+use std::fmt::Write;
+
+use anyhow::{anyhow, bail, Context, Result};
+use ratatui::backend::TestBackend;
+use ratatui::buffer::{Buffer, Cell};
+use ratatui::style::{Color, Modifier};
+use ratatui::Terminal;
+use tuirealm::{Application, EventListenerCfg};
+use unicode_width::UnicodeWidthStr;
+
+use crate::export::escape;
+use crate::model::Model;
+use crate::route::Route;
+use crate::runtime::draw;
+use crate::ui::{mount_view, restore_state, App};
+
+/// Usage line for a malformed render command
+const USAGE: &str =
+    "usage: render --view <route> [--width <columns>] [--height <rows>] [--plain|--ansi|--html]";
+
+/// Largest width or height accepted, so a command can't ask for a huge buffer
+const MAX_SIZE: u16 = 500;
+
+/// How a rendered screen is written out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Output {
+    /// Characters only, trailing spaces trimmed
+    Plain,
+    /// Characters with SGR escapes for colors and modifiers
+    Ansi,
+    /// A standalone HTML page with inline styles
+    Html,
+}
+
+/// The arguments of a `render` command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderRequest {
+    pub route: Route,
+    pub width: u16,
+    pub height: u16,
+    pub output: Output,
+}
+
+impl RenderRequest {
+    /// Parse the arguments after `render`, e.g. `--view resume --width 100`
+    ///
+    /// The view defaults to the home screen, the size to 80x24 and the
+    /// output to ANSI.
+    pub fn parse<'a>(args: impl IntoIterator<Item = &'a str>) -> Result<Self> {
+        let mut request = Self {
+            route: Route::Home,
+            width: 80,
+            height: 24,
+            output: Output::Ansi,
+        };
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let (name, inline) = match arg.split_once('=') {
+                Some((name, value)) => (name, Some(value)),
+                None => (arg, None),
+            };
+            let mut value = || inline.or_else(|| args.next()).context(USAGE);
+            match name {
+                "--view" => request.route = value()?.parse()?,
+                "--width" => request.width = size(value()?)?,
+                "--height" => request.height = size(value()?)?,
+                "--plain" => request.output = Output::Plain,
+                "--ansi" => request.output = Output::Ansi,
+                "--html" => request.output = Output::Html,
+                _ => bail!("unknown argument `{arg}` ({USAGE})"),
+            }
+        }
+        Ok(request)
+    }
+
+    /// Draw the view and write it out in the requested format
+    pub fn render(&self) -> Result<String> {
+        let buffer = render(&self.route, self.width, self.height)?;
+        Ok(match self.output {
+            Output::Plain => to_plain(&buffer),
+            Output::Ansi => to_ansi(&buffer),
+            Output::Html => to_html(&buffer, &self.route.to_string()),
+        })
+    }
+}
+
+/// Request made by an SSH exec command such as `render --view resume`
+///
+/// `None` if the command isn't a render.
+pub fn render_command(command: &str) -> Option<Result<RenderRequest>> {
+    let mut words = command.split_whitespace();
+    if words.next() != Some("render") {
+        return None;
+    }
+    Some(RenderRequest::parse(words))
+}
+
+/// A width or height argument
+fn size(value: &str) -> Result<u16> {
+    match value.parse::<u16>() {
+        Ok(size @ 1..=MAX_SIZE) => Ok(size),
+        _ => bail!("size must be between 1 and {MAX_SIZE}, got `{value}`"),
+    }
+}
+
+/// Draw `route` once at `width` x `height`, as a session opening it would
+pub fn render(route: &Route, width: u16, height: u16) -> Result<Buffer> {
+    let mut app: App = Application::init(EventListenerCfg::default());
+    let entry = route.entry();
+    mount_view(&mut app, entry.view).map_err(|err| anyhow!("{err}"))?;
+    if let Some(state) = entry.state {
+        restore_state(&mut app, entry.view, state).map_err(|err| anyhow!("{err}"))?;
+    }
+
+    let model = Model::new();
+    let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+    terminal.draw(|frame| draw(frame, &mut app, &model, entry.view, None))?;
+    Ok(terminal.backend().buffer().clone())
+}
+
+/// Cells of each row, without those hidden under a wide character
+fn rows(buffer: &Buffer) -> Vec<Vec<&Cell>> {
+    let area = buffer.area;
+    (area.top()..area.bottom())
+        .map(|y| {
+            let mut cells = Vec::new();
+            let mut x = area.left();
+            while x < area.right() {
+                let cell = &buffer[(x, y)];
+                cells.push(cell);
+                x += cell.symbol().width().max(1) as u16;
+            }
+            cells
+        })
+        .collect()
+}
+
+/// The screen as text, one line per row with trailing spaces trimmed
+pub fn to_plain(buffer: &Buffer) -> String {
+    let mut out = String::new();
+    for row in rows(buffer) {
+        let line: String = row.iter().map(|cell| cell.symbol()).collect();
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+/// The screen with SGR escapes, each row ending with a reset
+pub fn to_ansi(buffer: &Buffer) -> String {
+    let mut out = String::new();
+    for row in rows(buffer) {
+        let mut style = (Color::Reset, Color::Reset, Modifier::empty());
+        for cell in row {
+            let cell_style = (cell.fg, cell.bg, cell.modifier);
+            if cell_style != style {
+                out.push_str(&sgr(cell));
+                style = cell_style;
+            }
+            out.push_str(cell.symbol());
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+/// Modifiers with their SGR parameter
+const SGR_MODIFIERS: [(Modifier, u8); 9] = [
+    (Modifier::BOLD, 1),
+    (Modifier::DIM, 2),
+    (Modifier::ITALIC, 3),
+    (Modifier::UNDERLINED, 4),
+    (Modifier::SLOW_BLINK, 5),
+    (Modifier::RAPID_BLINK, 6),
+    (Modifier::REVERSED, 7),
+    (Modifier::HIDDEN, 8),
+    (Modifier::CROSSED_OUT, 9),
+];
+
+/// Escape switching from any style to the style of `cell`
+fn sgr(cell: &Cell) -> String {
+    let mut params = String::from("0");
+    for (modifier, param) in SGR_MODIFIERS {
+        if cell.modifier.contains(modifier) {
+            let _ = write!(params, ";{param}");
+        }
+    }
+    for (color, base) in [(cell.fg, 30), (cell.bg, 40)] {
+        let _ = match color {
+            Color::Reset => Ok(()),
+            Color::Rgb(r, g, b) => write!(params, ";{};2;{r};{g};{b}", base + 8),
+            Color::Indexed(i) => write!(params, ";{};5;{i}", base + 8),
+            named => {
+                let index = named_index(named);
+                // The bright colors have their own range, 90-97 and 100-107
+                let code = if index < 8 { base + index } else { base + 60 + index - 8 };
+                write!(params, ";{code}")
+            }
+        };
+    }
+    format!("\x1b[{params}m")
+}
+
+/// Index of a named color in the 16-color palette
+fn named_index(color: Color) -> u8 {
+    match color {
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::Gray => 7,
+        Color::DarkGray => 8,
+        Color::LightRed => 9,
+        Color::LightGreen => 10,
+        Color::LightYellow => 11,
+        Color::LightBlue => 12,
+        Color::LightMagenta => 13,
+        Color::LightCyan => 14,
+        Color::White => 15,
+        Color::Reset | Color::Rgb(..) | Color::Indexed(_) => 7,
+    }
+}
+
+/// xterm's default 16-color palette
+const PALETTE: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// CSS color for `color`, or `None` for the terminal default
+fn css_color(color: Color) -> Option<String> {
+    let (r, g, b) = match color {
+        Color::Reset => return None,
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Indexed(i @ 0..=15) => PALETTE[i as usize],
+        // 6x6x6 color cube
+        Color::Indexed(i @ 16..=231) => {
+            let level = |n: u8| if n == 0 { 0 } else { 55 + n * 40 };
+            let i = i - 16;
+            (level(i / 36), level(i / 6 % 6), level(i % 6))
+        }
+        // Grayscale ramp
+        Color::Indexed(i) => {
+            let gray = 8 + (i - 232) * 10;
+            (gray, gray, gray)
+        }
+        named => PALETTE[named_index(named) as usize],
+    };
+    Some(format!("#{r:02x}{g:02x}{b:02x}"))
+}
+
+/// Inline CSS for the style of `cell`
+fn css(cell: &Cell) -> String {
+    let (mut fg, mut bg) = (css_color(cell.fg), css_color(cell.bg));
+    if cell.modifier.contains(Modifier::REVERSED) {
+        (fg, bg) = (
+            Some(bg.unwrap_or_else(|| PAGE_BACKGROUND.to_string())),
+            Some(fg.unwrap_or_else(|| PAGE_FOREGROUND.to_string())),
+        );
+    }
+    let mut css = String::new();
+    if let Some(fg) = fg {
+        let _ = write!(css, "color:{fg};");
+    }
+    if let Some(bg) = bg {
+        let _ = write!(css, "background:{bg};");
+    }
+    for (modifier, property) in [
+        (Modifier::BOLD, "font-weight:bold;"),
+        (Modifier::DIM, "opacity:.6;"),
+        (Modifier::ITALIC, "font-style:italic;"),
+        (Modifier::UNDERLINED, "text-decoration:underline;"),
+        (Modifier::CROSSED_OUT, "text-decoration:line-through;"),
+        (Modifier::HIDDEN, "visibility:hidden;"),
+    ] {
+        if cell.modifier.contains(modifier) {
+            css.push_str(property);
+        }
+    }
+    css
+}
+
+/// Page colors: the theme's background and text
+const PAGE_BACKGROUND: &str = "#1a1b26";
+const PAGE_FOREGROUND: &str = "#c0caf5";
+
+/// The screen as a standalone HTML page, each run of cells in one style a
+/// `<span>`
+pub fn to_html(buffer: &Buffer, title: &str) -> String {
+    let mut body = String::new();
+    for row in rows(buffer) {
+        let mut run = String::new();
+        let mut style = String::new();
+        for cell in row {
+            let cell_style = css(cell);
+            if cell_style != style {
+                push_span(&mut body, &style, &run);
+                run.clear();
+                style = cell_style;
+            }
+            run.push_str(cell.symbol());
+        }
+        // Trailing spaces in the default style are left out
+        let run = if style.is_empty() { run.trim_end_matches(' ') } else { &run };
+        push_span(&mut body, &style, run);
+        body.push('\n');
+    }
+
+    format!(
+        "<!DOCTYPE html>\n\
+         <html lang=\"en\">\n\
+         <head>\n\
+         <meta charset=\"utf-8\">\n\
+         <title>{}</title>\n\
+         <style>{HTML_STYLE}</style>\n\
+         </head>\n\
+         <body>\n\
+         <pre>{body}</pre>\n\
+         </body>\n\
+         </html>\n",
+        escape(title)
+    )
+}
+
+const HTML_STYLE: &str = "\
+body{margin:0;background:#1a1b26;color:#c0caf5}\
+pre{margin:0;padding:1rem;font:14px/1.2 ui-monospace,'SF Mono',Menlo,Consolas,monospace}";
+
+fn push_span(body: &mut String, style: &str, text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    if style.is_empty() {
+        body.push_str(&escape(text));
+    } else {
+        let _ = write!(body, "<span style=\"{style}\">{}</span>", escape(text));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_arguments() {
+        let request = RenderRequest::parse(["--view", "resume", "--width=100", "--plain"]).unwrap();
+        assert_eq!(
+            request,
+            RenderRequest {
+                route: Route::Resume,
+                width: 100,
+                height: 24,
+                output: Output::Plain,
+            }
+        );
+        assert!(RenderRequest::parse(["--width", "0"]).is_err());
+        assert!(RenderRequest::parse(["--width", "100000"]).is_err());
+        assert!(RenderRequest::parse(["--view"]).is_err());
+        assert!(RenderRequest::parse(["--view", "/nowhere"]).is_err());
+        assert!(RenderRequest::parse(["--color"]).is_err());
+
+        assert!(render_command("resume --format pdf").is_none());
+        assert_eq!(render_command("render --html").unwrap().unwrap().output, Output::Html);
+    }
+
+    #[test]
+    fn test_screen_is_serialized_in_every_format() {
+        let request = RenderRequest::parse(["--view", "/resume", "--width", "60", "--height", "15"]);
+        let buffer = render(&request.unwrap().route, 60, 15).unwrap();
+
+        let plain = to_plain(&buffer);
+        assert_eq!(plain.lines().count(), 15);
+        assert!(plain.starts_with("Home › 📄 Resume\n"));
+        assert!(plain.lines().all(|line| line.width() <= 60 && !line.ends_with(' ')));
+
+        let ansi = to_ansi(&buffer);
+        assert_eq!(ansi.lines().count(), 15);
+        // Tokyo Night blue, bold, for the title
+        assert!(ansi.contains("\x1b[0;1;38;2;122;162;247m"));
+        assert!(ansi.lines().all(|line| line.ends_with("\x1b[0m")));
+
+        let html = to_html(&buffer, "/resume");
+        assert!(html.contains("<title>/resume</title>"));
+        assert!(html.contains("<span style=\"color:#7aa2f7;font-weight:bold;\">"));
+        assert!(!html.contains('\x1b'));
+    }
+}
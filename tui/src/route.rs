@@ -0,0 +1,160 @@
+//! URL-style routes for deep links, e.g. `/blog/<slug>` or `/projects/<slug>`
+//!
+//! Routes are parsed from `tui --open <path>`, an SSH exec command
+//! (`ssh -t host /blog/my-post`) or an SSH username (`ssh blog-my-post@host`).
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Error};
+
+use crate::components::{open_post_state, open_project_state};
+use crate::content::{projects_in_display_order, site_url, POSTS};
+use crate::history::Entry;
+use crate::msg::ViewId;
+
+/// An addressable place in the app
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Route {
+    Home,
+    Resume,
+    Blog,
+    BlogPost(String),
+    Projects,
+    Project(String),
+    Contact,
+    Settings,
+}
+
+impl Route {
+    /// Route named by an SSH username such as `blog-my-post` or `resume`
+    ///
+    /// Any other username (e.g. `guest`) has no route and opens normally.
+    pub fn from_ssh_user(user: &str) -> Option<Self> {
+        let path = match user.split_once('-') {
+            Some((section, slug)) => format!("{section}/{slug}"),
+            None => user.to_string(),
+        };
+        path.parse().ok()
+    }
+
+    /// View the route opens
+    pub fn view(&self) -> ViewId {
+        match self {
+            Route::Home => ViewId::Home,
+            Route::Resume => ViewId::Resume,
+            Route::Blog | Route::BlogPost(_) => ViewId::Blog,
+            Route::Projects | Route::Project(_) => ViewId::Projects,
+            Route::Contact => ViewId::Contact,
+            Route::Settings => ViewId::Settings,
+        }
+    }
+
+    /// Address of the same page on the website
+    pub fn web_url(&self) -> String {
+        format!("{}{self}", site_url())
+    }
+
+    /// History entry that opens the route, with the post or project selected
+    pub fn entry(&self) -> Entry {
+        let state = match self {
+            Route::BlogPost(slug) => post_index(slug).map(open_post_state),
+            Route::Project(slug) => project_index(slug).map(open_project_state),
+            _ => None,
+        };
+        Entry {
+            view: self.view(),
+            state,
+        }
+    }
+}
+
+impl FromStr for Route {
+    type Err = Error;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        let segments: Vec<&str> = path
+            .trim()
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .collect();
+
+        let route = match segments.as_slice() {
+            [] | ["home"] => Route::Home,
+            ["resume"] => Route::Resume,
+            ["blog"] => Route::Blog,
+            ["blog", slug] => {
+                post_index(slug).ok_or_else(|| anyhow!("no blog post named `{slug}`"))?;
+                Route::BlogPost(slug.to_string())
+            }
+            ["projects"] => Route::Projects,
+            ["projects" | "project", slug] => {
+                project_index(slug).ok_or_else(|| anyhow!("no project named `{slug}`"))?;
+                Route::Project(slug.to_string())
+            }
+            ["contact"] => Route::Contact,
+            ["settings"] => Route::Settings,
+            _ => bail!("unknown route `{}`", path.trim()),
+        };
+        Ok(route)
+    }
+}
+
+impl fmt::Display for Route {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Route::Home => write!(f, "/"),
+            Route::Resume => write!(f, "/resume"),
+            Route::Blog => write!(f, "/blog"),
+            Route::BlogPost(slug) => write!(f, "/blog/{slug}"),
+            Route::Projects => write!(f, "/projects"),
+            Route::Project(slug) => write!(f, "/projects/{slug}"),
+            Route::Contact => write!(f, "/contact"),
+            Route::Settings => write!(f, "/settings"),
+        }
+    }
+}
+
+/// Position of a post in the blog list
+fn post_index(slug: &str) -> Option<usize> {
+    POSTS.iter().position(|p| p.slug == slug)
+}
+
+/// Position of a project in the projects list
+fn project_index(slug: &str) -> Option<usize> {
+    projects_in_display_order()
+        .iter()
+        .position(|p| p.slug == slug)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_display_round_trip() {
+        let slug = POSTS[0].slug;
+        let route: Route = format!("/blog/{slug}/").parse().unwrap();
+        assert_eq!(route, Route::BlogPost(slug.to_string()));
+        assert_eq!(route.to_string(), format!("/blog/{slug}"));
+        assert_eq!(route.entry().view, ViewId::Blog);
+        assert!(route.entry().state.is_some());
+        assert_eq!(route.web_url(), format!("{}/blog/{slug}", site_url()));
+
+        assert_eq!("".parse::<Route>().unwrap(), Route::Home);
+        assert_eq!("resume".parse::<Route>().unwrap(), Route::Resume);
+        assert!("/blog/no-such-post".parse::<Route>().is_err());
+        assert!("/admin".parse::<Route>().is_err());
+    }
+
+    #[test]
+    fn test_ssh_username_routes() {
+        let slug = POSTS[0].slug;
+        assert_eq!(
+            Route::from_ssh_user(&format!("blog-{slug}")),
+            Some(Route::BlogPost(slug.to_string()))
+        );
+        assert_eq!(Route::from_ssh_user("projects"), Some(Route::Projects));
+        assert_eq!(Route::from_ssh_user("guest"), None);
+    }
+}
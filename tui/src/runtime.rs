@@ -1,21 +1,37 @@
 //! Shared runtime for running the interactive TUI.
 
 use std::io;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use ratatui::Terminal;
+use ratatui::{Frame, Terminal};
 use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Constraint, Layout};
+use ratatui::text::Span;
 use tuirealm::application::PollStrategy;
 use tuirealm::{Application, EventListenerCfg, Update};
 
+use crate::content;
+use crate::history::Entry;
+use crate::links;
 use crate::model::Model;
 use crate::msg::{Msg, ViewId};
-use crate::ui::{App, mount_view};
+use crate::recording::{RecordedWriter, Recorder, RecordingConfig};
+use crate::route::Route;
+use crate::styles;
+use crate::ui::{
+    App, close_overlay, find_in_view, mount_overlay, mount_view, overlay_area, restore_state,
+    snapshot,
+};
+use crate::widgets::{render_too_small, Breakpoint, Severity, StatusBar};
 
 /// Run the portfolio TUI using process stdio.
-pub fn run_stdio_tui() -> io::Result<()> {
+///
+/// With a `start` route the loading screen is skipped and the route opens
+/// directly, e.g. a shared link to a blog post.
+pub fn run_stdio_tui(start: Option<Route>) -> io::Result<()> {
+    let (recorder, notice) = start_recording();
     crossterm::terminal::enable_raw_mode()?;
-    let mut stdout = io::stdout();
+    let mut stdout = RecordedWriter::new(io::stdout(), recorder);
     crossterm::execute!(
         stdout,
         crossterm::terminal::EnterAlternateScreen,
@@ -24,7 +40,7 @@ pub fn run_stdio_tui() -> io::Result<()> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let result = run_app(&mut terminal);
+    let result = run_app(&mut terminal, start, notice);
 
     crossterm::terminal::disable_raw_mode()?;
     crossterm::execute!(
@@ -41,10 +57,14 @@ pub fn run_stdio_tui() -> io::Result<()> {
     Ok(())
 }
 
-fn run_app<B: ratatui::backend::Backend>(
+fn run_app<B: ratatui::backend::Backend + io::Write>(
     terminal: &mut Terminal<B>,
+    start: Option<Route>,
+    notice: Option<(Severity, String)>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let mut model = Model::new();
+    model.idle_timeout = env_secs("SSH_IDLE_TIMEOUT_SECS");
+    model.max_session = env_secs("SSH_MAX_SESSION_SECS");
 
     let mut app: App = Application::init(
         EventListenerCfg::default()
@@ -53,37 +73,205 @@ fn run_app<B: ratatui::backend::Backend>(
             .tick_interval(Duration::from_millis(50)),
     );
 
-    mount_view(&mut app, ViewId::Loading)?;
-    let mut current_view = ViewId::Loading;
+    let start = start.map(|route| route.entry());
+    let mut current_view = start.as_ref().map_or(ViewId::Loading, |entry| entry.view);
+    mount_view(&mut app, current_view)?;
+    if let Some(state) = start.and_then(|entry| entry.state) {
+        restore_state(&mut app, current_view, state)?;
+    }
+    model.current_view = current_view;
+    let mut overlay: Option<ViewId> = None;
+
+    let hyperlinks = links::hyperlinks_supported();
+    links::set_hyperlinks(hyperlinks);
+    let mut drawn_links = Vec::new();
+    let mut notice = notice;
 
     while !model.quit {
-        terminal.draw(|frame| {
-            let area = frame.area();
-            app.view(&current_view, frame, area);
-        })?;
-
-        let messages = match app.tick(PollStrategy::UpTo(3)) {
-            Ok(msgs) => msgs,
-            Err(_) => vec![],
-        };
+        // Announced once the status bar is up, after the splash screen
+        if let Some((severity, text)) = notice.take_if(|_| current_view != ViewId::Loading) {
+            model.notify(severity, text);
+        }
+        let frame = terminal.draw(|frame| draw(frame, &mut app, &model, current_view, overlay))?;
+
+        // Links redrawn by ratatui lose their hyperlink, so redraw them all
+        // whenever any of them moved or changed
+        if hyperlinks {
+            let cells = links::hyperlink_cells(frame.buffer);
+            if cells != drawn_links {
+                terminal
+                    .backend_mut()
+                    .draw(cells.iter().map(|(x, y, cell)| (*x, *y, cell)))?;
+                ratatui::backend::Backend::flush(terminal.backend_mut())?;
+                drawn_links = cells;
+            }
+        }
+
+        let messages = app.tick(PollStrategy::UpTo(3)).unwrap_or_default();
 
         for msg in messages {
-            let new_view = match &msg {
-                Msg::NavigateTo(view_id) => Some(*view_id),
-                Msg::GoBack => Some(ViewId::Home),
+            if msg != Msg::Tick {
+                model.last_input = Instant::now();
+            }
+
+            let target = match &msg {
+                Msg::NavigateTo(_)
+                | Msg::OpenRoute(_)
+                | Msg::OpenMatch(..)
+                | Msg::GoBack
+                | Msg::GoForward => {
+                    let from = Entry {
+                        view: current_view,
+                        state: snapshot(&app, current_view),
+                    };
+                    model.navigate(&msg, from)
+                }
                 _ => None,
             };
 
+            // While an overlay is open it receives all input, so any message
+            // other than `Msg::None` means it is done (closed or item chosen)
+            if let Some(open) = overlay.take_if(|_| msg != Msg::None) {
+                close_overlay(&mut app, open, current_view)?;
+            }
+
+            let open = match &msg {
+                Msg::ShowHelp => Some(ViewId::Help),
+                Msg::ShowPalette => Some(ViewId::Palette),
+                _ => None,
+            };
+            if let Some(view_id) = open {
+                mount_overlay(&mut app, view_id, current_view)?;
+                overlay = Some(view_id);
+            }
+
+            let status = match &msg {
+                Msg::CopyText(text) => Some(copy(terminal.backend_mut(), text)),
+                Msg::OpenUrl(url) => match links::open(url) {
+                    Ok(()) => Some((Severity::Info, format!("Opening {url}"))),
+                    // A browser can't be started for a remote visitor;
+                    // copying the URL is the next best thing
+                    Err(err) => Some(match copy(terminal.backend_mut(), url) {
+                        (Severity::Info, copied) => (Severity::Warning, format!("{copied} ({err})")),
+                        failed => failed,
+                    }),
+                },
+                _ => None,
+            };
+
+            let find = match &msg {
+                Msg::OpenMatch(_, find) => Some(find.clone()),
+                _ => None,
+            };
             model.update(Some(msg));
+            if let Some((severity, text)) = status {
+                model.notify(severity, text);
+            }
 
-            if let Some(view_id) = new_view {
-                if view_id != current_view {
-                    mount_view(&mut app, view_id)?;
-                    current_view = view_id;
+            if let Some(entry) = target {
+                mount_view(&mut app, entry.view)?;
+                if let Some(state) = entry.state {
+                    restore_state(&mut app, entry.view, state)?;
                 }
+                if let Some(find) = &find {
+                    find_in_view(&mut app, entry.view, find)?;
+                }
+                current_view = entry.view;
+                overlay = None;
             }
         }
     }
 
     Ok(())
 }
+
+/// Recorder for the session if `RECORD_DIR` is set, with the notice to show
+/// in the status bar
+///
+/// Only the output is recorded: keys are read by the event listener, not
+/// from a stream the recorder could wrap.
+fn start_recording() -> (Option<Recorder>, Option<(Severity, String)>) {
+    let Some(config) = RecordingConfig::from_env() else {
+        return (None, None);
+    };
+    let config = RecordingConfig {
+        input: false,
+        ..config
+    };
+    let (width, height) = crossterm::terminal::size().unwrap_or((80, 24));
+    match Recorder::create(&config, width, height) {
+        Ok(recorder) => (Some(recorder), Some((Severity::Warning, config.notice()))),
+        Err(err) => (None, Some((Severity::Error, format!("Not recording: {err}")))),
+    }
+}
+
+/// Draw `view`, the overlay open over it and the status bar
+pub(crate) fn draw(
+    frame: &mut Frame,
+    app: &mut App,
+    model: &Model,
+    view: ViewId,
+    overlay: Option<ViewId>,
+) {
+    let mut area = frame.area();
+    links::clear();
+    if Breakpoint::of(area) == Breakpoint::TooSmall {
+        render_too_small(frame, area);
+        return;
+    }
+    // The splash screen has the whole terminal to itself
+    if view != ViewId::Loading && area.height > 1 {
+        let [view_area, bar_area] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(area);
+        status_bar(model).render(frame, bar_area);
+        area = view_area;
+    }
+    app.view(&view, frame, area);
+    if let Some(overlay) = overlay {
+        app.view(&overlay, frame, overlay_area(area));
+    }
+}
+
+/// Put `text` on the clipboard, returning the status to report
+fn copy(out: &mut impl io::Write, text: &str) -> (Severity, String) {
+    match links::copy(out, text) {
+        Ok(()) if text.lines().count() > 1 => {
+            (Severity::Info, format!("Copied {} lines", text.lines().count()))
+        }
+        Ok(()) => (Severity::Info, format!("Copied {text}")),
+        Err(err) => (Severity::Error, format!("Copy failed: {err}")),
+    }
+}
+
+/// Status bar with the session clock, time until the server closes the
+/// session and the content version
+fn status_bar(model: &Model) -> StatusBar<'_> {
+    let mut bar = StatusBar::new()
+        .message(model.status())
+        .item(Span::raw(format!("⏱ {}", clock(model.started_at.elapsed()))));
+    if let Some(left) = model.time_left() {
+        let style = if left < Duration::from_secs(30) {
+            styles::warning()
+        } else {
+            styles::muted()
+        };
+        bar = bar.item(Span::styled(format!("closes in {}", clock(left)), style));
+    }
+    bar.item(Span::raw(format!("content {:07x}", content::revision() >> 36)))
+}
+
+/// `m:ss`, or `h:mm:ss` past an hour
+fn clock(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match secs / 3600 {
+        0 => format!("{}:{:02}", secs / 60, secs % 60),
+        hours => format!("{hours}:{:02}:{:02}", secs / 60 % 60, secs % 60),
+    }
+}
+
+/// Whole seconds from an environment variable, e.g. a session limit passed
+/// down by the SSH server
+fn env_secs(name: &str) -> Option<Duration> {
+    let secs: u64 = std::env::var(name).ok()?.parse().ok()?;
+    (secs > 0).then(|| Duration::from_secs(secs))
+}
@@ -0,0 +1,338 @@
+//! Full-text search across post bodies, project descriptions and the resume
+//!
+//! Queries are matched as a case-insensitive phrase in the text as the views
+//! draw it, without Markdown syntax or link URLs. Each hit remembers which
+//! occurrence of the phrase it is within its document, counted the way
+//! [`crate::widgets::FindState`] counts them, so the view opened from a
+//! result can scroll to that same occurrence.
+
+use std::cmp::Reverse;
+use std::ops::Range;
+
+use tuirealm::props::{AttrValue, Attribute, PropPayload, PropValue};
+
+use crate::content::{projects_in_display_order, POSTS, RESUME};
+use crate::route::Route;
+use crate::widgets::markdown_text;
+
+/// Characters of context shown before a match in a snippet
+const SNIPPET_BEFORE: usize = 24;
+
+/// Maximum snippet length in characters
+const SNIPPET_LEN: usize = 72;
+
+/// Hits listed per document before the rest are dropped
+const MAX_HITS_PER_DOC: usize = 5;
+
+/// Extra score for documents whose title contains the query
+const TITLE_BONUS: usize = 20;
+
+/// A phrase to highlight in a view, and which occurrence to scroll to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Find {
+    pub query: String,
+    /// Zero-based occurrence of `query` within the document
+    pub occurrence: usize,
+}
+
+/// Component attribute that starts an in-page find
+pub const FIND_ATTR: Attribute = Attribute::Custom("find");
+
+impl Find {
+    /// Attribute value for [`FIND_ATTR`]
+    pub fn to_attr(&self) -> AttrValue {
+        AttrValue::Payload(PropPayload::Tup2((
+            PropValue::Str(self.query.clone()),
+            PropValue::Usize(self.occurrence),
+        )))
+    }
+
+    /// Parse a [`FIND_ATTR`] value
+    pub fn from_attr(value: AttrValue) -> Option<Self> {
+        match value {
+            AttrValue::Payload(PropPayload::Tup2((
+                PropValue::Str(query),
+                PropValue::Usize(occurrence),
+            ))) => Some(Self { query, occurrence }),
+            _ => None,
+        }
+    }
+}
+
+/// Kind of document a hit comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    Resume,
+    Post,
+    Project,
+}
+
+impl Source {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Source::Resume => "resume",
+            Source::Post => "post",
+            Source::Project => "project",
+        }
+    }
+}
+
+/// A single match with a snippet of surrounding text
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub source: Source,
+    /// Title of the document the match is in
+    pub title: String,
+    pub route: Route,
+    pub snippet: String,
+    /// Char range of the match within `snippet`
+    pub highlight: Range<usize>,
+    /// Match to scroll to, or `None` when only the title matched
+    pub find: Option<Find>,
+}
+
+/// A searchable document
+struct Document {
+    source: Source,
+    title: String,
+    route: Route,
+    /// Text of the document's passages as drawn, in order
+    passages: Vec<String>,
+}
+
+fn documents() -> Vec<Document> {
+    let markdown = |body: &str| body.lines().map(markdown_text).collect();
+    let mut docs = vec![Document {
+        source: Source::Resume,
+        title: "Resume".to_string(),
+        route: Route::Resume,
+        passages: markdown(&RESUME),
+    }];
+    docs.extend(POSTS.iter().map(|post| Document {
+        source: Source::Post,
+        title: post.title.to_string(),
+        route: Route::BlogPost(post.slug.to_string()),
+        passages: post_passages(post.content),
+    }));
+    docs.extend(projects_in_display_order().iter().map(|project| Document {
+        source: Source::Project,
+        title: project.name.to_string(),
+        route: Route::Project(project.slug.to_string()),
+        passages: markdown(project.description),
+    }));
+    docs
+}
+
+/// Passages of a post as the post view draws them: each line of prose, and
+/// each code block as a whole; a block left open is not drawn
+fn post_passages(content: &str) -> Vec<String> {
+    let mut passages = Vec::new();
+    let mut code: Option<Vec<&str>> = None;
+    for line in content.lines() {
+        if line.starts_with("```") {
+            match code.take() {
+                Some(lines) => passages.push(lines.join("\n")),
+                None => code = Some(Vec::new()),
+            }
+        } else if let Some(lines) = &mut code {
+            lines.push(line);
+        } else {
+            passages.push(markdown_text(line));
+        }
+    }
+    passages
+}
+
+/// Hits for `query`, grouped by document with the best documents first
+///
+/// Documents rank by number of matches, with a bonus when the title matches.
+/// A title match with no body match still yields one hit showing the title.
+pub fn search(query: &str) -> Vec<SearchHit> {
+    let query = find_text(query);
+    let query = query.as_str();
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let mut ranked: Vec<(usize, Vec<SearchHit>)> = documents()
+        .into_iter()
+        .filter_map(|doc| {
+            let title_hit = !find_all(&doc.title, query).is_empty();
+            let (occurrences, mut hits) = doc.matches(query);
+
+            if hits.is_empty() && title_hit {
+                let range = find_all(&doc.title, query)[0].clone();
+                let (snippet, highlight) = snippet(&doc.title, range);
+                hits.push(doc.hit(snippet, highlight, None));
+            }
+            if hits.is_empty() {
+                return None;
+            }
+
+            let score = occurrences + if title_hit { TITLE_BONUS } else { 0 };
+            Some((score, hits))
+        })
+        .collect();
+
+    // Stable sort keeps document order (resume, posts, projects) on ties
+    ranked.sort_by_key(|(score, _)| Reverse(*score));
+    ranked.into_iter().flat_map(|(_, hits)| hits).collect()
+}
+
+impl Document {
+    /// Number of occurrences of `query`, and hits for the first few
+    fn matches(&self, query: &str) -> (usize, Vec<SearchHit>) {
+        let mut hits = Vec::new();
+        let mut occurrence = 0;
+        for passage in &self.passages {
+            let text = find_text(passage);
+            for range in find_all(&text, query) {
+                if hits.len() < MAX_HITS_PER_DOC {
+                    let (snippet, highlight) = snippet(&text, range);
+                    let find = Find {
+                        query: query.to_string(),
+                        occurrence,
+                    };
+                    hits.push(self.hit(snippet, highlight, Some(find)));
+                }
+                occurrence += 1;
+            }
+        }
+        (occurrence, hits)
+    }
+
+    fn hit(&self, snippet: String, highlight: Range<usize>, find: Option<Find>) -> SearchHit {
+        SearchHit {
+            source: self.source,
+            title: self.title.clone(),
+            route: self.route.clone(),
+            snippet,
+            highlight,
+            find,
+        }
+    }
+}
+
+/// Text as find matches it: each run of whitespace becomes one space and
+/// the ends are trimmed
+pub fn find_text(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Char ranges of every case-insensitive occurrence of `needle` in `haystack`
+///
+/// Occurrences do not overlap. Ranges index chars, not bytes.
+pub fn find_all(haystack: &str, needle: &str) -> Vec<Range<usize>> {
+    let needle: Vec<char> = needle.chars().map(fold).collect();
+    if needle.is_empty() {
+        return Vec::new();
+    }
+    let hay: Vec<char> = haystack.chars().map(fold).collect();
+
+    let mut ranges = Vec::new();
+    let mut i = 0;
+    while i + needle.len() <= hay.len() {
+        if hay[i..i + needle.len()] == needle[..] {
+            ranges.push(i..i + needle.len());
+            i += needle.len();
+        } else {
+            i += 1;
+        }
+    }
+    ranges
+}
+
+/// Lowercase a char while keeping a one-to-one char mapping
+fn fold(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// Trimmed window of `line` around `range`, and the match range within it
+fn snippet(line: &str, range: Range<usize>) -> (String, Range<usize>) {
+    let chars: Vec<char> = line.chars().collect();
+    let start = range.start.saturating_sub(SNIPPET_BEFORE);
+    let end = (start + SNIPPET_LEN).max(range.end).min(chars.len());
+
+    let mut text: String = chars[start..end].iter().collect();
+    let mut offset = start;
+    // Skip leading indentation and markdown markers so snippets line up
+    let trimmed = text.trim_start_matches([' ', '#', '-', '*', '>', '•']);
+    let skipped = text.chars().count() - trimmed.chars().count();
+    if skipped <= range.start - start {
+        offset += skipped;
+        text = trimmed.to_string();
+    }
+
+    let mut snippet = String::new();
+    let mut highlight = range.start - offset..range.end - offset;
+    if start > 0 {
+        snippet.push('…');
+        highlight = highlight.start + 1..highlight.end + 1;
+    }
+    snippet.push_str(text.trim_end());
+    if end < chars.len() {
+        snippet.push('…');
+    }
+    (snippet, highlight)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_all_is_case_insensitive() {
+        assert_eq!(find_all("Rust and rust", "RUST"), vec![0..4, 9..13]);
+        assert_eq!(find_all("aaaa", "aa"), vec![0..2, 2..4]);
+        assert!(find_all("Rust", "").is_empty());
+    }
+
+    #[test]
+    fn test_search_highlights_match_in_snippet() {
+        let post = &POSTS[0];
+        let word = post.content.split_whitespace().nth(5).unwrap();
+        let hits = search(word);
+        let hit = hits
+            .iter()
+            .find(|h| h.route == Route::BlogPost(post.slug.to_string()))
+            .unwrap();
+
+        let matched: String = hit
+            .snippet
+            .chars()
+            .skip(hit.highlight.start)
+            .take(hit.highlight.len())
+            .collect();
+        assert_eq!(matched.to_lowercase(), word.to_lowercase());
+        assert!(search("   ").is_empty());
+        assert!(search("zzqxj-no-such-phrase").is_empty());
+    }
+
+    #[test]
+    fn test_occurrences_count_like_the_view() {
+        use crate::widgets::{FindState, Markdown};
+
+        let body = "- **Rust is** fun\n- a [rust link](https://rust.example) and\n    rust   is fun, really rust is";
+        let doc = Document {
+            source: Source::Resume,
+            title: "Resume".to_string(),
+            route: Route::Resume,
+            passages: body.lines().map(markdown_text).collect(),
+        };
+        // Bold markers and link URLs are not drawn, so they are not searched
+        let (count, hits) = doc.matches("rust is");
+        assert_eq!(count, 3);
+        assert_eq!(hits[0].snippet, "Rust is fun");
+        assert_eq!(hits[0].highlight, 0..7);
+        assert_eq!(doc.matches("rust").0, 4);
+        assert_eq!(doc.matches("example").0, 0);
+
+        // The view numbers them alike, however narrow the wrapping
+        for width in [0, 12, 40] {
+            let markdown = Markdown::wrapped(body, width);
+            let mut find = FindState::new(hits[2].find.clone().unwrap());
+            find.index(&markdown.render_all(), markdown.passages());
+            assert!(find.status().contains(" 3/3 "), "{} at {width}", find.status());
+        }
+    }
+}
@@ -0,0 +1,412 @@
+//! Read-only SFTP subsystem presenting the content as a virtual tree
+//!
+//! ```text
+//! /resume.pdf, /resume.md, /resume.txt, /resume.html
+//! /contact.vcf
+//! /posts/<slug>.md        front matter, then the post
+//! /projects/<slug>.md     front matter, then the description
+//! ```
+//!
+//! `sftp -P 2222 host` browses it and `scp -P 2222 'host:/posts/*' .`
+//! copies files out. Everything is generated in memory from the content
+//! module; nothing here touches the server's filesystem.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use once_cell::sync::Lazy;
+use russh_sftp::protocol::{
+    Attrs, Data, File, FileAttributes, Handle, Name, OpenFlags, Status, StatusCode, Version,
+};
+
+use crate::content::{PROJECTS, POSTS};
+use crate::export::{self, Format};
+
+/// Permissions reported for files and directories
+const FILE_MODE: u32 = 0o100444;
+const DIR_MODE: u32 = 0o040555;
+
+/// Most handles a client may hold open at once, as each open file is a
+/// copy in memory
+const MAX_HANDLES: usize = 64;
+
+/// Modification time of every entry: when the server started, as the
+/// content is compiled in
+static STARTED: Lazy<u32> = Lazy::new(|| {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    u32::try_from(now.as_secs()).unwrap_or(u32::MAX)
+});
+
+/// A directory of the tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Dir {
+    Root,
+    Posts,
+    Projects,
+}
+
+/// A file of the tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Entry {
+    Resume(Format),
+    Contact,
+    /// Index into `POSTS`
+    Post(usize),
+    /// Index into `PROJECTS`
+    Project(usize),
+}
+
+impl Entry {
+    fn bytes(self) -> Cow<'static, [u8]> {
+        match self {
+            Entry::Resume(format) => Cow::Borrowed(export::resume(format)),
+            Entry::Contact => Cow::Owned(export::vcard().into_bytes()),
+            Entry::Post(idx) => Cow::Owned(export::post_markdown(&POSTS[idx]).into_bytes()),
+            Entry::Project(idx) => {
+                Cow::Owned(export::project_markdown(&PROJECTS[idx]).into_bytes())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Node {
+    Dir(Dir),
+    File(Entry),
+}
+
+impl Dir {
+    /// Names and nodes of the directory's entries, in listing order
+    fn entries(self) -> Vec<(String, Node)> {
+        match self {
+            Dir::Root => {
+                let mut entries: Vec<(String, Node)> = Format::ALL
+                    .into_iter()
+                    .map(|format| (format.file_name(), Node::File(Entry::Resume(format))))
+                    .collect();
+                entries.push(("contact.vcf".to_string(), Node::File(Entry::Contact)));
+                entries.push(("posts".to_string(), Node::Dir(Dir::Posts)));
+                entries.push(("projects".to_string(), Node::Dir(Dir::Projects)));
+                entries
+            }
+            Dir::Posts => POSTS
+                .iter()
+                .enumerate()
+                .map(|(idx, post)| (format!("{}.md", post.slug), Node::File(Entry::Post(idx))))
+                .collect(),
+            Dir::Projects => PROJECTS
+                .iter()
+                .enumerate()
+                .map(|(idx, project)| {
+                    (format!("{}.md", project.slug), Node::File(Entry::Project(idx)))
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Node {
+    /// Node at `path`, absolute or relative to the root
+    fn at(path: &str) -> Option<Self> {
+        let path = normalize(path);
+        let mut node = Node::Dir(Dir::Root);
+        for name in path.split('/').filter(|name| !name.is_empty()) {
+            let Node::Dir(dir) = node else {
+                return None;
+            };
+            node = dir.entries().into_iter().find(|(n, _)| n == name)?.1;
+        }
+        Some(node)
+    }
+
+    fn attrs(self) -> FileAttributes {
+        let mut attrs = FileAttributes::empty();
+        attrs.mtime = Some(*STARTED);
+        attrs.atime = Some(*STARTED);
+        match self {
+            Node::Dir(_) => attrs.permissions = Some(DIR_MODE),
+            Node::File(entry) => {
+                attrs.permissions = Some(FILE_MODE);
+                attrs.size = Some(entry.bytes().len() as u64);
+            }
+        }
+        attrs
+    }
+}
+
+/// Absolute form of `path`, resolving `.` and `..`
+fn normalize(path: &str) -> String {
+    let mut parts: Vec<&str> = Vec::new();
+    for part in path.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            part => parts.push(part),
+        }
+    }
+    format!("/{}", parts.join("/"))
+}
+
+/// What an open handle refers to
+#[derive(Debug)]
+enum Open {
+    /// A directory, and whether its listing was already sent
+    Dir { dir: Dir, listed: bool },
+    /// A file, generated when opened
+    File { entry: Entry, bytes: Cow<'static, [u8]> },
+}
+
+/// One client's SFTP session
+#[derive(Debug, Default)]
+pub struct SftpSession {
+    handles: HashMap<String, Open>,
+    next_handle: u64,
+}
+
+impl SftpSession {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open a handle to what `open` returns, unless the client already
+    /// holds [`MAX_HANDLES`]; `open` isn't called then
+    fn open_handle(&mut self, id: u32, open: impl FnOnce() -> Open) -> Result<Handle, StatusCode> {
+        if self.handles.len() >= MAX_HANDLES {
+            return Err(StatusCode::Failure);
+        }
+        self.next_handle += 1;
+        let handle = self.next_handle.to_string();
+        self.handles.insert(handle.clone(), open());
+        Ok(Handle { id, handle })
+    }
+}
+
+fn ok(id: u32) -> Status {
+    Status {
+        id,
+        status_code: StatusCode::Ok,
+        error_message: "Ok".to_string(),
+        language_tag: "en-US".to_string(),
+    }
+}
+
+impl russh_sftp::server::Handler for SftpSession {
+    type Error = StatusCode;
+
+    fn unimplemented(&self) -> Self::Error {
+        StatusCode::OpUnsupported
+    }
+
+    async fn init(
+        &mut self,
+        _version: u32,
+        _extensions: HashMap<String, String>,
+    ) -> Result<Version, Self::Error> {
+        Ok(Version::new())
+    }
+
+    async fn realpath(&mut self, id: u32, path: String) -> Result<Name, Self::Error> {
+        Ok(Name {
+            id,
+            files: vec![File::dummy(normalize(&path))],
+        })
+    }
+
+    async fn stat(&mut self, id: u32, path: String) -> Result<Attrs, Self::Error> {
+        let node = Node::at(&path).ok_or(StatusCode::NoSuchFile)?;
+        Ok(Attrs {
+            id,
+            attrs: node.attrs(),
+        })
+    }
+
+    async fn lstat(&mut self, id: u32, path: String) -> Result<Attrs, Self::Error> {
+        self.stat(id, path).await
+    }
+
+    async fn fstat(&mut self, id: u32, handle: String) -> Result<Attrs, Self::Error> {
+        let node = match self.handles.get(&handle) {
+            Some(Open::Dir { dir, .. }) => Node::Dir(*dir),
+            Some(Open::File { entry, .. }) => Node::File(*entry),
+            None => return Err(StatusCode::Failure),
+        };
+        Ok(Attrs {
+            id,
+            attrs: node.attrs(),
+        })
+    }
+
+    async fn opendir(&mut self, id: u32, path: String) -> Result<Handle, Self::Error> {
+        match Node::at(&path) {
+            Some(Node::Dir(dir)) => self.open_handle(id, || Open::Dir { dir, listed: false }),
+            Some(Node::File(_)) => Err(StatusCode::Failure),
+            None => Err(StatusCode::NoSuchFile),
+        }
+    }
+
+    /// Send the whole listing at once, then EOF
+    async fn readdir(&mut self, id: u32, handle: String) -> Result<Name, Self::Error> {
+        match self.handles.get_mut(&handle) {
+            Some(Open::Dir { dir, listed }) if !*listed => {
+                *listed = true;
+                let files = dir
+                    .entries()
+                    .into_iter()
+                    .map(|(name, node)| File::new(name, node.attrs()))
+                    .collect();
+                Ok(Name { id, files })
+            }
+            Some(Open::Dir { .. }) => Err(StatusCode::Eof),
+            _ => Err(StatusCode::Failure),
+        }
+    }
+
+    async fn open(
+        &mut self,
+        id: u32,
+        filename: String,
+        pflags: OpenFlags,
+        _attrs: FileAttributes,
+    ) -> Result<Handle, Self::Error> {
+        let writes = OpenFlags::WRITE | OpenFlags::APPEND | OpenFlags::CREATE | OpenFlags::TRUNCATE;
+        if pflags.intersects(writes) {
+            return Err(StatusCode::PermissionDenied);
+        }
+        match Node::at(&filename) {
+            Some(Node::File(entry)) => self.open_handle(id, || Open::File {
+                entry,
+                bytes: entry.bytes(),
+            }),
+            Some(Node::Dir(_)) => Err(StatusCode::Failure),
+            None => Err(StatusCode::NoSuchFile),
+        }
+    }
+
+    async fn read(
+        &mut self,
+        id: u32,
+        handle: String,
+        offset: u64,
+        len: u32,
+    ) -> Result<Data, Self::Error> {
+        let Some(Open::File { bytes, .. }) = self.handles.get(&handle) else {
+            return Err(StatusCode::Failure);
+        };
+        let start = usize::try_from(offset).unwrap_or(usize::MAX);
+        if start >= bytes.len() {
+            return Err(StatusCode::Eof);
+        }
+        let end = bytes.len().min(start.saturating_add(len as usize));
+        Ok(Data {
+            id,
+            data: bytes[start..end].to_vec(),
+        })
+    }
+
+    async fn close(&mut self, id: u32, handle: String) -> Result<Status, Self::Error> {
+        match self.handles.remove(&handle) {
+            Some(_) => Ok(ok(id)),
+            None => Err(StatusCode::Failure),
+        }
+    }
+
+    async fn write(
+        &mut self,
+        _id: u32,
+        _handle: String,
+        _offset: u64,
+        _data: Vec<u8>,
+    ) -> Result<Status, Self::Error> {
+        Err(StatusCode::PermissionDenied)
+    }
+
+    async fn remove(&mut self, _id: u32, _filename: String) -> Result<Status, Self::Error> {
+        Err(StatusCode::PermissionDenied)
+    }
+
+    async fn mkdir(
+        &mut self,
+        _id: u32,
+        _path: String,
+        _attrs: FileAttributes,
+    ) -> Result<Status, Self::Error> {
+        Err(StatusCode::PermissionDenied)
+    }
+
+    async fn rename(
+        &mut self,
+        _id: u32,
+        _oldpath: String,
+        _newpath: String,
+    ) -> Result<Status, Self::Error> {
+        Err(StatusCode::PermissionDenied)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use russh_sftp::server::Handler;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_browses_the_content_tree() {
+        let mut sftp = SftpSession::new();
+        assert_eq!(normalize("./posts/../resume.md"), "/resume.md");
+
+        let dir = sftp.opendir(1, ".".into()).await.unwrap().handle;
+        let listing = sftp.readdir(2, dir.clone()).await.unwrap();
+        let names: Vec<&str> = listing.files.iter().map(|f| f.filename.as_str()).collect();
+        assert_eq!(
+            names,
+            ["resume.pdf", "resume.md", "resume.txt", "resume.html", "contact.vcf", "posts", "projects"]
+        );
+        assert!(listing.files[5].attrs.is_dir());
+        assert_eq!(sftp.readdir(3, dir).await.unwrap_err(), StatusCode::Eof);
+
+        let posts = sftp.opendir(4, "/posts".into()).await.unwrap().handle;
+        let listing = sftp.readdir(5, posts).await.unwrap();
+        assert_eq!(listing.files.len(), POSTS.len());
+        assert_eq!(listing.files[0].filename, format!("{}.md", POSTS[0].slug));
+
+        let path = format!("/projects/{}.md", PROJECTS[0].slug);
+        let file = sftp
+            .open(6, path, OpenFlags::READ, FileAttributes::empty())
+            .await
+            .unwrap()
+            .handle;
+        let data = sftp.read(7, file.clone(), 0, 4).await.unwrap().data;
+        assert_eq!(data, b"---\n");
+        let len = export::project_markdown(&PROJECTS[0]).len() as u64;
+        assert_eq!(sftp.read(8, file, len, 8).await.unwrap_err(), StatusCode::Eof);
+
+        let write = OpenFlags::WRITE | OpenFlags::CREATE;
+        let denied = sftp.open(9, "/notes.txt".into(), write, FileAttributes::empty()).await;
+        assert_eq!(denied.unwrap_err(), StatusCode::PermissionDenied);
+        let missing = sftp.stat(10, "/resume.md/child".into()).await;
+        assert_eq!(missing.unwrap_err(), StatusCode::NoSuchFile);
+    }
+    #[tokio::test]
+    async fn test_limits_open_handles() {
+        let mut sftp = SftpSession::new();
+        let path = || "/resume.md".to_string();
+        let attrs = FileAttributes::empty;
+
+        let mut handles = Vec::new();
+        for id in 0..MAX_HANDLES as u32 {
+            let handle = sftp.open(id, path(), OpenFlags::READ, attrs()).await.unwrap();
+            handles.push(handle.handle);
+        }
+        let full = sftp.open(100, path(), OpenFlags::READ, attrs()).await;
+        assert_eq!(full.unwrap_err(), StatusCode::Failure);
+        let dir = sftp.opendir(101, "/".into()).await;
+        assert_eq!(dir.unwrap_err(), StatusCode::Failure);
+
+        // Closing one makes room for another
+        sftp.close(102, handles.pop().unwrap()).await.unwrap();
+        assert!(sftp.open(103, path(), OpenFlags::READ, attrs()).await.is_ok());
+    }
+}
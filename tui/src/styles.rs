@@ -55,6 +55,14 @@ pub fn accent() -> Style {
     Style::default().fg(COLOR_ACCENT)
 }
 
+/// Link style - underlined accent; the runtime turns links placed with
+/// [`crate::links::place`] in this style into OSC 8 hyperlinks
+pub fn link() -> Style {
+    Style::default()
+        .fg(COLOR_ACCENT)
+        .add_modifier(Modifier::UNDERLINED)
+}
+
 /// Warning style
 pub fn warning() -> Style {
     Style::default().fg(COLOR_WARNING)
@@ -72,6 +80,28 @@ pub fn tag() -> Style {
         .bg(COLOR_SECONDARY)
 }
 
+/// Tag under the cursor in a tag bar
+pub fn tag_focused() -> Style {
+    Style::default()
+        .fg(COLOR_BACKGROUND)
+        .bg(COLOR_ACCENT)
+        .add_modifier(Modifier::BOLD)
+}
+
+/// Search match highlight
+pub fn search_match() -> Style {
+    Style::default()
+        .fg(COLOR_BACKGROUND)
+        .bg(COLOR_WARNING)
+}
+
+/// Status bar along the bottom of the screen
+pub fn status_bar() -> Style {
+    Style::default()
+        .fg(COLOR_MUTED)
+        .bg(Color::Rgb(36, 40, 59))
+}
+
 /// Cursor/pointer style
 pub fn cursor() -> Style {
     Style::default()
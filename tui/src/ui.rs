@@ -1,10 +1,14 @@
 //! View orchestration - mounting/unmounting components and layout management
 
-use tuirealm::application::PollStrategy;
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use tuirealm::props::{AttrValue, Attribute};
 use tuirealm::{Application, NoUserEvent};
 
-use crate::components::{Blog, Contact, Home, Loading, Projects, Resume};
+use crate::components::{
+    Blog, Contact, Help, Home, Loading, Palette, Projects, Resume, Search, Settings,
+};
 use crate::msg::{Msg, ViewId};
+use crate::search::{Find, FIND_ATTR};
 
 /// Type alias for our Application
 pub type App = Application<ViewId, Msg, NoUserEvent>;
@@ -18,6 +22,10 @@ pub fn mount_view(app: &mut App, view_id: ViewId) -> Result<(), Box<dyn std::err
     let _ = app.umount(&ViewId::Blog);
     let _ = app.umount(&ViewId::Projects);
     let _ = app.umount(&ViewId::Contact);
+    let _ = app.umount(&ViewId::Settings);
+    let _ = app.umount(&ViewId::Search);
+    let _ = app.umount(&ViewId::Help);
+    let _ = app.umount(&ViewId::Palette);
 
     // Mount the new component
     match view_id {
@@ -39,6 +47,18 @@ pub fn mount_view(app: &mut App, view_id: ViewId) -> Result<(), Box<dyn std::err
         ViewId::Contact => {
             app.mount(view_id, Box::new(Contact::new()), vec![])?;
         }
+        ViewId::Settings => {
+            app.mount(view_id, Box::new(Settings::new()), vec![])?;
+        }
+        ViewId::Search => {
+            app.mount(view_id, Box::new(Search::new()), vec![])?;
+        }
+        ViewId::Help => {
+            app.mount(view_id, Box::new(Help::new(ViewId::Home)), vec![])?;
+        }
+        ViewId::Palette => {
+            app.mount(view_id, Box::new(Palette::new()), vec![])?;
+        }
     }
 
     // Set it as active
@@ -47,10 +67,75 @@ pub fn mount_view(app: &mut App, view_id: ViewId) -> Result<(), Box<dyn std::err
     Ok(())
 }
 
-/// Poll for events and collect messages
-pub fn poll_messages(app: &mut App) -> Vec<Msg> {
-    match app.tick(PollStrategy::UpTo(3)) {
-        Ok(messages) => messages,
-        Err(_) => vec![],
+/// Snapshot of a mounted view's local state (cursor, scroll, open item)
+pub fn snapshot(app: &App, view_id: ViewId) -> Option<AttrValue> {
+    app.query(&view_id, Attribute::Value).ok().flatten()
+}
+
+/// Restore a snapshot taken with [`snapshot`] into a freshly mounted view
+pub fn restore_state(
+    app: &mut App,
+    view_id: ViewId,
+    state: AttrValue,
+) -> Result<(), Box<dyn std::error::Error>> {
+    app.attr(&view_id, Attribute::Value, state)?;
+    Ok(())
+}
+
+/// Highlight a search phrase in a mounted view and scroll to the match
+///
+/// Views without in-page find ignore the attribute.
+pub fn find_in_view(
+    app: &mut App,
+    view_id: ViewId,
+    find: &Find,
+) -> Result<(), Box<dyn std::error::Error>> {
+    app.attr(&view_id, FIND_ATTR, find.to_attr())?;
+    Ok(())
+}
+
+/// Mount an overlay above `under` and give it focus
+pub fn mount_overlay(
+    app: &mut App,
+    overlay: ViewId,
+    under: ViewId,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let _ = app.umount(&overlay);
+    match overlay {
+        ViewId::Help => {
+            app.mount(overlay, Box::new(Help::new(under)), vec![])?;
+        }
+        ViewId::Palette => {
+            app.mount(overlay, Box::new(Palette::new()), vec![])?;
+        }
+        _ => return Err(format!("{overlay:?} is not an overlay").into()),
     }
+    app.active(&overlay)?;
+
+    Ok(())
+}
+
+/// Unmount an overlay and hand focus back to the view underneath
+pub fn close_overlay(
+    app: &mut App,
+    overlay: ViewId,
+    under: ViewId,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let _ = app.umount(&overlay);
+    app.active(&under)?;
+
+    Ok(())
+}
+
+/// Centered area for overlays, leaving a margin of the view visible
+pub fn overlay_area(area: Rect) -> Rect {
+    let width = area.width.saturating_sub(8).clamp(area.width.min(40), 72);
+    let height = area.height.saturating_sub(4);
+    let [row] = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [centered] = Layout::horizontal([Constraint::Length(width)])
+        .flex(Flex::Center)
+        .areas(row);
+    centered
 }
@@ -0,0 +1,115 @@
+//! Breakpoints that pick a layout for the terminal size
+
+use ratatui::layout::{Alignment, Constraint, Flex, Layout, Rect};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Padding, Paragraph};
+use ratatui::Frame;
+
+use crate::styles;
+
+/// Layout class of an area, from smallest to largest
+///
+/// # Example
+/// ```ignore
+/// match Breakpoint::of(area) {
+///     Breakpoint::Wide => self.render_two_panes(frame, area),
+///     _ => self.render_list(frame, area),
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Breakpoint {
+    /// Below the minimum size; nothing but a notice is drawn
+    TooSmall,
+    /// One-line headers, no logo and no item descriptions (e.g. 80x24)
+    Compact,
+    Normal,
+    /// Room for a list and the selected item side by side
+    Wide,
+}
+
+impl Breakpoint {
+    pub const MIN_WIDTH: u16 = 40;
+    pub const MIN_HEIGHT: u16 = 12;
+
+    /// Breakpoint of a view's area
+    pub fn of(area: Rect) -> Self {
+        if area.width < Self::MIN_WIDTH || area.height < Self::MIN_HEIGHT {
+            Breakpoint::TooSmall
+        } else if area.width < 80 || area.height < 28 {
+            Breakpoint::Compact
+        } else if area.width >= 140 {
+            Breakpoint::Wide
+        } else {
+            Breakpoint::Normal
+        }
+    }
+
+    pub fn is_compact(self) -> bool {
+        self <= Breakpoint::Compact
+    }
+
+    /// Split a list's area into the list and a preview of the selected item
+    /// beside it; the preview is empty unless the layout is wide
+    pub fn panes(self, area: Rect) -> [Rect; 2] {
+        if self != Breakpoint::Wide {
+            return [area, Rect::new(area.right(), area.y, 0, area.height)];
+        }
+        Layout::horizontal([Constraint::Percentage(40), Constraint::Min(1)]).areas(area)
+    }
+}
+
+/// Preview pane beside a list, separated from it by a rule
+pub fn render_preview(frame: &mut Frame, area: Rect, lines: Vec<Line<'static>>) {
+    let block = Block::default()
+        .borders(Borders::LEFT)
+        .border_style(styles::border())
+        .padding(Padding::left(1));
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Notice drawn instead of the views when the terminal is too small
+pub fn render_too_small(frame: &mut Frame, area: Rect) {
+    let lines = vec![
+        Line::styled("Terminal too small", styles::title()),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Current ", styles::muted()),
+            Span::styled(format!("{}x{}", area.width, area.height), styles::warning()),
+        ]),
+        Line::from(vec![
+            Span::styled("Needed  ", styles::muted()),
+            Span::styled(
+                format!("{}x{}", Breakpoint::MIN_WIDTH, Breakpoint::MIN_HEIGHT),
+                styles::accent(),
+            ),
+        ]),
+    ];
+    let [middle] = Layout::vertical([Constraint::Length(lines.len() as u16)])
+        .flex(Flex::Center)
+        .areas(area);
+    frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), middle);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breakpoint_of_terminal_sizes() {
+        assert_eq!(Breakpoint::of(Rect::new(0, 0, 30, 20)), Breakpoint::TooSmall);
+        assert_eq!(Breakpoint::of(Rect::new(0, 0, 100, 10)), Breakpoint::TooSmall);
+        // A classic 80x24 terminal, less the status bar
+        assert_eq!(Breakpoint::of(Rect::new(0, 0, 80, 23)), Breakpoint::Compact);
+        assert_eq!(Breakpoint::of(Rect::new(0, 0, 100, 40)), Breakpoint::Normal);
+        assert_eq!(Breakpoint::of(Rect::new(0, 0, 200, 50)), Breakpoint::Wide);
+        assert!(Breakpoint::TooSmall.is_compact());
+        assert!(!Breakpoint::Wide.is_compact());
+
+        let area = Rect::new(0, 0, 200, 50);
+        let [list, preview] = Breakpoint::Wide.panes(area);
+        assert_eq!((list.width, preview.x, preview.width), (80, 80, 120));
+        let [list, preview] = Breakpoint::Normal.panes(area);
+        assert_eq!(list, area);
+        assert!(preview.is_empty());
+    }
+}
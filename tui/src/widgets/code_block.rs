@@ -10,10 +10,13 @@ use unicode_width::UnicodeWidthStr;
 
 use crate::styles;
 
+/// Tabs are drawn as this many spaces, since terminals disagree on their width
+const TAB: &str = "    ";
+
 /// A styled code block widget with borders and background
 /// 
 /// # Example
-/// ```
+/// ```ignore
 /// use crate::widgets::CodeBlock;
 /// 
 /// let code = CodeBlock::new("fn main() {\n    println!(\"Hello\");\n}")
@@ -58,26 +61,33 @@ impl CodeBlock {
         // Build: ┌─ lang ─...─┐
         // Use display width for accurate terminal rendering
         let prefix = format!("┌─ {} ", lang_display);
-        let prefix_width = prefix.width();
         let suffix = "┐";
-        let suffix_width = suffix.width();
-        let remaining = w.saturating_sub(prefix_width + suffix_width);
+        // Drop a label too long for the block rather than overflow it
+        let prefix = if prefix.width() + suffix.width() > w { "┌".to_string() } else { prefix };
+        let remaining = w.saturating_sub(prefix.width() + suffix.width());
         let header = format!("{}{}{}", prefix, "─".repeat(remaining), suffix);
         
         Line::from(Span::styled(header, styles::code_lang()))
     }
 
     /// Render a code line: │ code content                            │
-    fn render_code_line(&self, line: &str) -> Line<'static> {
+    ///
+    /// Lines longer than the block continue on the next row.
+    fn render_code_line(&self, line: &str) -> Vec<Line<'static>> {
         let w = self.width as usize;
         // "│ " = 2 chars, " │" = 2 chars, so content = w - 4
-        let content_width = w.saturating_sub(4);
-        let padded = format!("{:<width$}", line, width = content_width);
-        Line::from(vec![
-            Span::styled("│ ", styles::code_lang()),
-            Span::styled(padded, styles::code()),
-            Span::styled(" │", styles::code_lang()),
-        ])
+        let content_width = w.saturating_sub(4).max(1);
+        split_to_width(&line.replace('\t', TAB), content_width)
+            .into_iter()
+            .map(|piece| {
+                let padding = " ".repeat(content_width.saturating_sub(piece.width()));
+                Line::from(vec![
+                    Span::styled("│ ", styles::code_lang()),
+                    Span::styled(piece + &padding, styles::code()),
+                    Span::styled(" │", styles::code_lang()),
+                ])
+            })
+            .collect()
     }
 
     /// Render the footer line: └────────────────────────────────────────┘
@@ -97,7 +107,7 @@ impl CodeBlock {
         lines.push(self.render_header());
         
         for code_line in self.code.lines() {
-            lines.push(self.render_code_line(code_line));
+            lines.extend(self.render_code_line(code_line));
         }
         
         lines.push(self.render_footer());
@@ -106,6 +116,21 @@ impl CodeBlock {
     }
 }
 
+/// Split `line` into pieces at most `width` columns wide, breaking between
+/// characters; a character wider than `width` gets a piece of its own
+fn split_to_width(line: &str, width: usize) -> Vec<String> {
+    let mut pieces = vec![String::new()];
+    for c in line.chars() {
+        let piece = pieces.last_mut().expect("pieces is never empty");
+        piece.push(c);
+        if piece.width() > width && piece.chars().count() > 1 {
+            piece.pop();
+            pieces.push(c.to_string());
+        }
+    }
+    pieces
+}
+
 impl Widget for CodeBlock {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let lines = self.to_lines();
@@ -119,7 +144,7 @@ impl Widget for CodeBlock {
             let mut x = area.x;
             
             for span in line.spans.iter() {
-                let width = span.content.chars().count() as u16;
+                let width = span.width() as u16;
                 buf.set_string(x, y, &span.content, span.style);
                 x += width;
             }
@@ -130,6 +155,7 @@ impl Widget for CodeBlock {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn test_code_block_creates_lines() {
@@ -140,4 +166,43 @@ mod tests {
         let lines = block.to_lines();
         assert_eq!(lines.len(), 3); // header + 1 code line + footer
     }
+
+    #[test]
+    fn test_long_lines_continue_inside_the_box() {
+        let block = CodeBlock::new("let greeting = \"héllo, wörld\";\tx").width(16);
+        let lines: Vec<String> = block.to_lines().iter().map(|line| line.to_string()).collect();
+        assert_eq!(
+            lines,
+            [
+                "┌─ code ───────┐",
+                "│ let greeting │",
+                "│  = \"héllo, w │",
+                "│ örld\";    x  │",
+                "└──────────────┘",
+            ]
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn prop_lines_are_as_wide_as_the_block(
+            code in "[\\PC\n\t]{0,200}",
+            language in "\\PC{0,20}",
+            width in 6u16..80,
+        ) {
+            let block = CodeBlock::new(code.as_str()).language(language).width(width);
+            for line in block.to_lines() {
+                prop_assert_eq!(line.width(), width as usize, "{}", line);
+            }
+
+            // Every character of the code is drawn, tabs as spaces
+            let drawn: String = block
+                .to_lines()
+                .iter()
+                .flat_map(|line| line.spans.get(1).map(|span| span.content.to_string()))
+                .collect();
+            let visible = |text: &str| text.chars().filter(|c| !c.is_whitespace()).collect::<String>();
+            prop_assert_eq!(visible(&drawn), visible(&code));
+        }
+    }
 }
@@ -0,0 +1,287 @@
+//! In-page find: highlights a phrase and steps through its matches with `n`/`N`
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use ratatui::text::{Line, Span};
+
+use crate::keymap::{key_label, keymap};
+use crate::msg::Msg;
+use crate::search::{find_all, find_text, Find};
+use crate::styles;
+
+/// A line of the source as search matches it, and the rendered lines
+/// drawing it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Passage {
+    pub text: String,
+    pub rows: Range<usize>,
+}
+
+/// Matches of a phrase in a scrollable view's rendered lines
+pub struct FindState {
+    query: String,
+    /// Rendered line of each occurrence, in document order
+    lines: Vec<usize>,
+    /// Char ranges to highlight, by rendered line
+    marks: HashMap<usize, Vec<Range<usize>>>,
+    current: usize,
+    /// Whether `lines` matches the current wrapping
+    indexed: bool,
+    /// Scroll target waiting for the next render
+    jump: Option<usize>,
+}
+
+impl FindState {
+    /// Start at `find.occurrence`; lines are indexed on the next render
+    pub fn new(find: Find) -> Self {
+        Self {
+            query: find.query,
+            lines: Vec::new(),
+            marks: HashMap::new(),
+            current: find.occurrence,
+            indexed: false,
+            jump: None,
+        }
+    }
+
+    /// Index the rendered lines, keeping the current occurrence
+    ///
+    /// Matches are found in the text of `passages`, as search finds them, so
+    /// occurrences are numbered alike however the lines wrap; a match split
+    /// across lines is highlighted on each. Call again whenever the content
+    /// is rewrapped. Schedules a jump to the current occurrence.
+    pub fn index(&mut self, lines: &[Line], passages: &[Passage]) {
+        self.lines.clear();
+        self.marks.clear();
+        for passage in passages {
+            let text = find_text(&passage.text);
+            let rows = &lines[passage.rows.start.min(lines.len())..passage.rows.end.min(lines.len())];
+            let drawn = align(&text, rows);
+            for range in find_all(&text, &self.query) {
+                // The part of the match on each line it is drawn on
+                let mut parts: Vec<(usize, Range<usize>)> = Vec::new();
+                for &(row, column) in drawn[range].iter().flatten() {
+                    let row = passage.rows.start + row;
+                    match parts.last_mut() {
+                        Some((last, part)) if *last == row => part.end = column + 1,
+                        _ => parts.push((row, column..column + 1)),
+                    }
+                }
+                let Some(&(first, _)) = parts.first() else {
+                    continue;
+                };
+                self.lines.push(first);
+                for (row, part) in parts {
+                    self.marks.entry(row).or_default().push(part);
+                }
+            }
+        }
+        self.current = self.current.min(self.lines.len().saturating_sub(1));
+        self.indexed = true;
+        self.jump = self.lines.get(self.current).copied();
+    }
+
+    /// Whether the lines have not been indexed since the last rewrap
+    pub fn needs_index(&self) -> bool {
+        !self.indexed
+    }
+
+    /// Drop the index, e.g. after a rewrap
+    pub fn invalidate(&mut self) {
+        self.indexed = false;
+    }
+
+    /// Line to scroll to, once, after indexing or stepping
+    pub fn take_jump(&mut self) -> Option<usize> {
+        self.jump.take()
+    }
+
+    /// Step to the next match, wrapping around like `less`
+    pub fn next(&mut self) {
+        if !self.lines.is_empty() {
+            self.current = (self.current + 1) % self.lines.len();
+            self.jump = Some(self.lines[self.current]);
+        }
+    }
+
+    /// Step to the previous match, wrapping around
+    pub fn prev(&mut self) {
+        if !self.lines.is_empty() {
+            self.current = (self.current + self.lines.len() - 1) % self.lines.len();
+            self.jump = Some(self.lines[self.current]);
+        }
+    }
+
+    /// Position shown in the footer, e.g. `“rust” 2/5 • n/N match`
+    pub fn status(&self) -> String {
+        if self.lines.is_empty() {
+            return format!("“{}” no matches", self.query);
+        }
+        let keymap = keymap();
+        let keys: Vec<String> = [Msg::FindNext, Msg::FindPrev]
+            .iter()
+            .filter_map(|msg| keymap.shortcut(msg))
+            .map(|key| key_label(&key))
+            .collect();
+        format!(
+            "“{}” {}/{} • {} match",
+            self.query,
+            self.current + 1,
+            self.lines.len(),
+            keys.join("/")
+        )
+    }
+
+    /// Highlight the matches drawn on rendered line `row`
+    pub fn highlight(&self, row: usize, line: Line<'static>) -> Line<'static> {
+        match self.marks.get(&row) {
+            Some(ranges) => highlight_ranges(line, ranges),
+            None => line,
+        }
+    }
+}
+
+/// Row and char column in `rows` where each char of `text` is drawn
+///
+/// Wrapping only adds and drops whitespace, so the other chars are found in
+/// order, skipping decorations such as a code block's borders. Whitespace,
+/// and chars that are not drawn, map to `None`.
+fn align(text: &str, rows: &[Line]) -> Vec<Option<(usize, usize)>> {
+    let mut drawn = rows.iter().enumerate().flat_map(|(row, line)| {
+        line.spans
+            .iter()
+            .flat_map(|span| span.content.chars())
+            .enumerate()
+            .map(move |(column, c)| (row, column, c))
+    });
+    text.chars()
+        .map(|c| {
+            if c.is_whitespace() {
+                return None;
+            }
+            drawn.find(|&(_, _, d)| d == c).map(|(row, column, _)| (row, column))
+        })
+        .collect()
+}
+
+/// Restyle the parts of `line` that match `query`, across span boundaries
+pub fn highlight_matches(line: Line<'static>, query: &str) -> Line<'static> {
+    let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+    highlight_ranges(line, &find_all(&text, query))
+}
+
+/// Restyle the chars of `line` in `ranges`
+fn highlight_ranges(line: Line<'static>, ranges: &[Range<usize>]) -> Line<'static> {
+    if ranges.is_empty() {
+        return line;
+    }
+
+    let chars = line
+        .spans
+        .iter()
+        .flat_map(|span| span.content.chars().map(move |c| (c, span.style)));
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    for (i, (c, style)) in chars.enumerate() {
+        let style = if ranges.iter().any(|r| r.contains(&i)) {
+            style.patch(styles::search_match())
+        } else {
+            style
+        };
+        match spans.last_mut() {
+            Some(last) if last.style == style => last.content.to_mut().push(c),
+            _ => spans.push(Span::styled(c.to_string(), style)),
+        }
+    }
+
+    Line::from(spans).style(line.style)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn passages(lines: &[Line]) -> Vec<Passage> {
+        (0..lines.len())
+            .map(|row| Passage {
+                text: lines[row].to_string(),
+                rows: row..row + 1,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_find_steps_through_matches_and_wraps() {
+        let lines = [
+            Line::from("Rust at work"),
+            Line::from("nothing here"),
+            Line::from(vec![Span::raw("ru"), Span::raw("st and rust")]),
+        ];
+        let mut find = FindState::new(Find {
+            query: "rust".to_string(),
+            occurrence: 1,
+        });
+        find.index(&lines, &passages(&lines));
+        assert_eq!(find.take_jump(), Some(2));
+        find.next();
+        assert_eq!(find.take_jump(), Some(2));
+        find.next();
+        assert_eq!(find.take_jump(), Some(0));
+        find.prev();
+        assert_eq!(find.take_jump(), Some(2));
+
+        // Matches spanning two spans are highlighted as one run
+        let highlighted = find.highlight(2, lines[2].clone());
+        assert_eq!(highlighted.spans[0].content, "rust");
+        assert_eq!(highlighted.spans[0].style, styles::search_match());
+    }
+
+    #[test]
+    fn test_find_matches_phrases_split_across_lines() {
+        let lines = [
+            Line::from("│ fn split_"),
+            Line::from("│ phrase │"),
+            Line::from("  more text"),
+        ];
+        let passages = [
+            Passage {
+                text: "fn split_phrase".to_string(),
+                rows: 0..2,
+            },
+            Passage {
+                text: "  more   text".to_string(),
+                rows: 2..3,
+            },
+        ];
+        let mut find = FindState::new(Find {
+            query: "split_phrase".to_string(),
+            occurrence: 0,
+        });
+        find.index(&lines, &passages);
+        assert_eq!(find.take_jump(), Some(0));
+        assert_eq!(find.status().split(' ').nth(1), Some("1/1"));
+
+        // Each part is highlighted where it is drawn, borders excluded
+        assert_eq!(highlighted(&find, 0, &lines[0]), ["split_"]);
+        assert_eq!(highlighted(&find, 1, &lines[1]), ["phrase"]);
+
+        // Whitespace runs count as one space
+        let mut find = FindState::new(Find {
+            query: "more text".to_string(),
+            occurrence: 0,
+        });
+        find.index(&lines, &passages);
+        assert_eq!(find.take_jump(), Some(2));
+        assert_eq!(highlighted(&find, 2, &lines[2]), ["more text"]);
+    }
+
+    /// Highlighted parts of rendered line `row`
+    fn highlighted(find: &FindState, row: usize, line: &Line<'static>) -> Vec<String> {
+        find.highlight(row, line.clone())
+            .spans
+            .iter()
+            .filter(|span| span.style == styles::search_match())
+            .map(|span| span.content.to_string())
+            .collect()
+    }
+}
@@ -0,0 +1,94 @@
+//! Hit-testing for mouse clicks against what was last rendered
+
+use ratatui::layout::{Position, Rect};
+
+/// Screen areas drawn at the last render and the item each one shows
+///
+/// Components clear it at the start of a render, record the area of every
+/// clickable item as they draw it, and look clicks up in it later.
+///
+/// # Example
+/// ```ignore
+/// self.hits.clear();
+/// for (i, row) in rows.enumerate() {
+///     self.hits.push(Rect::new(area.x, area.y + i as u16, area.width, 1), i);
+/// }
+/// // ...
+/// Msg::Click(column, row) => self.hits.at(column, row),
+/// ```
+#[derive(Debug, Clone)]
+pub struct HitMap<T> {
+    areas: Vec<(Rect, T)>,
+}
+
+impl<T> Default for HitMap<T> {
+    fn default() -> Self {
+        Self { areas: Vec::new() }
+    }
+}
+
+impl<T: Copy> HitMap<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forget the areas of the previous render
+    pub fn clear(&mut self) {
+        self.areas.clear();
+    }
+
+    /// Record that `item` is drawn in `area`
+    pub fn push(&mut self, area: Rect, item: T) {
+        if !area.is_empty() {
+            self.areas.push((area, item));
+        }
+    }
+
+    /// Item drawn at a screen cell, the last one recorded if areas overlap
+    pub fn at(&self, column: u16, row: u16) -> Option<T> {
+        self.areas
+            .iter()
+            .rev()
+            .find(|(area, _)| area.contains(Position::new(column, row)))
+            .map(|(_, item)| *item)
+    }
+}
+
+/// Areas of a stack of items drawn from the top of `area`, one per entry in
+/// `heights`, clipped to `area`
+pub fn stacked(area: Rect, heights: &[usize]) -> Vec<Rect> {
+    let mut y = area.y;
+    heights
+        .iter()
+        .map(|&height| {
+            let top = y.min(area.bottom());
+            y = y.saturating_add(height as u16);
+            Rect::new(area.x, top, area.width, y.min(area.bottom()) - top)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hit_map_finds_clicked_item() {
+        let area = Rect::new(2, 5, 20, 6);
+        let mut hits = HitMap::new();
+        for (i, rect) in stacked(area, &[3, 2, 4]).into_iter().enumerate() {
+            hits.push(rect, i);
+        }
+
+        assert_eq!(hits.at(2, 5), Some(0));
+        assert_eq!(hits.at(21, 7), Some(0));
+        assert_eq!(hits.at(10, 8), Some(1));
+        // The last item is cut off at the bottom of the area
+        assert_eq!(hits.at(10, 10), Some(2));
+        assert_eq!(hits.at(10, 11), None);
+        assert_eq!(hits.at(1, 5), None);
+
+        hits.clear();
+        assert_eq!(hits.at(2, 5), None);
+    }
+}
@@ -1,17 +1,7 @@
-//! Loading animation widget with bouncing dots
+//! Loading animation state with bouncing dots
 
 use std::time::{Duration, Instant};
 
-use ratatui::{
-    buffer::Buffer,
-    layout::{Alignment, Rect},
-    style::{Modifier, Style},
-    text::{Line, Span},
-    widgets::{Paragraph, StatefulWidget, Widget},
-};
-
-use crate::styles::{COLOR_PRIMARY, COLOR_SECONDARY};
-
 /// Bouncing dot animation frames
 const BOUNCE_FRAMES: &[&str] = &[
     "●    ",
@@ -155,103 +145,4 @@ impl LoadingState {
             self.last_tick = Instant::now();
         }
     }
-
-    /// Get current frame
-    fn current_frame(&self) -> &'static str {
-        let frames = self.style.frames();
-        frames[self.frame_idx % frames.len()]
-    }
-}
-
-/// Loading widget - displays animated loading text with bouncing dots
-///
-/// # Example
-/// ```
-/// // Create indefinite loading (for API calls)
-/// loading: LoadingState::indefinite(),
-///
-/// // Or with timeout
-/// loading: LoadingState::new(Duration::from_secs(5)),
-///
-/// // In tick():
-/// loading.tick();
-///
-/// // When API completes:
-/// loading.complete();
-///
-/// // In render():
-/// frame.render_stateful_widget(LoadingWidget::new("Loading"), area, &mut loading);
-/// ```
-pub struct LoadingWidget {
-    message: String,
-    alignment: Alignment,
-    text_style: Option<Style>,
-    animation_style: Option<Style>,
-}
-
-impl LoadingWidget {
-    /// Create a new loading widget with the given message
-    pub fn new(message: impl Into<String>) -> Self {
-        Self {
-            message: message.into(),
-            alignment: Alignment::Center,
-            text_style: None,
-            animation_style: None,
-        }
-    }
-
-    /// Set text alignment
-    #[allow(dead_code)]
-    pub fn alignment(mut self, alignment: Alignment) -> Self {
-        self.alignment = alignment;
-        self
-    }
-
-    /// Set custom text style
-    #[allow(dead_code)]
-    pub fn text_style(mut self, style: Style) -> Self {
-        self.text_style = Some(style);
-        self
-    }
-
-    /// Set custom animation style
-    #[allow(dead_code)]
-    pub fn animation_style(mut self, style: Style) -> Self {
-        self.animation_style = Some(style);
-        self
-    }
-}
-
-impl StatefulWidget for LoadingWidget {
-    type State = LoadingState;
-
-    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
-        let text_style = self.text_style.unwrap_or_else(|| {
-            Style::default()
-                .fg(COLOR_PRIMARY)
-                .add_modifier(Modifier::BOLD)
-        });
-
-        let animation_style = self.animation_style.unwrap_or_else(|| {
-            Style::default()
-                .fg(COLOR_SECONDARY)
-                .add_modifier(Modifier::BOLD)
-        });
-
-        // Get current animation frame
-        let frame = state.current_frame();
-
-        let line = Line::from(vec![
-            Span::styled(self.message.clone(), text_style),
-            Span::raw(" "),
-            Span::styled(frame, animation_style),
-        ]);
-
-        // Calculate vertical centering
-        let y_offset = area.height.saturating_sub(1) / 2;
-        let text_area = Rect::new(area.x, area.y + y_offset, area.width, 1);
-
-        let paragraph = Paragraph::new(line).alignment(self.alignment);
-        paragraph.render(text_area, buf);
-    }
 }
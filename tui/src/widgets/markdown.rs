@@ -1,13 +1,41 @@
 //! Markdown rendering widget - renders markdown text with styled output
 
 use ratatui::{
+    layout::Rect,
     style::Modifier,
     text::{Line, Span},
 };
 
-use super::wrap_text;
+use super::find::Passage;
+use super::text_wrap::wrap_spans;
+use crate::links::{self, SpanLinks};
 use crate::styles;
 
+/// A link in markdown text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    /// Text shown for the link
+    pub text: String,
+    pub url: String,
+}
+
+/// A heading and the line it is on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Heading {
+    /// 1 for `#`, 2 for `##`, 3 for `###`
+    pub level: usize,
+    pub title: String,
+    pub line: usize,
+}
+
+/// Level and text of a `# `, `## ` or `### ` heading line
+pub fn heading(line: &str) -> Option<(usize, &str)> {
+    ["# ", "## ", "### "]
+        .iter()
+        .enumerate()
+        .find_map(|(i, prefix)| Some((i + 1, line.strip_prefix(prefix)?)))
+}
+
 /// Render a single markdown line with appropriate styling
 ///
 /// Handles:
@@ -18,61 +46,238 @@ use crate::styles;
 /// - `**Label:** value` bold labels
 /// - `*italic*` text
 /// - `---` horizontal dividers
+/// - `[text](url)` links, bare `https://` URLs and email addresses
 pub fn render_line(line: &str) -> Line<'static> {
-    let text = line.to_string();
-
-    if text.starts_with("# ") {
-        Line::from(Span::styled(text[2..].to_string(), styles::title()))
-    } else if text.starts_with("## ") {
-        Line::from(Span::styled(text[3..].to_string(), styles::subtitle()))
-    } else if text.starts_with("### ") {
-        Line::from(Span::styled(text[4..].to_string(), styles::accent()))
-    } else if text.starts_with("- **") {
-        // List item with bold start
-        Line::from(vec![
-            Span::styled("  • ", styles::accent()),
-            Span::styled(text[4..].to_string(), styles::text()),
-        ])
-    } else if text.starts_with("- ") {
-        Line::from(vec![
-            Span::styled("  • ", styles::accent()),
-            Span::styled(text[2..].to_string(), styles::text()),
-        ])
-    } else if text.starts_with("1. ") || text.starts_with("2. ") || text.starts_with("3. ") {
-        Line::from(vec![
-            Span::styled(format!("  {}. ", &text[0..1]), styles::accent()),
-            Span::styled(text[3..].to_string(), styles::text()),
-        ])
-    } else if text.starts_with("**") && text.contains(":**") {
+    if line == DIVIDER {
+        return divider(RULE_WIDTH);
+    }
+    let (mut spans, text) = render_styled(line);
+    spans.extend(style_links(text).into_iter().map(|(span, _)| span));
+    Line::from(spans)
+}
+
+/// Render a markdown line wrapped to `width` columns
+///
+/// Wrapping happens after rendering, so links and bold text are never split
+/// by their syntax and list items wrap under their text. No row is wider
+/// than `width`, except for a single character wider on its own. Each row
+/// comes with the URLs of its links, including both parts of a link split
+/// across rows. A `width` of 0 leaves the line whole.
+pub fn render_wrapped(line: &str, width: usize) -> Vec<(Line<'static>, SpanLinks)> {
+    if line == DIVIDER {
+        let width = if width == 0 { RULE_WIDTH } else { width.min(RULE_WIDTH) };
+        return vec![(divider(width), Vec::new())];
+    }
+    let (marker, text) = render_styled(line);
+    let (text, urls): (Vec<_>, Vec<_>) = style_links(text).into_iter().unzip();
+    wrap_spans(marker, text, width)
+        .into_iter()
+        .map(|(line, sources)| {
+            let links = sources
+                .into_iter()
+                .enumerate()
+                .filter_map(|(i, source)| Some((i, urls[source?].clone()?)))
+                .collect();
+            (line, links)
+        })
+        .collect()
+}
+
+/// Text of a markdown line as drawn, without its syntax, e.g. for search
+pub fn plain_text(line: &str) -> String {
+    render_line(line).to_string()
+}
+
+/// Source of a horizontal rule
+const DIVIDER: &str = "---";
+
+/// Widest a horizontal rule is drawn
+const RULE_WIDTH: usize = 40;
+
+fn divider(width: usize) -> Line<'static> {
+    Line::from(Span::styled("─".repeat(width), styles::border()))
+}
+
+/// Marker drawn before the first row of a line (a bullet, number or
+/// indentation), and the text after it
+fn render_styled(line: &str) -> (Vec<Span<'static>>, Vec<Span<'static>>) {
+    let bullet = || vec![Span::styled("  • ", styles::accent())];
+
+    if let Some((level, rest)) = heading(line) {
+        let style = match level {
+            1 => styles::title(),
+            2 => styles::subtitle(),
+            _ => styles::accent(),
+        };
+        (Vec::new(), vec![Span::styled(rest.to_string(), style)])
+    } else if let Some(rest) = line.strip_prefix("- **") {
+        // List item with bold start, up to the closing `**` if there is one
+        let (bold, rest) = rest.split_once("**").unwrap_or((rest, ""));
+        let text = vec![
+            Span::styled(bold.to_string(), styles::text().add_modifier(Modifier::BOLD)),
+            Span::styled(rest.to_string(), styles::text()),
+        ];
+        (bullet(), text)
+    } else if let Some(rest) = line.strip_prefix("- ") {
+        (bullet(), vec![Span::styled(rest.to_string(), styles::text())])
+    } else if let Some((number, rest)) = numbered_item(line) {
+        let marker = vec![Span::styled(format!("  {number}. "), styles::accent())];
+        (marker, vec![Span::styled(rest.to_string(), styles::text())])
+    } else if let Some((label, rest)) = line
+        .strip_prefix("**")
+        .and_then(|inner| inner.split_once(":**"))
+    {
         // Bold label like "**Languages:** ..."
-        if let Some(idx) = text.find(":**") {
-            let label = &text[2..idx];
-            let rest = text[idx + 3..].trim_end_matches("**");
-            Line::from(vec![
-                Span::styled(
-                    format!("{}:", label),
-                    styles::text().add_modifier(Modifier::BOLD),
-                ),
-                Span::styled(rest.to_string(), styles::text()),
-            ])
-        } else {
-            Line::from(Span::styled(text, styles::text()))
-        }
-    } else if text.starts_with('*') && text.ends_with('*') && !text.starts_with("**") && text.len() > 2
+        let text = vec![
+            Span::styled(
+                format!("{}:", label),
+                styles::text().add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(rest.trim_end_matches("**").to_string(), styles::text()),
+        ];
+        (Vec::new(), text)
+    } else if let Some(inner) = line
+        .strip_prefix('*')
+        .and_then(|inner| inner.strip_suffix('*'))
+        .filter(|inner| !inner.is_empty() && !inner.starts_with('*'))
     {
         // Italic text
-        Line::from(Span::styled(
-            text[1..text.len() - 1].to_string(),
-            styles::muted().add_modifier(Modifier::ITALIC),
-        ))
-    } else if text == "---" {
-        Line::from(Span::styled(
-            "────────────────────────────────────────",
-            styles::border(),
-        ))
+        let style = styles::muted().add_modifier(Modifier::ITALIC);
+        (Vec::new(), vec![Span::styled(inner.to_string(), style)])
     } else {
-        Line::from(Span::styled(text, styles::text()))
+        // Indented text keeps its indentation on every row
+        let text = line.trim_start();
+        let indent = &line[..line.len() - text.len()];
+        let marker = match indent {
+            "" => Vec::new(),
+            indent => vec![Span::styled(indent.to_string(), styles::text())],
+        };
+        (marker, vec![Span::styled(text.to_string(), styles::text())])
+    }
+}
+
+/// Number and text of a `1. `, `2. ` or `3. ` list item
+fn numbered_item(line: &str) -> Option<(&str, &str)> {
+    ["1", "2", "3"]
+        .iter()
+        .find_map(|number| Some((*number, line.strip_prefix(number)?.strip_prefix(". ")?)))
+}
+
+/// Split each span around its links, drawing them in the link style, and
+/// pair every span with the URL it links to
+fn style_links(text: Vec<Span<'static>>) -> Vec<(Span<'static>, Option<String>)> {
+    let mut spans = Vec::new();
+    for span in text {
+        if !has_link(&span.content) {
+            spans.push((span, None));
+            continue;
+        }
+        for (text, link) in split_links(&span.content) {
+            match link {
+                Some(link) => spans.push((Span::styled(link.text, styles::link()), Some(link.url))),
+                None => spans.push((Span::styled(text, span.style), None)),
+            }
+        }
+    }
+    spans
+}
+
+/// Cheap check before parsing a span for links
+fn has_link(text: &str) -> bool {
+    text.contains("](") || text.contains("://") || text.contains('@')
+}
+
+/// Split text into plain segments and links, in order
+pub fn split_links(text: &str) -> Vec<(String, Option<Link>)> {
+    let mut segments = Vec::new();
+    let mut plain = String::new();
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        if let Some((link, len)) = link_at(rest) {
+            if !plain.is_empty() {
+                segments.push((std::mem::take(&mut plain), None));
+            }
+            segments.push((rest[..len].to_string(), Some(link)));
+            rest = &rest[len..];
+            continue;
+        }
+        // Skip to the start of the next word or bracket
+        let mut chars = rest.char_indices();
+        let (_, c) = chars.next().unwrap_or((0, ' '));
+        let step = if c.is_whitespace() || c == '[' || c == '(' {
+            c.len_utf8()
+        } else {
+            rest.find(|c: char| c.is_whitespace() || c == '[' || c == '(')
+                .unwrap_or(rest.len())
+        };
+        plain.push_str(&rest[..step]);
+        rest = &rest[step..];
+    }
+    if !plain.is_empty() {
+        segments.push((plain, None));
     }
+    segments
+}
+
+/// Link starting at the beginning of `text`, with its length in bytes
+fn link_at(text: &str) -> Option<(Link, usize)> {
+    if let Some(inner) = text.strip_prefix('[') {
+        let close = inner.find("](")?;
+        let url_end = inner[close + 2..].find(')')?;
+        let label = &inner[..close];
+        let url = &inner[close + 2..close + 2 + url_end];
+        if label.is_empty() || label.contains(['[', ']']) || url.is_empty() || url.contains(char::is_whitespace) {
+            return None;
+        }
+        let link = Link {
+            text: label.to_string(),
+            url: url.to_string(),
+        };
+        return Some((link, 1 + close + 2 + url_end + 1));
+    }
+
+    // Bare URL or email address: one word, minus trailing punctuation
+    let word_end = text.find(char::is_whitespace).unwrap_or(text.len());
+    let word = text[..word_end].trim_end_matches(['.', ',', ';', ':', '!', '?', ')']);
+    let url = if word.starts_with("https://") || word.starts_with("http://") {
+        word.to_string()
+    } else if is_email(word) {
+        format!("mailto:{word}")
+    } else {
+        return None;
+    };
+    let link = Link {
+        text: word.to_string(),
+        url,
+    };
+    Some((link, word.len()))
+}
+
+fn is_email(word: &str) -> bool {
+    let Some((user, domain)) = word.split_once('@') else {
+        return false;
+    };
+    let valid = |c: char| c.is_ascii_alphanumeric() || "._%+-".contains(c);
+    !user.is_empty()
+        && user.chars().all(valid)
+        && domain.contains('.')
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+        && domain.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+}
+
+/// Links in markdown content, in order of first appearance
+pub fn links(content: &str) -> Vec<Link> {
+    let mut found: Vec<Link> = Vec::new();
+    for line in content.lines() {
+        for (_, link) in split_links(line) {
+            if let Some(link) = link.filter(|l| !found.iter().any(|f| f.url == l.url)) {
+                found.push(link);
+            }
+        }
+    }
+    found
 }
 
 /// Render multiple markdown lines
@@ -83,7 +288,12 @@ pub fn render_lines(content: &str) -> Vec<Line<'static>> {
 
 /// Markdown content holder for rendering with word wrapping support
 pub struct Markdown {
-    lines: Vec<String>,
+    lines: Vec<Line<'static>>,
+    /// URLs of the links on each line
+    links: Vec<SpanLinks>,
+    /// Text of each source line and the lines it wrapped to
+    passages: Vec<Passage>,
+    headings: Vec<Heading>,
     wrap_width: Option<usize>,
 }
 
@@ -91,16 +301,40 @@ impl Markdown {
     /// Create from raw markdown content (no wrapping)
     pub fn new(content: &str) -> Self {
         Self {
-            lines: content.lines().map(|s| s.to_string()).collect(),
             wrap_width: None,
+            ..Self::wrapped(content, 0)
         }
     }
 
     /// Create with word wrapping at specified width
     pub fn wrapped(content: &str, width: usize) -> Self {
-        let wrapped = wrap_text(content, width);
+        let mut lines = Vec::new();
+        let mut links = Vec::new();
+        let mut passages = Vec::new();
+        let mut found = Vec::new();
+        for line in content.lines() {
+            if let Some((level, title)) = heading(line) {
+                found.push(Heading {
+                    level,
+                    title: title.to_string(),
+                    line: lines.len(),
+                });
+            }
+            let start = lines.len();
+            for (row, row_links) in render_wrapped(line, width) {
+                lines.push(row);
+                links.push(row_links);
+            }
+            passages.push(Passage {
+                text: plain_text(line),
+                rows: start..lines.len(),
+            });
+        }
         Self {
-            lines: wrapped,
+            lines,
+            links,
+            passages,
+            headings: found,
             wrap_width: Some(width),
         }
     }
@@ -124,15 +358,128 @@ impl Markdown {
 
     /// Render a range of lines (for viewport scrolling)
     pub fn render_range(&self, start: usize, end: usize) -> Vec<Line<'static>> {
-        self.lines[start.min(self.lines.len())..end.min(self.lines.len())]
-            .iter()
-            .map(|s| render_line(s))
-            .collect()
+        self.lines[start.min(self.lines.len())..end.min(self.lines.len())].to_vec()
     }
 
     /// Render all lines
-    #[allow(dead_code)]
     pub fn render_all(&self) -> Vec<Line<'static>> {
-        self.lines.iter().map(|s| render_line(s)).collect()
+        self.lines.clone()
+    }
+
+    /// Make the links clickable in lines from `start` on, drawn in `area`
+    pub fn place_links(&self, area: Rect, start: usize) {
+        let start = start.min(self.lines.len());
+        let links = self.links[start..].iter().map(Vec::as_slice);
+        links::place(area, self.lines[start..].iter().zip(links));
+    }
+
+    /// Source lines with the lines they wrapped to, for in-page find
+    pub fn passages(&self) -> &[Passage] {
+        &self.passages
+    }
+
+    /// Headings with their line after wrapping, for a table of contents
+    pub fn headings(&self) -> Vec<Heading> {
+        self.headings.clone()
+    }
+}
+
+/// Headings among `lines`, numbered from the first line
+pub fn headings<'a>(lines: impl IntoIterator<Item = &'a str>) -> Vec<Heading> {
+    lines
+        .into_iter()
+        .enumerate()
+        .filter_map(|(line, text)| {
+            let (level, title) = heading(text)?;
+            Some(Heading {
+                level,
+                title: title.to_string(),
+                line,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_links_are_split_out_of_text() {
+        let segments = split_links("See [the docs](https://example.com/docs), or mail me@example.com.");
+        let texts: Vec<&str> = segments.iter().map(|(text, _)| text.as_str()).collect();
+        assert_eq!(
+            texts,
+            ["See ", "[the docs](https://example.com/docs)", ", or mail ", "me@example.com", "."]
+        );
+        assert_eq!(
+            segments[1].1,
+            Some(Link {
+                text: "the docs".to_string(),
+                url: "https://example.com/docs".to_string(),
+            })
+        );
+        assert_eq!(segments[3].1.as_ref().unwrap().url, "mailto:me@example.com");
+
+        let line = render_line("- Source: https://github.com/example/repo");
+        let link = line.spans.iter().find(|s| s.style == styles::link()).unwrap();
+        assert_eq!(link.content, "https://github.com/example/repo");
+
+        assert!(links("no links @ all [here]").is_empty());
+    }
+
+    #[test]
+    fn test_headings_are_numbered_by_line() {
+        let markdown = Markdown::new("# Name\nintro\n## Experience\n### Acme\n#hashtag\n#### deep");
+        let headings = markdown.headings();
+        let found: Vec<(usize, &str, usize)> = headings
+            .iter()
+            .map(|h| (h.level, h.title.as_str(), h.line))
+            .collect();
+        assert_eq!(found, [(1, "Name", 0), (2, "Experience", 2), (3, "Acme", 3)]);
+    }
+
+    #[test]
+    fn test_bold_list_items_close_the_bold() {
+        let line = render_line("- **Blueprinter** — a Rails engine");
+        let texts: Vec<&str> = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(texts, ["  • ", "Blueprinter", " — a Rails engine"]);
+        assert!(line.spans[1].style.add_modifier.contains(Modifier::BOLD));
+
+        assert_eq!(render_line("- **").to_string(), "  • ");
+        assert_eq!(render_line("2. second").to_string(), "  2. second");
+        assert_eq!(render_line("**").to_string(), "**");
+    }
+
+    #[test]
+    fn test_wrapping_follows_rendered_text() {
+        let rows = render_wrapped("- **Bold** then [a link](https://example.com) here", 18);
+        let texts: Vec<String> = rows.iter().map(|(line, _)| line.to_string()).collect();
+        assert_eq!(texts, ["  • Bold then a", "    link here"]);
+        assert_eq!(rows[0].0.spans[1].content, "Bold");
+        assert!(rows[0].0.spans[1].style.add_modifier.contains(Modifier::BOLD));
+
+        // Both parts of the wrapped link point at its URL
+        let url = "https://example.com".to_string();
+        assert_eq!(rows[0].1, [(3, url.clone())]);
+        assert_eq!(rows[0].0.spans[3].content, "a");
+        assert_eq!(rows[1].1, [(1, url)]);
+        assert_eq!(rows[1].0.spans[1].content, "link");
+
+        assert_eq!(render_wrapped("---", 12)[0].0.width(), 12);
+        assert_eq!(render_wrapped("---", 80)[0].0.width(), 40);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_wrapped_lines_fit_the_width(
+            line in "(\\PC|# |- |\\*\\*|1\\. |:\\*\\*|\\]\\(|https://|---|  ){0,24}",
+            width in 5usize..60,
+        ) {
+            for (row, _) in render_wrapped(&line, width) {
+                prop_assert!(row.width() <= width, "{:?} -> {}", line, row);
+            }
+        }
     }
 }
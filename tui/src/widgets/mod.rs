@@ -1,20 +1,38 @@
 //! Reusable widget components
 
+mod breakpoint;
 mod code_block;
+mod find;
+mod hit_map;
 mod loading;
 mod markdown;
 mod page_layout;
+mod qr_code;
+mod scroll_list;
 mod selectable_item;
+mod status_bar;
 mod tag_list;
 mod text_input;
 mod text_wrap;
-mod typewriter;
+mod toc;
 
+pub use breakpoint::{render_preview, render_too_small, Breakpoint};
 pub use code_block::CodeBlock;
+pub use find::{highlight_matches, FindState, Passage};
+pub use hit_map::{stacked, HitMap};
 pub use loading::LoadingState;
-pub use markdown::{render_line as render_markdown_line, Markdown};
+pub use markdown::{
+    heading as markdown_heading, headings as markdown_headings, links as markdown_links,
+    plain_text as markdown_text, render_line as render_markdown_line,
+    render_wrapped as render_markdown_wrapped,
+    split_links as split_markdown_links, Heading, Link, Markdown,
+};
 pub use page_layout::PageLayout;
+pub use qr_code::QrCode;
+pub use scroll_list::ScrollList;
 pub use selectable_item::{accent_bold, featured_badge, SelectableItem};
+pub use status_bar::{Severity, StatusBar};
 pub use tag_list::TagList;
 pub use text_input::TextInput;
 pub use text_wrap::wrap_text;
+pub use toc::Toc;
@@ -3,17 +3,20 @@
 use ratatui::{
     layout::{Constraint, Layout, Rect},
     style::Style,
+    text::{Line, Span},
     widgets::{Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
     Frame,
 };
 
+use super::Breakpoint;
 use crate::styles::{self, COLOR_PRIMARY, COLOR_SUBTLE};
 
 /// A standard page layout with header, dividers, content area, and help text
 ///
 /// # Example
-/// ```
+/// ```ignore
 /// PageLayout::new("📝 Blog")
+///     .breadcrumbs(["Home"])
 ///     .help("↑/k up • ↓/j down • esc back")
 ///     .scroll_state(viewport, content_length, viewport_height)
 ///     .render(frame, area, |f, content_area| {
@@ -22,6 +25,7 @@ use crate::styles::{self, COLOR_PRIMARY, COLOR_SUBTLE};
 /// ```
 pub struct PageLayout {
     title: String,
+    breadcrumbs: Vec<String>,
     help_text: String,
     scroll_state: Option<ScrollState>,
 }
@@ -38,11 +42,22 @@ impl PageLayout {
     pub fn new(title: impl Into<String>) -> Self {
         Self {
             title: title.into(),
+            breadcrumbs: Vec::new(),
             help_text: String::new(),
             scroll_state: None,
         }
     }
 
+    /// Set the parent pages shown before the title, e.g. `Home › Blog ›`
+    pub fn breadcrumbs<I, S>(mut self, trail: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.breadcrumbs = trail.into_iter().map(Into::into).collect();
+        self
+    }
+
     /// Set the help text shown at the bottom
     pub fn help(mut self, text: impl Into<String>) -> Self {
         self.help_text = text.into();
@@ -79,20 +94,19 @@ impl PageLayout {
     where
         F: FnOnce(&mut Frame, Rect),
     {
-        let chunks = Layout::vertical([
-            Constraint::Length(1), // Title
-            Constraint::Length(1), // Divider
-            Constraint::Min(1),    // Content
-            Constraint::Length(1), // Divider
-            Constraint::Length(1), // Help
-        ])
-        .split(area);
+        let chunks = Self::chunks(area);
 
         // Header
-        let header = Paragraph::new(self.title).style(styles::title());
+        let mut header_spans: Vec<Span> = Vec::new();
+        for crumb in self.breadcrumbs {
+            header_spans.push(Span::styled(crumb, styles::muted()));
+            header_spans.push(Span::styled(" › ", styles::border()));
+        }
+        header_spans.push(Span::styled(self.title, styles::title()));
+        let header = Paragraph::new(Line::from(header_spans));
         frame.render_widget(header, chunks[0]);
 
-        // Top divider
+        // Top divider (dropped with the bottom one on short terminals)
         let divider = Paragraph::new(styles::divider(area.width)).style(styles::border());
         frame.render_widget(divider, chunks[1]);
 
@@ -150,11 +164,9 @@ impl PageLayout {
         // Help text with optional scroll percentage
         let help_with_scroll = if let Some(scroll) = &self.scroll_state {
             let max_scroll = scroll.content_length.saturating_sub(scroll.viewport_height);
-            if max_scroll > 0 {
-                let percentage = (scroll.position * 100) / max_scroll;
-                format!("{} [{}%]", self.help_text, percentage.min(100))
-            } else {
-                self.help_text
+            match (scroll.position * 100).checked_div(max_scroll) {
+                Some(percentage) => format!("{} [{}%]", self.help_text, percentage.min(100)),
+                None => self.help_text,
             }
         } else {
             self.help_text
@@ -166,14 +178,20 @@ impl PageLayout {
 
     /// Get the content area rect without rendering (useful for calculating scroll bounds)
     pub fn content_area(area: Rect) -> Rect {
-        let chunks = Layout::vertical([
-            Constraint::Length(1), // Title
-            Constraint::Length(1), // Divider
-            Constraint::Min(1),    // Content
-            Constraint::Length(1), // Divider
-            Constraint::Length(1), // Help
+        Self::chunks(area)[2]
+    }
+
+    /// Title, divider, content, divider and help rows; compact layouts
+    /// have no dividers
+    fn chunks(area: Rect) -> [Rect; 5] {
+        let divider = if Breakpoint::of(area).is_compact() { 0 } else { 1 };
+        Layout::vertical([
+            Constraint::Length(1),       // Title
+            Constraint::Length(divider), // Divider
+            Constraint::Min(1),          // Content
+            Constraint::Length(divider), // Divider
+            Constraint::Length(1),       // Help
         ])
-        .split(area);
-        chunks[2]
+        .areas(area)
     }
 }
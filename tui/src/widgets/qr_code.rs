@@ -0,0 +1,100 @@
+//! QrCode widget - renders a QR code with Unicode half blocks
+
+use qrcode::{Color as Module, EcLevel};
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+
+/// Light modules around the code; scanners need a margin to find it
+const QUIET_ZONE: usize = 2;
+
+/// Colors of the code itself, not the theme, so it scans on any background
+const DARK: Color = Color::Rgb(0, 0, 0);
+const LIGHT: Color = Color::Rgb(255, 255, 255);
+
+/// A QR code drawn two modules per cell with `▀`, `▄` and `█`
+///
+/// # Example
+/// ```ignore
+/// if let Some(qr) = QrCode::new("https://example.com") {
+///     let lines = qr.to_lines();
+/// }
+/// ```
+pub struct QrCode {
+    /// Modules including the quiet zone, row by row; `true` is dark
+    modules: Vec<Vec<bool>>,
+}
+
+impl QrCode {
+    /// Encode `data`, or `None` if it is too long for a QR code
+    pub fn new(data: &str) -> Option<Self> {
+        let code = qrcode::QrCode::with_error_correction_level(data, EcLevel::L).ok()?;
+        let width = code.width();
+        let colors = code.to_colors();
+        let size = width + 2 * QUIET_ZONE;
+        let modules = (0..size)
+            .map(|y| {
+                (0..size)
+                    .map(|x| {
+                        let (x, y) = (x.wrapping_sub(QUIET_ZONE), y.wrapping_sub(QUIET_ZONE));
+                        x < width && y < width && colors[y * width + x] == Module::Dark
+                    })
+                    .collect()
+            })
+            .collect();
+        Some(Self { modules })
+    }
+
+    /// Columns needed to draw the code
+    pub fn width(&self) -> u16 {
+        self.modules.len() as u16
+    }
+
+    /// Rows needed to draw the code
+    pub fn height(&self) -> u16 {
+        self.modules.len().div_ceil(2) as u16
+    }
+
+    /// Convert to lines for rendering, one per pair of module rows
+    pub fn to_lines(&self) -> Vec<Line<'static>> {
+        let style = Style::default().fg(DARK).bg(LIGHT);
+        self.modules
+            .chunks(2)
+            .map(|rows| {
+                let text: String = (0..rows[0].len())
+                    .map(|x| {
+                        let top = rows[0][x];
+                        let bottom = rows.get(1).is_some_and(|row| row[x]);
+                        match (top, bottom) {
+                            (true, true) => '█',
+                            (true, false) => '▀',
+                            (false, true) => '▄',
+                            (false, false) => ' ',
+                        }
+                    })
+                    .collect();
+                Line::from(Span::styled(text, style))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_qr_code_has_finder_patterns_and_quiet_zone() {
+        let qr = QrCode::new("https://example.com").unwrap();
+        // Version 2 is 25 modules wide
+        assert_eq!(qr.width(), 25 + 2 * QUIET_ZONE as u16);
+        assert_eq!(qr.height(), qr.width().div_ceil(2));
+
+        let lines: Vec<String> = qr.to_lines().iter().map(|line| line.to_string()).collect();
+        assert_eq!(lines.len(), qr.height() as usize);
+        assert!(lines[0].chars().all(|c| c == ' '));
+        // The top of the top-left finder pattern: a dark row over a ring
+        assert!(lines[1].starts_with("  █▀▀▀▀▀█ "));
+
+        assert!(QrCode::new(&"x".repeat(8000)).is_none());
+    }
+}
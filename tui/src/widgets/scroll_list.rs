@@ -0,0 +1,85 @@
+//! Scroll state for lists of multi-line items that keeps the cursor visible
+
+use std::ops::Range;
+
+/// Item offset of a scrolling list, adjusted on each render
+///
+/// # Example
+/// ```ignore
+/// let heights: Vec<usize> = items.iter().map(|item| item.len()).collect();
+/// let shown = self.scroll.follow(&heights, self.cursor, area.height as usize);
+/// let lines: Vec<Line> = items[shown].iter().flatten().cloned().collect();
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct ScrollList {
+    offset: usize,
+    /// Items that fit on screen at the last render
+    page: usize,
+}
+
+impl ScrollList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Scroll so the `cursor` item is fully visible in `height` lines
+    ///
+    /// `heights` holds the line count of each item. Returns the range of
+    /// items to draw.
+    pub fn follow(&mut self, heights: &[usize], cursor: usize, height: usize) -> Range<usize> {
+        let cursor = cursor.min(heights.len().saturating_sub(1));
+        if cursor < self.offset {
+            self.offset = cursor;
+        }
+        while self.offset < cursor && heights[self.offset..=cursor].iter().sum::<usize>() > height
+        {
+            self.offset += 1;
+        }
+        self.offset = self.offset.min(heights.len().saturating_sub(1));
+
+        let mut used = 0;
+        let mut end = self.offset;
+        while end < heights.len() && used < height {
+            used += heights[end];
+            end += 1;
+        }
+        // A partly shown last item does not count towards a page
+        let whole = if used > height { end - self.offset - 1 } else { end - self.offset };
+        self.page = whole.max(1);
+
+        self.offset..end
+    }
+
+    /// Number of items a page up/down should move by
+    pub fn page(&self) -> usize {
+        self.page.max(1)
+    }
+
+    /// First line shown and total line count, for the scrollbar
+    pub fn position(&self, heights: &[usize]) -> (usize, usize) {
+        let first = heights[..self.offset.min(heights.len())].iter().sum();
+        (first, heights.iter().sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_follow_keeps_cursor_visible() {
+        let heights = [3; 10];
+        let mut scroll = ScrollList::new();
+
+        assert_eq!(scroll.follow(&heights, 0, 10), 0..4);
+        assert_eq!(scroll.page(), 3);
+
+        // Cursor below the screen scrolls just far enough
+        assert_eq!(scroll.follow(&heights, 5, 10), 3..7);
+        assert_eq!(scroll.position(&heights), (9, 30));
+
+        // Cursor above the screen scrolls back up
+        assert_eq!(scroll.follow(&heights, 1, 10), 1..5);
+        assert_eq!(scroll.follow(&heights, 9, 10).end, 10);
+    }
+}
@@ -10,7 +10,7 @@ use crate::styles;
 /// A selectable list item with cursor and highlight styling
 ///
 /// # Example
-/// ```
+/// ```ignore
 /// SelectableItem::new("Blog Post Title")
 ///     .selected(is_selected)
 ///     .suffix(Span::styled("2026-02-05", styles::muted()))
@@ -93,50 +93,6 @@ impl SelectableItem {
     }
 }
 
-/// Helper to create a menu item with hotkey display
-pub struct MenuItem {
-    key: char,
-    label: String,
-    description: String,
-    selected: bool,
-}
-
-impl MenuItem {
-    /// Create a new menu item
-    pub fn new(key: char, label: impl Into<String>, description: impl Into<String>) -> Self {
-        Self {
-            key,
-            label: label.into(),
-            description: description.into(),
-            selected: false,
-        }
-    }
-
-    /// Set whether this item is selected
-    pub fn selected(mut self, selected: bool) -> Self {
-        self.selected = selected;
-        self
-    }
-
-    /// Convert to a Line for rendering
-    pub fn to_line(&self) -> Line<'static> {
-        if self.selected {
-            Line::from(vec![
-                Span::styled("▸ ", styles::cursor()),
-                Span::styled(format!(" {} ", self.label), styles::selected()),
-                Span::styled(format!(" {}", self.description), styles::muted()),
-            ])
-        } else {
-            Line::from(vec![
-                Span::raw("  "),
-                Span::styled(format!("[{}]", self.key), styles::accent()),
-                Span::styled(format!(" {}", self.label), styles::menu_item()),
-                Span::styled(format!(" {}", self.description), styles::muted()),
-            ])
-        }
-    }
-}
-
 /// Create a featured badge span
 pub fn featured_badge() -> Span<'static> {
     Span::styled(" ★", styles::warning())
@@ -0,0 +1,118 @@
+//! StatusBar widget - one line with a transient message and session info
+
+use ratatui::{
+    layout::Rect,
+    style::Modifier,
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::styles;
+
+/// How serious a status message is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    fn icon(&self) -> &'static str {
+        match self {
+            Severity::Info => "✓",
+            Severity::Warning => "⚠",
+            Severity::Error => "✗",
+        }
+    }
+
+    fn style(&self) -> ratatui::style::Style {
+        match self {
+            Severity::Info => styles::accent(),
+            Severity::Warning => styles::warning(),
+            Severity::Error => styles::error().add_modifier(Modifier::BOLD),
+        }
+    }
+}
+
+/// Status line: the message on the left, persistent items on the right
+///
+/// # Example
+/// ```ignore
+/// StatusBar::new()
+///     .message(Some(("Copied link", Severity::Info)))
+///     .item(Span::raw("⏱ 04:12"))
+///     .render(frame, bar_area);
+/// ```
+#[derive(Default)]
+pub struct StatusBar<'a> {
+    message: Option<(&'a str, Severity)>,
+    items: Vec<Span<'static>>,
+}
+
+impl<'a> StatusBar<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the transient message
+    pub fn message(mut self, message: Option<(&'a str, Severity)>) -> Self {
+        self.message = message;
+        self
+    }
+
+    /// Add a persistent item, shown right-aligned in order
+    pub fn item(mut self, item: Span<'static>) -> Self {
+        self.items.push(item);
+        self
+    }
+
+    pub fn render(self, frame: &mut Frame, area: Rect) {
+        let mut right: Vec<Span> = Vec::new();
+        for item in self.items {
+            if !right.is_empty() {
+                right.push(Span::styled(" • ", styles::muted()));
+            }
+            right.push(item);
+        }
+        right.push(Span::raw(" "));
+        let right_width: usize = right.iter().map(|s| s.content.width()).sum();
+
+        let mut left = vec![Span::raw(" ")];
+        if let Some((text, severity)) = self.message {
+            // The message gives way to the persistent items on narrow screens
+            let room = (area.width as usize).saturating_sub(right_width + 4);
+            left.push(Span::styled(format!("{} ", severity.icon()), severity.style()));
+            left.push(Span::styled(truncate(text, room), severity.style()));
+        }
+        let left_width: usize = left.iter().map(|s| s.content.width()).sum();
+
+        let gap = (area.width as usize).saturating_sub(left_width + right_width);
+        let mut spans = left;
+        spans.push(Span::raw(" ".repeat(gap)));
+        spans.extend(right);
+
+        frame.render_widget(
+            Paragraph::new(Line::from(spans)).style(styles::status_bar()),
+            area,
+        );
+    }
+}
+
+/// Cut `text` to `width` columns, ending in an ellipsis when cut
+fn truncate(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    let mut out = String::new();
+    for c in text.chars() {
+        if out.width() + c.to_string().width() + 1 > width {
+            break;
+        }
+        out.push(c);
+    }
+    out.push('…');
+    out
+}
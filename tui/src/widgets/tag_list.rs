@@ -1,5 +1,6 @@
 //! TagList widget - renders a row of styled tag badges
 
+use ratatui::style::Style;
 use ratatui::text::{Line, Span};
 
 use crate::styles;
@@ -7,13 +8,14 @@ use crate::styles;
 /// A list of tags rendered as styled badges
 ///
 /// # Example
-/// ```
+/// ```ignore
 /// let tags = TagList::new(&["Go", "TUI", "CLI"]);
 /// let line = tags.to_line();
 /// ```
 pub struct TagList<'a> {
     tags: Vec<&'a str>,
     prefix: Option<Span<'static>>,
+    focused: Option<usize>,
 }
 
 impl<'a> TagList<'a> {
@@ -23,6 +25,7 @@ impl<'a> TagList<'a> {
         Self {
             tags: tags.to_vec(),
             prefix: None,
+            focused: None,
         }
     }
 
@@ -31,6 +34,7 @@ impl<'a> TagList<'a> {
         Self {
             tags: tags.to_vec(),
             prefix: None,
+            focused: None,
         }
     }
 
@@ -45,6 +49,20 @@ impl<'a> TagList<'a> {
         self.prefix(Span::raw(" ".repeat(spaces)))
     }
 
+    /// Highlight the tag at `index`, e.g. while choosing one with the keyboard
+    pub fn focused(mut self, index: Option<usize>) -> Self {
+        self.focused = index;
+        self
+    }
+
+    fn style(&self, index: usize) -> Style {
+        if self.focused == Some(index) {
+            styles::tag_focused()
+        } else {
+            styles::tag()
+        }
+    }
+
     /// Convert to a Line for rendering
     pub fn to_line(&self) -> Line<'static> {
         let mut spans = Vec::new();
@@ -53,8 +71,8 @@ impl<'a> TagList<'a> {
             spans.push(prefix.clone());
         }
 
-        for tag in &self.tags {
-            spans.push(Span::styled(format!(" {} ", tag), styles::tag()));
+        for (i, tag) in self.tags.iter().enumerate() {
+            spans.push(Span::styled(format!(" {} ", tag), self.style(i)));
             spans.push(Span::raw(" "));
         }
 
@@ -65,8 +83,8 @@ impl<'a> TagList<'a> {
     pub fn to_spans(&self) -> Vec<Span<'static>> {
         let mut spans = Vec::new();
 
-        for tag in &self.tags {
-            spans.push(Span::styled(format!(" {} ", tag), styles::tag()));
+        for (i, tag) in self.tags.iter().enumerate() {
+            spans.push(Span::styled(format!(" {} ", tag), self.style(i)));
             spans.push(Span::raw(" "));
         }
 
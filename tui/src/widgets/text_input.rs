@@ -12,7 +12,7 @@ use crate::styles;
 /// A styled text input field with label
 ///
 /// # Example
-/// ```
+/// ```ignore
 /// TextInput::new("Email", &self.email)
 ///     .focused(self.focused_field == ContactField::Email)
 ///     .render(frame, area);
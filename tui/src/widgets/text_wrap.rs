@@ -1,8 +1,19 @@
 //! Text wrapping utilities for terminal display
 
+use ratatui::text::{Line, Span};
 use unicode_width::UnicodeWidthStr;
 
+use super::markdown_heading;
+
+/// Narrowest text column worth keeping indentation for
+const MIN_TEXT_WIDTH: usize = 10;
+
 /// Wrap text to fit within a given width while preserving words
+///
+/// No line is wider than `max_width` columns, except a single character
+/// that is wider on its own. Words longer than the width are broken across
+/// lines, and only whitespace is dropped. Heading markers (`# `) are not
+/// drawn, so they don't count towards the width of a heading.
 pub fn wrap_text(text: &str, max_width: usize) -> Vec<String> {
     if max_width == 0 {
         return vec![text.to_string()];
@@ -11,31 +22,27 @@ pub fn wrap_text(text: &str, max_width: usize) -> Vec<String> {
     let mut result = Vec::new();
 
     for line in text.lines() {
-        if line.is_empty() {
+        if line.trim().is_empty() {
             result.push(String::new());
             continue;
         }
 
-        // Don't wrap markdown headings, list items (they're usually short)
-        // or code fence markers
-        if line.starts_with('#') || line.starts_with("```") {
-            result.push(line.to_string());
+        if let Some((_, title)) = markdown_heading(line) {
+            let marker = &line[..line.len() - title.len()];
+            let mut wrapped = wrap_line(title, max_width, "");
+            wrapped[0].insert_str(0, marker);
+            result.extend(wrapped);
             continue;
         }
 
         // Preserve indentation for list items
         let (indent, content) = extract_indent(line);
-        let indent_width = UnicodeWidthStr::width(indent);
-        let effective_width = max_width.saturating_sub(indent_width);
-
-        if effective_width < 10 {
-            // Too narrow to wrap meaningfully
-            result.push(line.to_string());
-            continue;
+        if max_width.saturating_sub(indent.width()) < MIN_TEXT_WIDTH {
+            // Too narrow to keep the indentation
+            result.extend(wrap_line(content, max_width, ""));
+        } else {
+            result.extend(wrap_line(content, max_width, indent));
         }
-
-        let wrapped = wrap_line(content, effective_width, indent);
-        result.extend(wrapped);
     }
 
     result
@@ -48,51 +55,179 @@ fn extract_indent(line: &str) -> (&str, &str) {
     (&line[..indent_len], trimmed)
 }
 
-/// Wrap a single line of text, preserving the given indent on continuation lines
+/// Wrap a single line of text, preserving the given indent on every line
 fn wrap_line(text: &str, max_width: usize, indent: &str) -> Vec<String> {
     let mut result = Vec::new();
     let mut current_line = String::from(indent);
-    let mut current_width = UnicodeWidthStr::width(indent);
-    let mut is_first_line = true;
+    let mut has_words = false;
 
     for word in text.split_whitespace() {
-        let word_width = UnicodeWidthStr::width(word);
-
-        if current_width + word_width + 1 > max_width && !is_first_line {
-            // Start a new line
-            if !current_line.trim().is_empty() {
-                result.push(current_line);
+        if has_words {
+            if current_line.width() + 1 + word.width() <= max_width {
+                current_line.push(' ');
+                current_line.push_str(word);
+                continue;
             }
-            current_line = format!("{}{}", indent, word);
-            current_width = UnicodeWidthStr::width(indent) + word_width;
-        } else if current_line.trim().is_empty() {
-            // First word on line
-            current_line = format!("{}{}", indent, word);
-            current_width = UnicodeWidthStr::width(indent) + word_width;
-            is_first_line = false;
-        } else {
-            // Add word to current line
-            current_line.push(' ');
+            // Start a new line
+            result.push(std::mem::replace(&mut current_line, indent.to_string()));
+        }
+        has_words = true;
+
+        if current_line.width() + word.width() <= max_width {
             current_line.push_str(word);
-            current_width += word_width + 1;
-            is_first_line = false;
+            continue;
+        }
+
+        // Too long for a line of its own: break it between characters
+        let mut has_chars = false;
+        for c in word.chars() {
+            current_line.push(c);
+            if current_line.width() > max_width && has_chars {
+                current_line.pop();
+                result.push(std::mem::replace(&mut current_line, format!("{indent}{c}")));
+            }
+            has_chars = true;
         }
     }
 
-    if !current_line.is_empty() {
-        result.push(current_line);
+    result.push(current_line);
+    result
+}
+
+/// Wrap styled text to `max_width` columns after `marker`, e.g. a bullet
+///
+/// Breaks lines like [`wrap_text`] but keeps the style of every span, so
+/// rendered Markdown can be wrapped without splitting its syntax. Rows after
+/// the first line up with the text after the marker, unless that leaves
+/// fewer than 10 columns; then they start at the left edge and the marker
+/// loses its leading whitespace.
+///
+/// Each row comes with the index in `text` of the span each of its spans
+/// was cut from, `None` for the marker and indentation.
+pub fn wrap_spans(
+    marker: Vec<Span<'static>>,
+    text: Vec<Span<'static>>,
+    max_width: usize,
+) -> Vec<(Line<'static>, Vec<Option<usize>>)> {
+    let marker_width: usize = marker.iter().map(Span::width).sum();
+    if max_width == 0 {
+        let sources = [vec![None; marker.len()], (0..text.len()).map(Some).collect()].concat();
+        return vec![(Line::from([marker, text].concat()), sources)];
+    }
+    let hanging = max_width.saturating_sub(marker_width) >= MIN_TEXT_WIDTH;
+    let mut marker = marker;
+    if !hanging {
+        for span in &mut marker {
+            span.content = span.content.trim_start().to_string().into();
+            if !span.content.is_empty() {
+                break;
+            }
+        }
+        marker.retain(|span| !span.content.is_empty());
     }
+    let indent = if hanging { marker_width } else { 0 };
+    let first_width = max_width
+        .saturating_sub(marker.iter().map(Span::width).sum())
+        .max(1);
 
-    if result.is_empty() {
-        result.push(String::new());
+    let mut rows = Vec::new();
+    let mut row: StyledChars = Vec::new();
+    let mut width = first_width;
+    for (gap, word) in styled_words(&text) {
+        if !row.is_empty() {
+            let mut joined = row.clone();
+            joined.push((' ', gap.unwrap_or(word[0].1)));
+            joined.extend(&word);
+            if styled_width(&joined, &text) <= width {
+                row = joined;
+                continue;
+            }
+            rows.push(std::mem::take(&mut row));
+            width = max_width - indent;
+        }
+
+        if styled_width(&word, &text) <= width {
+            row = word;
+            continue;
+        }
+
+        // Too long for a line of its own: break it between characters
+        for c in word {
+            row.push(c);
+            if styled_width(&row, &text) > width && row.len() > 1 {
+                row.pop();
+                rows.push(std::mem::replace(&mut row, vec![c]));
+                width = max_width - indent;
+            }
+        }
     }
+    rows.push(row);
 
-    result
+    rows.into_iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let mut spans = match i {
+                0 => marker.clone(),
+                _ if indent > 0 => vec![Span::raw(" ".repeat(indent))],
+                _ => Vec::new(),
+            };
+            let mut sources = vec![None; spans.len()];
+            for (source, span) in styled_spans(&row, &text) {
+                spans.push(span);
+                sources.push(Some(source));
+            }
+            (Line::from(spans), sources)
+        })
+        .collect()
+}
+
+/// Characters tagged with the index of their span
+type StyledChars = Vec<(char, usize)>;
+
+/// Words of `text` as tagged characters, each with the span of the
+/// whitespace before it
+fn styled_words(text: &[Span]) -> Vec<(Option<usize>, StyledChars)> {
+    let mut words = Vec::new();
+    let mut gap = None;
+    let mut word: Option<(Option<usize>, StyledChars)> = None;
+    for (i, span) in text.iter().enumerate() {
+        for c in span.content.chars() {
+            if c.is_whitespace() {
+                words.extend(word.take());
+                gap.get_or_insert(i);
+            } else {
+                word.get_or_insert_with(|| (gap.take(), Vec::new())).1.push((c, i));
+            }
+        }
+    }
+    words.extend(word);
+    words
+}
+
+/// Tagged characters regrouped into spans, each with its index in `text`
+fn styled_spans(chars: &[(char, usize)], text: &[Span]) -> Vec<(usize, Span<'static>)> {
+    let mut spans: Vec<(usize, String)> = Vec::new();
+    for &(c, i) in chars {
+        match spans.last_mut() {
+            Some((last, content)) if *last == i => content.push(c),
+            _ => spans.push((i, c.to_string())),
+        }
+    }
+    spans
+        .into_iter()
+        .map(|(i, content)| (i, Span::styled(content, text[i].style)))
+        .collect()
+}
+
+/// Width of tagged characters as drawn, span by span
+fn styled_width(chars: &[(char, usize)], text: &[Span]) -> usize {
+    styled_spans(chars, text).iter().map(|(_, span)| span.width()).sum()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn test_wrap_short_line() {
@@ -103,12 +238,99 @@ mod tests {
     #[test]
     fn test_wrap_long_line() {
         let result = wrap_text("This is a longer line that should wrap", 20);
-        assert_eq!(result.len(), 2);
+        assert_eq!(result, vec!["This is a longer", "line that should", "wrap"]);
     }
 
     #[test]
     fn test_preserve_heading() {
-        let result = wrap_text("# This is a heading", 10);
+        let result = wrap_text("# This is a heading", 17);
         assert_eq!(result, vec!["# This is a heading"]);
     }
+
+    #[test]
+    fn test_wrap_long_heading() {
+        let result = wrap_text("## A heading that is too long", 14);
+        assert_eq!(result, vec!["## A heading that", "is too long"]);
+    }
+
+    #[test]
+    fn test_break_long_word() {
+        let result = wrap_text("see https://example.com/a/long/path", 12);
+        assert_eq!(result, vec!["see", "https://exam", "ple.com/a/lo", "ng/path"]);
+        assert_eq!(wrap_text("日本語", 3), vec!["日", "本", "語"]);
+    }
+
+    #[test]
+    fn test_narrow_width_drops_indent() {
+        let result = wrap_text("    - indented item", 12);
+        assert_eq!(result, vec!["- indented", "item"]);
+    }
+
+    #[test]
+    fn test_wrap_spans_hangs_under_the_marker() {
+        use ratatui::style::{Style, Stylize};
+
+        let bold = Style::new().bold();
+        let marker = vec![Span::raw("  • ")];
+        let text = vec![Span::styled("Bold words", bold), Span::raw(" and plain text")];
+        let rows = wrap_spans(marker.clone(), text.clone(), 18);
+        let texts: Vec<String> = rows.iter().map(|(line, _)| line.to_string()).collect();
+        assert_eq!(texts, ["  • Bold words and", "    plain text"]);
+        assert_eq!(rows[0].1, [None, Some(0), Some(1)]);
+        assert_eq!(rows[1].1, [None, Some(1)]);
+
+        // A word split across rows keeps its style on both
+        let rows = wrap_spans(Vec::new(), vec![Span::styled("abcdefgh", bold)], 5);
+        assert_eq!(rows[1].0.spans, [Span::styled("fgh", bold)]);
+        assert_eq!(rows[1].1, [Some(0)]);
+
+        // Too narrow to hang: later rows start at the edge
+        let texts: Vec<String> = wrap_spans(marker, text, 12)
+            .iter()
+            .map(|(line, _)| line.to_string())
+            .collect();
+        assert_eq!(texts, ["• Bold words", "and plain", "text"]);
+    }
+
+    /// Markdown-ish lines mixing markers, wide and zero-width characters
+    fn markdown() -> impl Strategy<Value = String> {
+        let token = prop_oneof![
+            "\\PC{0,12}",
+            Just("\n".to_string()),
+            Just("  ".to_string()),
+            Just("\t".to_string()),
+            Just("# ".to_string()),
+            Just("### ".to_string()),
+            Just("- **".to_string()),
+            Just("**".to_string()),
+            Just("```".to_string()),
+            Just("日本語".to_string()),
+            Just("e\u{301}".to_string()),
+            Just("👩\u{200d}💻".to_string()),
+        ];
+        prop::collection::vec(token, 0..24).prop_map(|tokens| tokens.concat())
+    }
+
+    fn non_whitespace(text: &str) -> String {
+        text.chars().filter(|c| !c.is_whitespace()).collect()
+    }
+
+    proptest! {
+        #[test]
+        fn prop_wrapped_lines_fit_width(text in markdown(), width in 1usize..60) {
+            for line in wrap_text(&text, width) {
+                let drawn = markdown_heading(&line).map_or(line.as_str(), |(_, title)| title);
+                prop_assert!(
+                    drawn.width() <= width || drawn.trim().chars().count() == 1,
+                    "{line:?} is wider than {width}"
+                );
+            }
+        }
+
+        #[test]
+        fn prop_wrapping_keeps_non_whitespace(text in markdown(), width in 1usize..60) {
+            let wrapped = wrap_text(&text, width).concat();
+            prop_assert_eq!(non_whitespace(&wrapped), non_whitespace(&text));
+        }
+    }
 }
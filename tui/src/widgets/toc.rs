@@ -0,0 +1,257 @@
+//! Table of contents for long pages: a sidebar or a popup of headings
+
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use super::{stacked, Breakpoint, Heading, HitMap};
+use crate::styles;
+
+/// Width of the sidebar, including its rule
+const SIDEBAR_WIDTH: u16 = 32;
+
+/// Headings of a page and how they are shown
+///
+/// Wide terminals get a sidebar beside the text; elsewhere the contents
+/// open as a popup that takes the keys until an entry is chosen.
+///
+/// # Example
+/// ```ignore
+/// self.toc.set_headings(self.markdown.headings());
+/// let [sidebar, text] = self.toc.split(area, content_area);
+/// // ... draw the text in `text`
+/// self.toc.render_sidebar(frame, sidebar, self.viewport);
+/// self.toc.render_popup(frame, area, self.viewport);
+/// ```
+#[derive(Debug)]
+pub struct Toc {
+    headings: Vec<Heading>,
+    /// Sidebar shown on wide terminals
+    sidebar: bool,
+    /// Popup open on narrower terminals
+    popup: bool,
+    /// Whether the last render had room for the sidebar
+    wide: bool,
+    /// Highlighted entry of the popup
+    cursor: usize,
+    /// Entries on screen at the last render
+    hits: HitMap<usize>,
+}
+
+impl Default for Toc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Toc {
+    pub fn new() -> Self {
+        Self {
+            headings: Vec::new(),
+            sidebar: true,
+            popup: false,
+            wide: false,
+            cursor: 0,
+            hits: HitMap::new(),
+        }
+    }
+
+    pub fn set_headings(&mut self, headings: Vec<Heading>) {
+        self.headings = headings;
+        self.cursor = self.cursor.min(self.headings.len().saturating_sub(1));
+    }
+
+    /// Section the line `viewport` is in
+    pub fn current(&self, viewport: usize) -> Option<usize> {
+        self.headings.iter().rposition(|h| h.line <= viewport)
+    }
+
+    /// Line of the first heading below `viewport`
+    pub fn next_line(&self, viewport: usize) -> Option<usize> {
+        self.headings.iter().find(|h| h.line > viewport).map(|h| h.line)
+    }
+
+    /// Line of the last heading above `viewport`
+    pub fn prev_line(&self, viewport: usize) -> Option<usize> {
+        self.headings.iter().rev().find(|h| h.line < viewport).map(|h| h.line)
+    }
+
+    /// Line of heading `idx`, e.g. to keep a section in view after rewrapping
+    pub fn line(&self, idx: usize) -> Option<usize> {
+        self.headings.get(idx).map(|h| h.line)
+    }
+
+    /// Show or hide the sidebar, or open or close the popup
+    pub fn toggle(&mut self, viewport: usize) {
+        if self.wide {
+            self.sidebar = !self.sidebar;
+        } else if !self.headings.is_empty() {
+            self.popup = !self.popup;
+            self.cursor = self.current(viewport).unwrap_or(0);
+        }
+    }
+
+    /// Whether the popup is open and should get the keys
+    pub fn is_open(&self) -> bool {
+        self.popup && !self.wide
+    }
+
+    pub fn close(&mut self) {
+        self.popup = false;
+    }
+
+    pub fn cursor_up(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn cursor_down(&mut self) {
+        self.cursor = (self.cursor + 1).min(self.headings.len().saturating_sub(1));
+    }
+
+    /// Close the popup, returning the line of the highlighted heading
+    pub fn choose(&mut self) -> Option<usize> {
+        self.popup = false;
+        self.line(self.cursor)
+    }
+
+    /// Line of the heading clicked, closing the popup
+    pub fn click(&mut self, column: u16, row: u16) -> Option<usize> {
+        let idx = self.hits.at(column, row)?;
+        self.popup = false;
+        self.line(idx)
+    }
+
+    /// Split a page's content area into the sidebar and the text
+    ///
+    /// The sidebar is empty unless `area`, the whole page, is wide and the
+    /// sidebar hasn't been hidden.
+    pub fn split(&mut self, area: Rect, content: Rect) -> [Rect; 2] {
+        self.wide = Breakpoint::of(area) == Breakpoint::Wide;
+        let width = if self.wide && self.sidebar && !self.headings.is_empty() {
+            SIDEBAR_WIDTH
+        } else {
+            0
+        };
+        Layout::horizontal([Constraint::Length(width), Constraint::Min(1)])
+            .spacing(width.min(1))
+            .areas(content)
+    }
+
+    /// Contents beside the text, highlighting the section at `viewport`
+    pub fn render_sidebar(&mut self, frame: &mut Frame, area: Rect, viewport: usize) {
+        self.hits.clear();
+        if area.is_empty() {
+            return;
+        }
+
+        let block = Block::default()
+            .borders(Borders::RIGHT)
+            .border_style(styles::border());
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let current = self.current(viewport);
+        self.render_entries(frame, inner, current, current);
+    }
+
+    /// Popup of the contents over `area`, if open
+    pub fn render_popup(&mut self, frame: &mut Frame, area: Rect, viewport: usize) {
+        if !self.is_open() {
+            return;
+        }
+
+        let height = (self.headings.len() as u16 + 2).min(area.height.saturating_sub(2));
+        let width = 48.min(area.width.saturating_sub(4));
+        let [row] = Layout::vertical([Constraint::Length(height)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [popup] = Layout::horizontal([Constraint::Length(width)])
+            .flex(Flex::Center)
+            .areas(row);
+
+        let block = Block::default()
+            .title(Span::styled(" Contents ", styles::title()))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(styles::header_border());
+        let inner = block.inner(popup);
+        frame.render_widget(Clear, popup);
+        frame.render_widget(block, popup);
+
+        self.hits.clear();
+        self.render_entries(frame, inner, self.current(viewport), Some(self.cursor));
+    }
+
+    /// Draw one line per heading, indented by level and scrolled to keep
+    /// the highlighted entry visible; the current section is marked
+    fn render_entries(
+        &mut self,
+        frame: &mut Frame,
+        area: Rect,
+        current: Option<usize>,
+        highlight: Option<usize>,
+    ) {
+        let height = area.height as usize;
+        let skip = highlight.map_or(0, |i| (i + 1).saturating_sub(height));
+        let shown = skip..self.headings.len().min(skip + height);
+
+        let mut lines = Vec::new();
+        for (i, heading) in self.headings[shown.clone()].iter().enumerate() {
+            let i = skip + i;
+            let indent = "  ".repeat(heading.level.saturating_sub(1));
+            let marker = if Some(i) == current { "▸ " } else { "  " };
+            let room = (area.width as usize).saturating_sub(indent.len() + 2);
+            let title: String = heading.title.chars().take(room).collect();
+            let style = match heading.level {
+                1 => styles::text(),
+                2 => styles::subtitle(),
+                _ => styles::muted(),
+            };
+            let line = Line::from(vec![
+                Span::raw(indent),
+                Span::styled(marker, styles::cursor()),
+                Span::styled(title, style),
+            ]);
+            lines.push(if Some(i) == highlight {
+                line.style(styles::selected())
+            } else {
+                line
+            });
+        }
+
+        let rows = stacked(area, &vec![1; lines.len()]);
+        for (rect, idx) in rows.into_iter().zip(shown) {
+            self.hits.push(rect, idx);
+        }
+        frame.render_widget(Paragraph::new(lines), area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::Markdown;
+
+    #[test]
+    fn test_sections_follow_viewport() {
+        let mut toc = Toc::new();
+        let markdown = Markdown::new("# Top\na\nb\n## One\nc\n## Two\nd");
+        toc.set_headings(markdown.headings());
+
+        assert_eq!(toc.current(0), Some(0));
+        assert_eq!(toc.current(4), Some(1));
+        assert_eq!(toc.current(6), Some(2));
+        assert_eq!(toc.next_line(0), Some(3));
+        assert_eq!(toc.next_line(5), None);
+        assert_eq!(toc.prev_line(5), Some(3));
+        assert_eq!(toc.prev_line(0), None);
+
+        // Narrow terminals open a popup at the current section
+        toc.toggle(4);
+        assert!(toc.is_open());
+        toc.cursor_down();
+        assert_eq!(toc.choose(), Some(5));
+        assert!(!toc.is_open());
+    }
+}
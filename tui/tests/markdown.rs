@@ -0,0 +1,87 @@
+//! Property tests feeding arbitrary UTF-8 to the Markdown renderers
+//!
+//! Content will come from an API, so the renderers must not panic on any
+//! input, and what they draw must fit the width they were given without
+//! losing any text. The fuzz targets in `fuzz/` drive the same entry points
+//! with coverage guidance.
+
+use proptest::prelude::*;
+use ratatui::text::Line;
+use whoami_tui::components::render_post_lines;
+use whoami_tui::links;
+use whoami_tui::widgets::{markdown_text, Markdown};
+
+/// Arbitrary text, biased towards Markdown syntax and code fences
+fn markdown() -> impl Strategy<Value = String> {
+    let token = prop_oneof![
+        any::<String>(),
+        "\\PC{0,16}",
+        Just("\n".to_string()),
+        Just("\n```".to_string()),
+        Just("\n```rust\n".to_string()),
+        Just("\n# ".to_string()),
+        Just("\n## ".to_string()),
+        Just("\n- **".to_string()),
+        Just("\n1. ".to_string()),
+        Just("**".to_string()),
+        Just(":**".to_string()),
+        Just("*".to_string()),
+        Just("\n---\n".to_string()),
+        Just("[link](https://example.com)".to_string()),
+        Just(" me@example.com ".to_string()),
+        Just("\t".to_string()),
+        Just("日本語".to_string()),
+        Just("e\u{301}".to_string()),
+    ];
+    prop::collection::vec(token, 0..32).prop_map(|tokens| tokens.concat())
+}
+
+/// `content` without code fences and horizontal rules
+///
+/// Code blocks are clipped rather than wrapped and rules are drawn as wide as
+/// the text, so only the prose around them keeps every character.
+fn prose(content: &str) -> String {
+    content
+        .lines()
+        .filter(|line| !line.starts_with("```") && *line != "---")
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Characters of the text as drawn unwrapped, without whitespace
+fn drawn_text(content: &str) -> String {
+    content.lines().map(markdown_text).collect::<String>().split_whitespace().collect()
+}
+
+/// Characters of wrapped lines, without whitespace
+fn wrapped_text(lines: &[Line]) -> String {
+    lines.iter().map(Line::to_string).collect::<String>().split_whitespace().collect()
+}
+
+proptest! {
+    #[test]
+    fn prop_post_lines_fit_the_width(content in markdown(), width in 10usize..120) {
+        for line in render_post_lines(&content, width) {
+            prop_assert!(line.width() <= width, "{:?} is wider than {width}", line.to_string());
+        }
+    }
+
+    #[test]
+    fn prop_post_lines_keep_the_text(content in markdown(), width in 10usize..120) {
+        // Clickable links leave out the footnotes listing them
+        links::set_hyperlinks(true);
+        let content = prose(&content);
+        let lines = render_post_lines(&content, width);
+        prop_assert_eq!(wrapped_text(&lines), drawn_text(&content));
+    }
+
+    #[test]
+    fn prop_wrapped_markdown_keeps_the_text(content in markdown(), width in 1usize..120) {
+        let content = prose(&content);
+        let markdown = Markdown::wrapped(&content, width);
+        prop_assert_eq!(wrapped_text(&markdown.render_all()), drawn_text(&content));
+        for heading in markdown.headings() {
+            prop_assert!(heading.line < markdown.len());
+        }
+    }
+}
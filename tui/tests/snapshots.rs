@@ -0,0 +1,119 @@
+//! Snapshot tests rendering every view to a `TestBackend`
+//!
+//! Each case mounts a component with the fixture content in
+//! `tests/fixtures/content.json`, feeds it a scripted key sequence with a
+//! frame drawn after every key, as the runtime does, and compares the final
+//! screen at each size in [`SIZES`] with the text snapshot in
+//! `tests/snapshots`. Links are numbered, as in terminals without
+//! hyperlinks, whatever terminal runs the tests.
+//!
+//! After an intended change, review the new snapshots with
+//! `cargo insta review`, or accept all of them with
+//! `INSTA_UPDATE=always cargo test --test snapshots`.
+
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+use tuirealm::event::{Key, KeyEvent};
+use tuirealm::{Component, Event, NoUserEvent};
+use whoami_tui::components::{
+    Blog, Contact, Help, Home, Loading, Palette, Projects, Resume, Search, Settings,
+};
+use whoami_tui::msg::{Msg, ViewId};
+use whoami_tui::{content, links};
+
+/// Terminal sizes every view is rendered at, from the minimum up
+const SIZES: [(u16, u16); 4] = [(40, 12), (80, 24), (120, 40), (200, 60)];
+
+/// Content the views are drawn with, independent of `content.json`
+const FIXTURE: &str = include_str!("fixtures/content.json");
+
+/// Draw `component` after each of `keys` and return the last screen, one
+/// line per row with trailing spaces removed
+fn render<C>(component: &mut C, keys: &[Key], width: u16, height: u16) -> String
+where
+    C: Component<Msg, NoUserEvent>,
+{
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(|f| component.view(f, f.area())).unwrap();
+    for &key in keys {
+        component.on(Event::Keyboard(KeyEvent::from(key)));
+        terminal.draw(|f| component.view(f, f.area())).unwrap();
+    }
+
+    let buffer = terminal.backend().buffer();
+    (0..height)
+        .map(|y| {
+            let row: String = (0..width).map(|x| buffer[(x, y)].symbol()).collect();
+            row.trim_end().to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Compare the screens of a fresh component after `keys` at every size
+/// with the snapshots named `<name>_<width>x<height>`
+fn assert_screens<C>(name: &str, new: impl Fn() -> C, keys: &[Key])
+where
+    C: Component<Msg, NoUserEvent>,
+{
+    assert!(content::load(FIXTURE), "content was read before the fixture was loaded");
+    links::set_hyperlinks(false);
+    for (width, height) in SIZES {
+        let screen = render(&mut new(), keys, width, height);
+        insta::with_settings!({ prepend_module_to_snapshot => false, omit_expression => true }, {
+            insta::assert_snapshot!(format!("{name}_{width}x{height}"), screen);
+        });
+    }
+}
+
+/// Keys that type `text`
+fn typed(text: &str) -> Vec<Key> {
+    text.chars().map(Key::Char).collect()
+}
+
+#[test]
+fn test_loading_screens() {
+    assert_screens("loading", Loading::new, &[]);
+}
+
+#[test]
+fn test_home_screens() {
+    assert_screens("home", Home::new, &[]);
+    assert_screens("home_menu", Home::new, &[Key::Down, Key::Down]);
+}
+
+#[test]
+fn test_resume_screens() {
+    assert_screens("resume", Resume::new, &[]);
+    assert_screens("resume_page_down", Resume::new, &[Key::PageDown]);
+}
+
+#[test]
+fn test_blog_screens() {
+    assert_screens("blog", Blog::new, &[]);
+    assert_screens("blog_post", Blog::new, &[Key::Enter]);
+    assert_screens("blog_post_page_down", Blog::new, &[Key::Down, Key::Enter, Key::PageDown]);
+}
+
+#[test]
+fn test_projects_screens() {
+    assert_screens("projects", Projects::new, &[]);
+    assert_screens("project", Projects::new, &[Key::Enter]);
+}
+
+#[test]
+fn test_contact_screens() {
+    assert_screens("contact", Contact::new, &[]);
+    let mut keys = typed("Ada Lovelace");
+    keys.push(Key::Tab);
+    keys.extend(typed("ada@example.com"));
+    assert_screens("contact_draft", Contact::new, &keys);
+}
+
+#[test]
+fn test_overlay_screens() {
+    assert_screens("help", || Help::new(ViewId::Home), &[]);
+    assert_screens("settings", Settings::new, &[]);
+    assert_screens("search", Search::new, &typed("rails"));
+    assert_screens("palette", Palette::new, &typed("res"));
+}
@@ -0,0 +1,459 @@
+//! End-to-end tests of the `ssh-server` binary
+//!
+//! Each test boots the server on a free port, with its limits and timeouts
+//! set through the same environment variables as in production but scaled
+//! down to seconds, and talks to it with an in-process russh client. Shell
+//! sessions get a PTY; their output is fed to a VT parser so tests can wait
+//! for text to appear on the screen.
+
+use std::io::{BufRead, BufReader};
+use std::net::{Ipv4Addr, SocketAddr};
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use russh::client::{self, Handle};
+use russh::keys::PublicKey;
+use russh::{ChannelMsg, Disconnect};
+use tokio::time::{timeout, Instant};
+use whoami_tui::content::{POSTS, RESUME};
+use whoami_tui::export;
+
+/// Longest a test waits for the server to do something
+const WAIT: Duration = Duration::from_secs(15);
+
+/// A running `ssh-server`, killed when dropped
+struct Server {
+    process: Child,
+    addr: SocketAddr,
+    /// Banner the server sent the last client before authentication
+    banner: Arc<Mutex<Option<String>>>,
+}
+
+impl Server {
+    /// Start the server on a free port with extra environment variables
+    fn start(env: &[(&str, &str)]) -> Self {
+        let mut process = Command::new(env!("CARGO_BIN_EXE_ssh-server"))
+            .env("PORT", "0")
+            .envs(env.iter().copied())
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("start ssh-server");
+
+        // "Listening on 0.0.0.0:<port>"
+        let mut line = String::new();
+        let stdout = process.stdout.take().expect("piped stdout");
+        BufReader::new(stdout).read_line(&mut line).expect("read listening address");
+        let port = line
+            .trim()
+            .rsplit(':')
+            .next()
+            .and_then(|port| port.parse().ok())
+            .unwrap_or_else(|| panic!("no port in {line:?}"));
+
+        Self {
+            process,
+            addr: SocketAddr::from((Ipv4Addr::LOCALHOST, port)),
+            banner: Arc::default(),
+        }
+    }
+
+    /// Connect without authenticating
+    async fn connect(&self) -> Handle<Client> {
+        let config = Arc::new(client::Config::default());
+        let client = Client {
+            banner: self.banner.clone(),
+        };
+        client::connect(config, self.addr, client).await.expect("connect")
+    }
+
+    /// Processes the server has started for sessions, still running or not
+    /// yet reaped
+    #[cfg(target_os = "linux")]
+    fn sessions(&self) -> usize {
+        // Each thread lists the children it started
+        let tasks = format!("/proc/{}/task", self.process.id());
+        std::fs::read_dir(tasks)
+            .expect("list server threads")
+            .filter_map(|task| std::fs::read_to_string(task.ok()?.path().join("children")).ok())
+            .map(|children| children.split_whitespace().count())
+            .sum()
+    }
+
+    /// Connect and authenticate as `user`, returning `None` if refused
+    async fn login(&self, user: &str) -> Option<Handle<Client>> {
+        let mut handle = self.connect().await;
+        let auth = handle.authenticate_none(user).await.expect("authenticate");
+        auth.success().then_some(handle)
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.process.kill();
+        let _ = self.process.wait();
+    }
+}
+
+/// Client that trusts the server's ephemeral host key
+struct Client {
+    banner: Arc<Mutex<Option<String>>>,
+}
+
+impl client::Handler for Client {
+    type Error = russh::Error;
+
+    async fn auth_banner(
+        &mut self,
+        banner: &str,
+        _session: &mut client::Session,
+    ) -> Result<(), Self::Error> {
+        *self.banner.lock().unwrap() = Some(banner.to_string());
+        Ok(())
+    }
+
+    async fn check_server_key(&mut self, _key: &PublicKey) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+}
+
+/// An interactive session and the screen it has drawn so far
+struct Terminal {
+    channel: russh::Channel<client::Msg>,
+    parser: vt100::Parser,
+}
+
+impl Terminal {
+    /// Open a shell on a `cols` x `rows` PTY
+    ///
+    /// Keys sent before the TUI puts its terminal in raw mode are lost, so
+    /// wait for something to be drawn before typing.
+    async fn open(handle: &Handle<Client>, cols: u16, rows: u16) -> Self {
+        let channel = handle.channel_open_session().await.expect("open session");
+        channel
+            .request_pty(true, "xterm-256color", cols.into(), rows.into(), 0, 0, &[])
+            .await
+            .expect("request pty");
+        channel.request_shell(true).await.expect("request shell");
+        Self {
+            channel,
+            parser: vt100::Parser::new(rows, cols, 0),
+        }
+    }
+
+    fn screen(&self) -> String {
+        self.parser.screen().contents()
+    }
+
+    /// Read output until the screen shows `text`
+    async fn wait_for(&mut self, text: &str) {
+        let found = timeout(WAIT, async {
+            while !self.screen().contains(text) {
+                match self.channel.wait().await {
+                    Some(ChannelMsg::Data { data }) => self.parser.process(&data),
+                    Some(ChannelMsg::Eof | ChannelMsg::Close) | None => return false,
+                    Some(_) => {}
+                }
+            }
+            true
+        })
+        .await;
+        assert!(
+            matches!(found, Ok(true)),
+            "{text:?} never appeared on screen:\n{}",
+            self.screen()
+        );
+    }
+
+    /// Type `keys` into the session
+    async fn press(&self, keys: &str) {
+        self.channel.data(keys.as_bytes()).await.expect("send keys");
+    }
+
+    /// Wait for the server to end the session, meanwhile pressing `keys`
+    /// every `every` if given, and return when it did
+    async fn closed(&mut self, keep_alive: Option<(&str, Duration)>) -> Instant {
+        let every = keep_alive.map_or(WAIT, |(_, every)| every);
+        let mut next_key = Instant::now();
+        let ended = timeout(WAIT, async {
+            loop {
+                if Instant::now() >= next_key {
+                    if let Some((keys, _)) = keep_alive {
+                        let _ = self.channel.data(keys.as_bytes()).await;
+                    }
+                    next_key = Instant::now() + every;
+                }
+                let Ok(msg) = tokio::time::timeout_at(next_key, self.channel.wait()).await else {
+                    continue;
+                };
+                match msg {
+                    Some(ChannelMsg::Data { data }) => self.parser.process(&data),
+                    Some(ChannelMsg::Eof | ChannelMsg::Close) | None => return Instant::now(),
+                    Some(_) => {}
+                }
+            }
+        })
+        .await;
+        ended.expect("session was never closed")
+    }
+}
+
+/// Run `command` and return its output, error output and exit status
+async fn exec(handle: &Handle<Client>, command: &str) -> (Vec<u8>, String, Option<u32>) {
+    let mut channel = handle.channel_open_session().await.expect("open session");
+    channel.exec(true, command).await.expect("exec");
+    let (mut stdout, mut stderr, mut status) = (Vec::new(), Vec::new(), None);
+    timeout(WAIT, async {
+        while let Some(msg) = channel.wait().await {
+            match msg {
+                ChannelMsg::Data { data } => stdout.extend_from_slice(&data),
+                ChannelMsg::ExtendedData { data, .. } => stderr.extend_from_slice(&data),
+                ChannelMsg::ExitStatus { exit_status } => status = Some(exit_status),
+                ChannelMsg::Close => break,
+                _ => {}
+            }
+        }
+    })
+    .await
+    .expect("command never finished");
+    (stdout, String::from_utf8_lossy(&stderr).into_owned(), status)
+}
+
+#[tokio::test]
+async fn test_guest_sees_boot_screen() {
+    let server = Server::start(&[]);
+    let handle = server.login("guest").await.expect("guest login");
+    let mut terminal = Terminal::open(&handle, 80, 24).await;
+
+    terminal.wait_for("Establishing SSH session...").await;
+}
+
+#[tokio::test]
+async fn test_shell_follows_keys() {
+    let server = Server::start(&[]);
+    // The `home` username skips the boot screen
+    let handle = server.login("home").await.expect("login");
+    let mut terminal = Terminal::open(&handle, 100, 30).await;
+    terminal.wait_for("[r] Resume").await;
+
+    terminal.press("r").await;
+    terminal.wait_for("Experience").await;
+    terminal.wait_for("Home › ").await;
+
+    terminal.press("\x1b").await;
+    terminal.wait_for("[r] Resume").await;
+}
+
+#[cfg(target_os = "linux")]
+#[tokio::test]
+async fn test_session_process_ends_with_connection() {
+    let server = Server::start(&[]);
+    let handle = server.login("home").await.expect("login");
+    let mut terminal = Terminal::open(&handle, 80, 24).await;
+    terminal.wait_for("[r] Resume").await;
+    assert_eq!(server.sessions(), 1);
+
+    handle
+        .disconnect(Disconnect::ByApplication, "", "en")
+        .await
+        .unwrap();
+    let deadline = Instant::now() + WAIT;
+    while server.sessions() > 0 {
+        assert!(Instant::now() < deadline, "session process outlived the connection");
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
+
+#[tokio::test]
+async fn test_username_opens_deep_link() {
+    let server = Server::start(&[]);
+    let post = &POSTS[0];
+    let user = format!("blog-{}", post.slug);
+    let handle = server.login(&user).await.expect("login");
+    let mut terminal = Terminal::open(&handle, 120, 40).await;
+
+    terminal.wait_for(post.title).await;
+}
+
+#[tokio::test]
+async fn test_only_none_auth_is_accepted() {
+    let server = Server::start(&[]);
+    let mut handle = server.connect().await;
+
+    let password = handle.authenticate_password("guest", "hunter2").await.unwrap();
+    assert!(!password.success());
+    assert!(handle.authenticate_none("guest").await.unwrap().success());
+}
+
+#[tokio::test]
+async fn test_exec_commands_write_exports_and_screens() {
+    let server = Server::start(&[]);
+    let handle = server.login("guest").await.expect("login");
+
+    let (stdout, _, status) = exec(&handle, "resume --format md").await;
+    assert_eq!(stdout, RESUME.as_bytes());
+    assert_eq!(status, Some(0));
+
+    let (stdout, _, status) = exec(&handle, "contact.vcf").await;
+    assert_eq!(stdout, export::vcard().as_bytes());
+    assert_eq!(status, Some(0));
+
+    let (stdout, _, status) = exec(&handle, "render --view /resume --width 60 --plain").await;
+    let screen = String::from_utf8(stdout).unwrap();
+    assert!(screen.starts_with("Home › 📄 Resume\n"), "{screen}");
+    assert_eq!(screen.lines().count(), 24);
+    assert_eq!(status, Some(0));
+
+    let (stdout, stderr, _) = exec(&handle, "resume --format doc").await;
+    assert!(stdout.is_empty());
+    assert!(stderr.contains("doc"), "{stderr}");
+
+    let (stdout, stderr, _) = exec(&handle, "render --width 9999").await;
+    assert!(stdout.is_empty());
+    assert!(stderr.contains("9999"), "{stderr}");
+}
+
+#[tokio::test]
+async fn test_sftp_serves_content_tree() {
+    let server = Server::start(&[]);
+    let handle = server.login("guest").await.expect("login");
+    let channel = handle.channel_open_session().await.unwrap();
+    channel.request_subsystem(true, "sftp").await.unwrap();
+    let sftp = russh_sftp::client::SftpSession::new(channel.into_stream())
+        .await
+        .expect("start sftp");
+
+    let posts: Vec<String> = sftp
+        .read_dir("/posts")
+        .await
+        .unwrap()
+        .map(|entry| entry.file_name())
+        .collect();
+    assert_eq!(posts.len(), POSTS.len());
+    assert!(posts.contains(&format!("{}.md", POSTS[0].slug)));
+
+    let card = sftp.read("/contact.vcf").await.unwrap();
+    assert_eq!(card, export::vcard().as_bytes());
+    assert!(sftp.create("/notes.txt").await.is_err());
+}
+
+#[tokio::test]
+async fn test_max_sessions_rejects_extra_clients() {
+    let server = Server::start(&[("SSH_MAX_SESSIONS", "1")]);
+    let first = server.login("guest").await.expect("first login");
+    assert!(server.login("guest").await.is_none(), "second session accepted");
+
+    // The slot frees up once the first client leaves
+    first
+        .disconnect(Disconnect::ByApplication, "", "en")
+        .await
+        .unwrap();
+    let deadline = Instant::now() + WAIT;
+    while server.login("guest").await.is_none() {
+        assert!(Instant::now() < deadline, "slot never freed");
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+}
+
+#[tokio::test]
+async fn test_rate_limit_per_ip() {
+    let server = Server::start(&[("SSH_RATE_LIMIT_PER_MIN", "2")]);
+    assert!(server.login("guest").await.is_some());
+    assert!(server.login("guest").await.is_some());
+    assert!(server.login("guest").await.is_none(), "third login within a minute");
+}
+
+#[tokio::test]
+async fn test_idle_session_is_closed() {
+    let server = Server::start(&[("SSH_IDLE_TIMEOUT_SECS", "2")]);
+    let handle = server.login("home").await.expect("login");
+    let mut terminal = Terminal::open(&handle, 80, 24).await;
+    terminal.wait_for("[r] Resume").await;
+    terminal.press("j").await;
+    let last_key = Instant::now();
+
+    let closed = terminal.closed(None).await;
+    let idle = closed - last_key;
+    assert!(idle >= Duration::from_millis(1500), "closed after {idle:?} idle");
+}
+
+#[tokio::test]
+async fn test_active_session_is_closed_at_max_duration() {
+    let server = Server::start(&[("SSH_MAX_SESSION_SECS", "2"), ("SSH_IDLE_TIMEOUT_SECS", "60")]);
+    let handle = server.login("home").await.expect("login");
+    let opened = Instant::now();
+    let mut terminal = Terminal::open(&handle, 80, 24).await;
+
+    // Keep typing so the session is never idle
+    let closed = terminal.closed(Some(("j", Duration::from_millis(200)))).await;
+    let lasted = closed - opened;
+    assert!(lasted >= Duration::from_millis(1500), "closed after {lasted:?}");
+    assert!(lasted < Duration::from_secs(5), "closed after {lasted:?}");
+}
+
+#[tokio::test]
+async fn test_sessions_are_recorded_when_enabled() {
+    let dir = std::env::temp_dir().join(format!("whoami-ssh-casts-{}", std::process::id()));
+    let dir_env = dir.to_str().unwrap();
+    let server = Server::start(&[
+        ("RECORD_DIR", dir_env),
+        ("RECORD_KEEP", "2"),
+        ("RECORD_INPUT", "1"),
+    ]);
+    let handle = server.login("home").await.expect("login");
+    let banner = server.banner.lock().unwrap().clone().expect("recording banner");
+    assert!(banner.contains("the keys you type"), "{banner:?}");
+
+    let mut terminal = Terminal::open(&handle, 80, 24).await;
+    terminal.wait_for("[r] Resume").await;
+    terminal.press("r").await;
+    terminal.wait_for("Experience").await;
+    terminal.channel.window_change(100, 30, 0, 0).await.unwrap();
+    handle
+        .disconnect(Disconnect::ByApplication, "", "en")
+        .await
+        .unwrap();
+
+    // The recording is written out once the session has ended
+    let deadline = Instant::now() + WAIT;
+    let events = loop {
+        let cast = std::fs::read_dir(&dir)
+            .unwrap()
+            .filter_map(|entry| std::fs::read_to_string(entry.ok()?.path()).ok())
+            .next()
+            .unwrap_or_default();
+        let lines: Vec<serde_json::Value> =
+            cast.lines().filter_map(|line| serde_json::from_str(line).ok()).collect();
+        if lines.iter().any(|event| event[1] == "r") {
+            break lines;
+        }
+        assert!(Instant::now() < deadline, "recording never finished:\n{cast}");
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    };
+    assert_eq!(events[0]["version"], 2);
+    assert_eq!(events[0]["width"], 80);
+    assert_eq!(events[0]["height"], 24);
+    let data = |kind: &str| -> String {
+        events
+            .iter()
+            .filter(|event| event[1] == kind)
+            .filter_map(|event| event[2].as_str())
+            .collect()
+    };
+    assert!(data("o").contains("Resume"));
+    assert_eq!(data("i"), "r");
+    assert_eq!(data("r"), "100x30");
+
+    // Older recordings are removed beyond RECORD_KEEP
+    for _ in 0..2 {
+        let handle = server.login("home").await.expect("login");
+        let mut terminal = Terminal::open(&handle, 80, 24).await;
+        terminal.wait_for("[r] Resume").await;
+    }
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 2);
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let server = Server::start(&[]);
+    server.login("home").await.expect("login");
+    assert_eq!(*server.banner.lock().unwrap(), None);
+}